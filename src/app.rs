@@ -31,6 +31,9 @@ pub fn run() {
     // Diagnostics (TASK-0023): one process-wide sink gated on the verbose
     // flag; records render identically to terminal and log file.
     diagnostics::init(args.verbose);
+    if let Some(mode) = args.timestamps {
+        crate::cmd::override_timestamps(mode);
+    }
 
     if args.log_truncate_on_change && args.log_file.is_none() {
         stdout::failure(
@@ -78,6 +81,14 @@ pub fn run() {
     // config discovery, and command template preparation to it.
     let workspace_root = std::env::current_dir().expect("Failed to get current directory");
 
+    // Event trace (`--record-events`): every watcher batch is appended for
+    // `fzz replay`; commands without a watch loop record nothing.
+    if let Some(path) = args.record_events.as_deref() {
+        crate::event_trace::init(std::path::Path::new(path), &workspace_root)
+            .unwrap_or_else(|err| stdout::failure("Failed to prepare event trace", err));
+        stdout::info(&format!("Recording watcher events to {}", path));
+    }
+
     match args.action {
         // Commands
         Action::Check => check_config(&args.config),
@@ -158,7 +169,10 @@ pub fn run() {
             )
             .with_debounce(debounce)
             .with_backend(backend)
+            .with_settle(load_settle(&args.config))
+            .with_event_source(load_event_source(&args.config))
             .with_gitignore(load_respect_gitignore(&args.config))
+            .with_editor_noise(load_editor_noise(&args.config))
             .with_recovery_policy(effective_recovery_policy(&args, &args.config))
             .with_hooks(load_hooks(&args.config))
             .with_session_hooks(load_session_hooks(&args.config));
@@ -188,7 +202,10 @@ pub fn run() {
                     load_hooks(&args.config),
                     load_session_hooks(&args.config),
                     control_socket.as_deref().map(std::path::PathBuf::from),
-                );
+                )
+                .with_editor_noise(load_editor_noise(&args.config))
+                .with_settle(load_settle(&args.config))
+                .with_event_source(load_event_source(&args.config));
                 match tracker.observe(&runtime) {
                     crate::config_revision::RevisionDecision::New(revision) => {
                         watches.with_revision(revision)
@@ -214,7 +231,7 @@ pub fn run() {
             }
             stdout::info(&rules::available_targets(&rules));
         }
-        Action::Run { ref target } => {
+        Action::Run { .. } | Action::RunAffected { .. } => {
             let rules = load_rules(&args.config);
             if let Err(err) = rules::validate_rules(&rules) {
                 stdout::failure("Invalid config file.", err);
@@ -227,14 +244,38 @@ pub fn run() {
                 concurrency,
             )
             .with_debounce(debounce)
+            .with_gitignore(load_respect_gitignore(&args.config))
+            .with_editor_noise(load_editor_noise(&args.config))
             .with_recovery_policy(effective_recovery_policy(&args, &args.config));
-            let plan = match watches.run_target_plan(target) {
-                Ok(plan) => plan,
-                Err(crate::watches::RunTargetError::Missing(_)) => stdout::failure(
-                    &format!("No target found for '{}'", target),
-                    rules::available_targets(&rules),
-                ),
-                Err(error) => stdout::failure("Cannot run target", error.to_string()),
+            let target_failure = |error: crate::watches::RunTargetError| -> ! {
+                match error {
+                    crate::watches::RunTargetError::Missing(target) => stdout::failure(
+                        &format!("No target found for '{}'", target),
+                        rules::available_targets(&rules),
+                    ),
+                    error => stdout::failure("Cannot run target", error.to_string()),
+                }
+            };
+            let plan = match args.action {
+                Action::RunAffected {
+                    ref target,
+                    ref source,
+                } => {
+                    let paths = cli::run::affected_paths(&workspace_root, source)
+                        .unwrap_or_else(|err| stdout::failure("Cannot list changed paths", err));
+                    let (plan, affected) = watches
+                        .affected_plan(&paths, target.as_deref())
+                        .unwrap_or_else(|error| target_failure(error));
+                    stdout::info(&cli::run::affected_summary(paths.len(), &affected));
+                    if plan.is_empty() {
+                        return;
+                    }
+                    plan
+                }
+                Action::Run { ref target } => watches
+                    .run_target_plan(target)
+                    .unwrap_or_else(|error| target_failure(error)),
+                _ => unreachable!("matched run actions above"),
             };
 
             let shutdown = install_shutdown_signal_handler(None);
@@ -256,7 +297,11 @@ pub fn run() {
             .with_hooks(load_hooks(&args.config))
             .with_recovery_policy(effective_recovery_policy(&args, &args.config))
             .with_recovery_approval(Arc::new(crate::approval::TtyRecoveryApproval));
-            let result = command.execute(plan, target);
+            let result = match args.action {
+                Action::RunAffected { ref source, .. } => command.execute_affected(plan, source),
+                Action::Run { ref target } => command.execute(plan, target),
+                _ => unreachable!("matched run actions above"),
+            };
             let signal_exit = shutdown.load(std::sync::atomic::Ordering::SeqCst);
             if signal_exit != 0 {
                 process::exit(signal_exit);
@@ -280,6 +325,7 @@ pub fn run() {
             .with_debounce(load_debounce(&args.config))
             .with_backend(load_watch_backend(&args.config))
             .with_gitignore(load_respect_gitignore(&args.config))
+            .with_editor_noise(load_editor_noise(&args.config))
             .with_recovery_policy(effective_recovery_policy(&args, &args.config))
             .with_hooks(load_hooks(&args.config));
             let result = watches.explain(path);
@@ -290,6 +336,28 @@ pub fn run() {
             stdout::info(&explain_output(path, &result, &facts, &watches));
         }
 
+        Action::Replay { ref file } => {
+            let rules = load_rules(&args.config);
+            if let Err(err) = rules::validate_rules(&rules) {
+                stdout::failure("Invalid config file.", err);
+            }
+            let watches = Watches::with_root_and_concurrency(
+                rules.clone(),
+                workspace_root.clone(),
+                effective_concurrency(&args, &args.config),
+            )
+            .with_debounce(load_debounce(&args.config))
+            .with_gitignore(load_respect_gitignore(&args.config))
+            .with_editor_noise(load_editor_noise(&args.config));
+            execute(ReplayCommand::new(
+                watches,
+                std::path::PathBuf::from(file),
+                args.verbose,
+                !args.no_run_on_init,
+                !matches!(args.on_busy, OnBusy::Wait),
+            ));
+        }
+
         // Ad-hoc command provided via `fzz exec -- PROGRAM ARG...`. The argv
         // is preserved end to end: it is never joined and re-parsed through a
         // shell. Shell operators only work when the caller explicitly invokes
@@ -353,6 +421,23 @@ fn explain_output(
     output.push_str(path);
     output.push('\n');
 
+    match &result.editor_noise {
+        Some(crate::editor_noise::Noise::Drop { editor }) => {
+            output.push_str(&format!(
+                "  editor noise: {} artifact, suppressed (on.editor_noise)\n",
+                editor
+            ));
+            return output;
+        }
+        Some(crate::editor_noise::Noise::Target { editor, target }) => {
+            output.push_str(&format!(
+                "  editor noise: {} atomic save of {} (on.editor_noise)\n",
+                editor, target
+            ));
+        }
+        None => {}
+    }
+
     if result.matched.is_empty() && result.ignored.is_empty() {
         output.push_str("  unmatched: no configured task watches this path\n");
         // Contract §8: for a future/missing path, name the subscription root
@@ -380,6 +465,12 @@ fn explain_output(
             if rule.recovery_available {
                 output.push_str("        recovery: configured (approval required)\n");
             }
+            let (signal, grace) = crate::process_owner::stop_policy(rule.stop);
+            output.push_str(&format!(
+                "        stop: {} then SIGKILL after {}\n",
+                signal.as_str(),
+                crate::config::format_grace(grace)
+            ));
             for change in &rule.change_patterns {
                 output.push_str(&format!("        change: {}\n", change));
             }
@@ -481,6 +572,16 @@ fn check_config(config_file: &Option<String>) {
     {
         stdout::info(&format!("debounce: {:?}", debounce));
     }
+    if let Some(settle) = config::settle_from_file(&config_path)
+        .unwrap_or_else(|err| stdout::failure("Invalid settle config", err))
+    {
+        stdout::info(&format!(
+            "settle: {:?} (max {:?})",
+            settle.interval, settle.max
+        ));
+    }
+    let _editor_noise = config::editor_noise_from_file(&config_path)
+        .unwrap_or_else(|err| stdout::failure("Invalid editor noise config", err));
     let _recovery_policy = config::recovery_policy_from_file(&config_path)
         .unwrap_or_else(|err| stdout::failure("Invalid recovery policy config", err));
     let concurrency = config::concurrency_from_file(&config_path)
@@ -636,6 +737,57 @@ fn load_respect_gitignore(config_file: &Option<String>) -> bool {
         .unwrap_or_else(|err| stdout::failure("Invalid gitignore config", err))
 }
 
+/// The `on.settle` write-settle policy; default none.
+fn load_settle(config_file: &Option<String>) -> Option<crate::watcher::Settle> {
+    let path = match config_file.as_deref() {
+        Some(path) => Some(path.to_owned()),
+        None if std::path::Path::new(cli::watch::DEFAULT_FILENAME).exists() => {
+            Some(cli::watch::DEFAULT_FILENAME.to_owned())
+        }
+        None => {
+            let yaml = cli::watch::DEFAULT_FILENAME.replace(".yaml", ".yml");
+            std::path::Path::new(&yaml).exists().then_some(yaml)
+        }
+    };
+    config::settle_from_file(&path?)
+        .unwrap_or_else(|err| stdout::failure("Invalid settle config", err))
+}
+
+/// The `on.event_source` external event source; default none.
+fn load_event_source(config_file: &Option<String>) -> Option<crate::watcher::EventSource> {
+    let path = match config_file.as_deref() {
+        Some(path) => Some(path.to_owned()),
+        None if std::path::Path::new(cli::watch::DEFAULT_FILENAME).exists() => {
+            Some(cli::watch::DEFAULT_FILENAME.to_owned())
+        }
+        None => {
+            let yaml = cli::watch::DEFAULT_FILENAME.replace(".yaml", ".yml");
+            std::path::Path::new(&yaml).exists().then_some(yaml)
+        }
+    };
+    config::event_source_from_file(&path?)
+        .unwrap_or_else(|err| stdout::failure("Invalid event source config", err))
+}
+
+/// Whether `on.editor_noise` is enabled; default true.
+fn load_editor_noise(config_file: &Option<String>) -> bool {
+    let path = match config_file.as_deref() {
+        Some(path) => Some(path.to_owned()),
+        None if std::path::Path::new(cli::watch::DEFAULT_FILENAME).exists() => {
+            Some(cli::watch::DEFAULT_FILENAME.to_owned())
+        }
+        None => {
+            let yaml = cli::watch::DEFAULT_FILENAME.replace(".yaml", ".yml");
+            std::path::Path::new(&yaml).exists().then_some(yaml)
+        }
+    };
+    let Some(path) = path else {
+        return true;
+    };
+    config::editor_noise_from_file(&path)
+        .unwrap_or_else(|err| stdout::failure("Invalid editor noise config", err))
+}
+
 fn effective_recovery_policy(
     args: &Arguments,
     config_file: &Option<String>,
@@ -784,7 +936,9 @@ fn execute_watch_command(
 
     let verbose = args.verbose;
     let fail_fast = args.fail_fast || environment::is_enabled("FUNZZY_BAIL");
+    let restart_affected = matches!(args.on_busy, OnBusy::RestartAffected);
     let non_block = matches!(args.on_busy, OnBusy::Restart)
+        || restart_affected
         || environment::is_enabled("FUNZZY_NON_BLOCK")
         || args.control_socket.is_some();
 
@@ -797,6 +951,7 @@ fn execute_watch_command(
             args.log_file.as_deref(),
             run_on_init,
             non_block,
+            restart_affected,
         );
     }
     // Both wait and restart watch modes share one signal notification and
@@ -812,6 +967,7 @@ fn execute_watch_command(
             args.control_socket.map(std::path::PathBuf::from),
             event_stream,
         )
+        .with_restart_affected(restart_affected)
         .with_reload(coordinator.clone())
         .with_reload_ready(reload_session.take_ready())
         .with_shutdown(std::sync::Arc::clone(&shutdown))
@@ -880,13 +1036,20 @@ fn emit_startup_record(
     log_file: Option<&str>,
     run_on_init: bool,
     non_block: bool,
+    restart_affected: bool,
 ) {
     let config_path = config_file_paths
         .first()
         .cloned()
         .unwrap_or_else(|| "default".to_owned());
     let watch_roots = watches.paths_to_watch().unwrap_or_default().join(",");
-    let policy = if non_block { "restart" } else { "wait" };
+    let policy = if restart_affected {
+        "restart-affected"
+    } else if non_block {
+        "restart"
+    } else {
+        "wait"
+    };
     diagnostics::debug(&diagnostics::Record {
         source: Some("config"),
         decision: Some("startup"),
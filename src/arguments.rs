@@ -21,6 +21,18 @@ pub enum OnBusy {
     Wait,
     /// Cancel the active child and schedule the newest generation.
     Restart,
+    /// Cancel only the active jobs the newest generation schedules again;
+    /// the rest keep running to completion.
+    RestartAffected,
+}
+
+/// Where `fzz run` reads its synthetic change batch from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AffectedSource {
+    /// `--changed-since REF`: `git diff --name-only REF` from the root.
+    ChangedSince(String),
+    /// `--paths-from FILE|-`: one path per line; `-` reads stdin.
+    PathsFrom(String),
 }
 
 /// Semantic application action selected from the parsed subcommand.
@@ -44,6 +56,12 @@ pub enum Action {
     },
     /// `fzz run TARGET`: execute selected configured tasks once, locally.
     Run { target: String },
+    /// `fzz run [TARGET] --changed-since REF | --paths-from FILE|-`: run
+    /// only the jobs whose change globs match the given paths, once.
+    RunAffected {
+        target: Option<String>,
+        source: AffectedSource,
+    },
     /// `fzz explain PATH`: print which tasks a path matches or is ignored by.
     Explain { path: String },
     /// `fzz init [--template PROFILE]`: create a starter config file.
@@ -64,6 +82,9 @@ pub enum Action {
     },
     /// `fzz exec -- PROGRAM ARG...`: ad-hoc command over stdin-supplied paths.
     Exec { command: Vec<String> },
+    /// `fzz replay FILE`: feed a recorded event trace through the watch
+    /// loop and print the generations it would schedule, running nothing.
+    Replay { file: String },
 }
 
 /// Parser-owned, semantic application arguments.
@@ -75,12 +96,16 @@ pub struct Arguments {
     pub log_file: Option<String>,
     /// NDJSON run-event stream destination (TASK-0039); None = no stream.
     pub events_file: Option<String>,
+    /// Watcher event trace destination (`--record-events`); None = off.
+    pub record_events: Option<String>,
     pub control_socket: Option<String>,
     pub on_busy: OnBusy,
     pub no_run_on_init: bool,
     pub fail_fast: bool,
     /// Explicit CLI override for configured recovery policy.
     pub recovery_policy: Option<crate::config::RecoveryPolicy>,
+    /// Explicit CLI override for `execution.timestamps`.
+    pub timestamps: Option<crate::rules::Timestamps>,
     pub sequential: bool,
     pub verbose: bool,
 }
@@ -132,6 +157,7 @@ impl Arguments {
             .filter(|value| !value.is_empty());
         let log_file = matches.get_one::<String>("log_file").cloned();
         let events_file = matches.get_one::<String>("events_file").cloned();
+        let record_events = matches.get_one::<String>("record_events").cloned();
         let control_socket = matches.get_one::<String>("control_socket").cloned();
 
         let action = match matches.subcommand() {
@@ -179,11 +205,22 @@ impl Arguments {
                 action
             }
             Some(("run", sub)) => {
-                let target = sub
-                    .get_one::<String>("target")
+                let target = sub.get_one::<String>("target").cloned();
+                let source = sub
+                    .get_one::<String>("changed_since")
                     .cloned()
-                    .expect("target is required by clap");
-                Action::Run { target }
+                    .map(AffectedSource::ChangedSince)
+                    .or_else(|| {
+                        sub.get_one::<String>("paths_from")
+                            .cloned()
+                            .map(AffectedSource::PathsFrom)
+                    });
+                match source {
+                    Some(source) => Action::RunAffected { target, source },
+                    None => Action::Run {
+                        target: target.expect("target is required by clap"),
+                    },
+                }
             }
             Some(("explain", sub)) => {
                 let path = sub
@@ -283,6 +320,12 @@ impl Arguments {
                     .unwrap_or_default();
                 Action::Exec { command }
             }
+            Some(("replay", sub)) => Action::Replay {
+                file: sub
+                    .get_one::<String>("file")
+                    .cloned()
+                    .expect("file is required by clap"),
+            },
             Some((other, _)) => {
                 unreachable!("clap rejects unknown subcommand {other:?} before dispatch")
             }
@@ -294,6 +337,7 @@ impl Arguments {
             log_truncate_on_change: matches.get_flag("log_truncate_on_change"),
             log_file,
             events_file,
+            record_events,
             control_socket,
             on_busy: if matches.get_flag("restart") {
                 OnBusy::Restart
@@ -304,6 +348,7 @@ impl Arguments {
                     .unwrap_or("wait")
                 {
                     "restart" => OnBusy::Restart,
+                    "restart-affected" => OnBusy::RestartAffected,
                     _ => OnBusy::Wait,
                 }
             },
@@ -315,6 +360,9 @@ impl Arguments {
                     _ => crate::config::RecoveryPolicy::Prompt,
                 },
             ),
+            timestamps: matches
+                .get_one::<String>("timestamps")
+                .and_then(|mode| crate::rules::Timestamps::parse(mode)),
             sequential: match matches.subcommand() {
                 Some(("run" | "watch", sub)) => sub.get_flag("sequential"),
                 _ => false,
@@ -371,10 +419,15 @@ pub fn command() -> Command {
                 .global(true)
                 .value_name("POLICY")
                 .value_parser(clap::builder::PossibleValuesParser::new([
-                    "wait", "restart",
+                    "wait",
+                    "restart",
+                    "restart-affected",
                 ]))
                 .default_value("wait")
-                .help("What to do when a change arrives while a run is active (wait|restart)."),
+                .help(
+                    "What to do when a change arrives while a run is active \
+                     (wait|restart|restart-affected).",
+                ),
         )
         .arg(
             Arg::new("restart")
@@ -422,6 +475,16 @@ pub fn command() -> Command {
                 .value_parser(clap::builder::PossibleValuesParser::new(["prompt", "skip"]))
                 .help("Recovery policy for failed jobs (prompt|skip); overrides execution.recovery_policy."),
         )
+        .arg(
+            Arg::new("timestamps")
+                .long("timestamps")
+                .global(true)
+                .value_name("MODE")
+                .value_parser(clap::builder::PossibleValuesParser::new(
+                    crate::rules::Timestamps::VALUES,
+                ))
+                .help("Prefix job output lines with wall-clock or elapsed time (wall|elapsed|none); overrides execution.timestamps."),
+        )
         .arg(
             Arg::new("log_truncate_on_change")
                 .short('T')
@@ -447,6 +510,14 @@ pub fn command() -> Command {
                 .value_parser(clap::builder::ValueParser::string())
                 .help("Append NDJSON run events (started/tick/task_terminal/finished/cancelled) to the specified file (TASK-0039)."),
         )
+        .arg(
+            Arg::new("record_events")
+                .long("record-events")
+                .global(true)
+                .value_name("file")
+                .value_parser(clap::builder::ValueParser::string())
+                .help("Record raw watcher events and normalized batches to the specified file for `fzz replay`."),
+        )
         .arg(
             Arg::new("control_socket")
                 .long("control-socket")
@@ -521,7 +592,7 @@ pub fn command() -> Command {
             Command::new("run")
                 .about("Run configured tasks once in this process (no watcher or control socket).")
                 .long_about(
-                    "Run configured tasks once in this process, then exit with their combined outcome.\n\nThis is local execution. `fzz control run TARGET` requests work from an existing watcher. Path filtering is not supported; TARGET selects a full configured workflow.",
+                    "Run configured tasks once in this process, then exit with their combined outcome.\n\nThis is local execution. `fzz control run TARGET` requests work from an existing watcher. Path filtering is not supported as positional input; TARGET selects a full configured workflow. Use --changed-since or --paths-from to run only the jobs whose change globs match a list of paths (TARGET is then optional and narrows the candidates).\n\nExamples:\n  fzz run --changed-since origin/main\n  git diff --name-only HEAD~3 | fzz run @ci --paths-from -",
                 )
                 .version(env!("CARGO_PKG_VERSION"))
                 .arg(
@@ -530,11 +601,28 @@ pub fn command() -> Command {
                         .action(ArgAction::SetTrue)
                         .help("Run the selected workflow with effective concurrency 1."),
                 )
+                .arg(
+                    Arg::new("changed_since")
+                        .long("changed-since")
+                        .value_name("REF")
+                        .num_args(1)
+                        .conflicts_with("paths_from")
+                        .value_parser(clap::builder::ValueParser::string())
+                        .help("Run only jobs affected by `git diff --name-only REF`."),
+                )
+                .arg(
+                    Arg::new("paths_from")
+                        .long("paths-from")
+                        .value_name("FILE")
+                        .num_args(1)
+                        .value_parser(clap::builder::ValueParser::string())
+                        .help("Run only jobs affected by the paths listed in FILE (`-` for stdin)."),
+                )
                 .arg(
                     Arg::new("target")
                         .value_name("TARGET")
                         .num_args(1)
-                        .required(true)
+                        .required_unless_present_any(["changed_since", "paths_from"])
                         .value_parser(clap::builder::ValueParser::string())
                         .help("Exact task name, @tag, or unambiguous name substring."),
                 ),
@@ -632,6 +720,22 @@ pub fn command() -> Command {
                         .help("Path to explain (relative or absolute)."),
                 ),
         )
+        .subcommand(
+            Command::new("replay")
+                .about("Replay a recorded event trace as a dry run.")
+                .version(env!("CARGO_PKG_VERSION"))
+                .long_about(
+                    "Feed a trace written by `--record-events FILE` through the real watch loop using the current config. Batches keep their recorded boundaries and timestamps (no waiting, no settle, no content gate) and nothing is executed: each generation the watcher would schedule is printed with its trigger and selected jobs. Attach the trace and config to a bug report to reproduce timing-dependent routing.",
+                )
+                .arg(
+                    Arg::new("file")
+                        .value_name("FILE")
+                        .num_args(1)
+                        .required(true)
+                        .value_parser(clap::builder::ValueParser::string())
+                        .help("Trace file written by --record-events."),
+                ),
+        )
         .subcommand(
             Command::new("exec")
                 .about("Run an ad-hoc command over stdin-supplied paths.")
@@ -1010,6 +1114,25 @@ mod tests {
         assert!(parse(&["run", "@quick", "src/lib.rs"]).is_err());
     }
 
+    #[test]
+    fn run_affected_flags_make_target_optional() {
+        assert_eq!(
+            parse_action(&["run", "--changed-since", "origin/main"]),
+            Action::RunAffected {
+                target: None,
+                source: AffectedSource::ChangedSince("origin/main".to_owned()),
+            }
+        );
+        assert_eq!(
+            parse_action(&["run", "@ci", "--paths-from", "-"]),
+            Action::RunAffected {
+                target: Some("@ci".to_owned()),
+                source: AffectedSource::PathsFrom("-".to_owned()),
+            }
+        );
+        assert!(parse(&["run", "--changed-since", "main", "--paths-from", "-"]).is_err());
+    }
+
     #[test]
     fn recovery_policy_override_accepts_only_prompt_or_skip() {
         let args = parse(&["watch", "--recovery-policy", "skip"]).expect("parse");
@@ -1025,6 +1148,16 @@ mod tests {
         assert!(parse(&["watch", "--recovery-policy", "auto"]).is_err());
     }
 
+    #[test]
+    fn timestamps_override_accepts_wall_elapsed_or_none() {
+        let args = parse(&["watch", "--timestamps", "elapsed"]).expect("parse");
+        assert_eq!(args.timestamps, Some(crate::rules::Timestamps::Elapsed));
+        let args = parse(&["run", "@quick", "--timestamps", "none"]).expect("parse");
+        assert_eq!(args.timestamps, Some(crate::rules::Timestamps::None));
+        assert_eq!(parse(&["watch"]).expect("parse").timestamps, None);
+        assert!(parse(&["watch", "--timestamps", "local"]).is_err());
+    }
+
     #[test]
     fn run_sequential_flag_sets_sequential() {
         let args = parse(&["run", "@quick", "--sequential"]).expect("parse");
@@ -1725,6 +1858,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn replay_takes_a_trace_and_record_events_is_global() {
+        assert_eq!(
+            parse_action(&["replay", "trace.ndjson"]),
+            Action::Replay {
+                file: "trace.ndjson".to_string()
+            }
+        );
+        assert!(parse(&["replay"]).is_err());
+        let args = parse(&["watch", "--record-events", "trace.ndjson"]).expect("parse");
+        assert_eq!(args.record_events.as_deref(), Some("trace.ndjson"));
+    }
+
     #[test]
     fn unknown_subcommand_fails() {
         // The bare ad-hoc form `fzz '<command>'` is removed in V2; the first
@@ -1812,6 +1958,12 @@ mod tests {
         assert_eq!(args.on_busy, OnBusy::Restart);
     }
 
+    #[test]
+    fn on_busy_restart_affected_selects_restart_affected() {
+        let args = parse(&["--on-busy", "restart-affected"]).expect("parse");
+        assert_eq!(args.on_busy, OnBusy::RestartAffected);
+    }
+
     #[test]
     fn on_busy_invalid_value_fails() {
         assert!(parse(&["--on-busy", "bogus"]).is_err());
@@ -2019,6 +2171,7 @@ mod config_command_tests {
             "completions",
             "config",
             "explain",
+            "replay",
             "exec",
             "control",
             "ctl",
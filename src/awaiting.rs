@@ -177,7 +177,12 @@ impl AwaitCoordinator {
             Event::Tick { .. } => {}
             // Per-task outcomes live in `WatcherState`; the coordinator only
             // tracks generation/batch/pending facts for freshness (TASK-0050).
-            Event::TaskTerminal { .. } | Event::RecoveryPhase { .. } => {}
+            Event::TaskTerminal { .. }
+            | Event::TaskWaiting { .. }
+            | Event::ServiceState { .. }
+            | Event::ServiceAction { .. }
+            | Event::Diagnostics { .. }
+            | Event::RecoveryPhase { .. } => {}
         }
     }
 
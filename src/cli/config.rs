@@ -78,6 +78,68 @@ fn schema_property(spec: &OptionSpec) -> Value {
             "type": "object",
             "additionalProperties": { "type": "string" }
         }),
+        SpecKind::IntMap => json!({
+            "type": "object",
+            "additionalProperties": { "type": "integer", "minimum": 1 }
+        }),
+        SpecKind::Range(minimum, maximum) => json!({
+            "type": "integer",
+            "minimum": minimum,
+            "maximum": maximum
+        }),
+        SpecKind::RangeList(minimum, maximum) => json!({
+            "type": ["integer", "array"],
+            "minimum": minimum,
+            "maximum": maximum,
+            "items": { "type": "integer", "minimum": minimum, "maximum": maximum }
+        }),
+        SpecKind::Fields(fields) => {
+            let properties: serde_json::Map<String, Value> = fields
+                .iter()
+                .map(|field| (field.to_string(), json!({ "type": ["string", "integer"] })))
+                .collect();
+            json!({
+                "type": "object",
+                "properties": properties,
+                "additionalProperties": false
+            })
+        }
+        SpecKind::NamesOrFields(names, fields) => {
+            let properties: serde_json::Map<String, Value> = fields
+                .iter()
+                .map(|field| (field.to_string(), json!({ "type": "string" })))
+                .collect();
+            json!({
+                "type": ["string", "array"],
+                "items": {
+                    "anyOf": [
+                        { "type": "string", "enum": names },
+                        {
+                            "type": "object",
+                            "properties": properties,
+                            "additionalProperties": false
+                        }
+                    ]
+                }
+            })
+        }
+        SpecKind::Steps => json!({
+            "type": ["string", "array"],
+            "items": {
+                "anyOf": [
+                    { "type": "string" },
+                    {
+                        "type": "object",
+                        "properties": {
+                            "run": { "type": "string" },
+                            "continue_on_error": { "type": "boolean" }
+                        },
+                        "required": ["run"],
+                        "additionalProperties": false
+                    }
+                ]
+            }
+        }),
     };
     prop["description"] = json!(spec.help);
     // Literal defaults only: textual defaults like "machine parallelism" are
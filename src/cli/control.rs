@@ -528,29 +528,64 @@ pub fn render_status(status: &StatusSnapshot) -> String {
 
 /// Renders ordered executor snapshots without adding a timer or sorting them.
 /// Empty task data is the explicit legacy path and retains the prior output.
+/// Steps of multi-command jobs are indented under their job.
 fn render_job_timing_rows(tasks: &[TerminalTaskSnapshot]) -> String {
     if tasks.is_empty() {
         return String::new();
     }
-    let identities: Vec<String> = tasks
-        .iter()
-        .map(|task| {
-            if task.id == task.name {
-                task.name.clone()
-            } else {
-                format!("[{}] {}", task.id, task.name)
-            }
-        })
-        .collect();
-    let name_width = identities
+    let mut entries: Vec<(String, String, Option<u64>, String)> = vec![];
+    for task in tasks {
+        let identity = if task.id == task.name {
+            task.name.clone()
+        } else {
+            format!("[{}] {}", task.id, task.name)
+        };
+        let mut notes: Vec<String> = task
+            .waiting
+            .iter()
+            .chain(&task.failure_reason)
+            .take(1)
+            .cloned()
+            .collect();
+        match task.restarts {
+            Some(1) => notes.push("1 restart".to_owned()),
+            Some(restarts) if restarts > 1 => notes.push(format!("{restarts} restarts")),
+            _ => {}
+        }
+        if let Some(health) = &task.health {
+            notes.push(format!("health {health}"));
+        }
+        if let Some(generation) = task.superseded_by {
+            notes.push(format!("superseded by generation {generation}"));
+        }
+        let reason = if notes.is_empty() {
+            String::new()
+        } else {
+            format!("  ({})", notes.join("; "))
+        };
+        entries.push((identity, task.state.clone(), task.duration_ms, reason));
+        for step in &task.steps {
+            let state = match step.exit_code {
+                Some(code) if code != 0 => format!("{} ({code})", step.state),
+                _ => step.state.clone(),
+            };
+            entries.push((
+                format!("  {}. {}", step.index, step.command),
+                state,
+                step.duration_ms,
+                String::new(),
+            ));
+        }
+    }
+    let name_width = entries
         .iter()
-        .map(String::len)
+        .map(|(identity, ..)| identity.len())
         .max()
         .unwrap_or(3)
         .max("JOB".len());
-    let state_width = tasks
+    let state_width = entries
         .iter()
-        .map(|task| task.state.len())
+        .map(|(_, state, ..)| state.len())
         .max()
         .unwrap_or(6)
         .max("RESULT".len());
@@ -558,14 +593,12 @@ fn render_job_timing_rows(tasks: &[TerminalTaskSnapshot]) -> String {
         "jobs:\n  {:<name_width$}  {:<state_width$}  DURATION\n",
         "JOB", "RESULT"
     );
-    for (identity, task) in identities.iter().zip(tasks) {
-        let duration = task
-            .duration_ms
+    for (identity, state, duration_ms, reason) in &entries {
+        let duration = duration_ms
             .map(format_duration)
             .unwrap_or_else(|| "-".to_owned());
         rows.push_str(&format!(
-            "  {identity:<name_width$}  {:<state_width$}  {duration}\n",
-            task.state
+            "  {identity:<name_width$}  {state:<state_width$}  {duration}{reason}\n"
         ));
     }
     rows
@@ -810,6 +843,18 @@ pub fn render_output(output: &OutputSnapshot) -> String {
     }
     for task in &output.tasks {
         rendered.push_str(&format!("task: {}\n", task.id));
+        if let Some(tests) = &task.tests {
+            rendered.push_str(&format!(
+                "  tests: {} passed, {} failed, {} skipped\n",
+                tests.passed, tests.failed, tests.skipped
+            ));
+            for (name, message) in &tests.failures {
+                match message {
+                    Some(message) => rendered.push_str(&format!("    FAILED {name}: {message}\n")),
+                    None => rendered.push_str(&format!("    FAILED {name}\n")),
+                }
+            }
+        }
         for (name, stream) in [("stdout", &task.stdout), ("stderr", &task.stderr)] {
             let Some(stream) = stream else { continue };
             rendered.push_str(&format!(
@@ -886,12 +931,36 @@ mod tests {
                     name: "lint".to_string(),
                     state: "failed".to_string(),
                     duration_ms: Some(1_800),
+                    waiting: None,
+                    failure_reason: None,
+                    restarts: None,
+                    health: None,
+                    steps: vec![],
+                    superseded_by: None,
                 },
                 TerminalTaskSnapshot {
                     id: "docs".to_string(),
                     name: "docs".to_string(),
                     state: "cancelled".to_string(),
                     duration_ms: None,
+                    waiting: None,
+                    failure_reason: None,
+                    restarts: None,
+                    health: None,
+                    steps: vec![],
+                    superseded_by: None,
+                },
+                TerminalTaskSnapshot {
+                    id: "api".to_string(),
+                    name: "api".to_string(),
+                    state: "ready".to_string(),
+                    duration_ms: None,
+                    waiting: None,
+                    failure_reason: None,
+                    restarts: Some(2),
+                    health: Some("passing".to_string()),
+                    steps: vec![],
+                    superseded_by: None,
                 },
             ]),
             effective_concurrency: Some(1),
@@ -910,6 +979,7 @@ mod tests {
         assert!(rendered.contains("[checks#1] lint"));
         assert!(rendered.contains("1.8s"));
         assert!(rendered.contains("docs") && rendered.contains("-"));
+        assert!(rendered.contains("(2 restarts; health passing)"));
         assert!(!rendered.contains("(none)"));
     }
 
@@ -976,7 +1046,9 @@ mod tests {
 
     #[test]
     fn render_output_shows_tasks_streams_and_bounds() {
-        use crate::control_client::{OutputSnapshot, RetrievedTaskSnapshot, StreamSnapshot};
+        use crate::control_client::{
+            OutputSnapshot, RetrievedTaskSnapshot, StreamSnapshot, TestsSnapshot,
+        };
         let output = OutputSnapshot {
             generation: 7,
             resolved_task: None,
@@ -993,6 +1065,12 @@ mod tests {
                     truncated: false,
                 }),
                 stderr: None,
+                tests: Some(TestsSnapshot {
+                    passed: 4,
+                    failed: 1,
+                    skipped: 0,
+                    failures: vec![("parser::empty".to_string(), Some("boom".to_string()))],
+                }),
             }],
         };
         let rendered = render_output(&output);
@@ -1001,6 +1079,8 @@ mod tests {
         assert!(rendered.contains("stdout: 2 lines, retained 18 bytes, observed 18 bytes:"));
         assert!(rendered.contains("line one"));
         assert!(rendered.contains("line two"));
+        assert!(rendered.contains("tests: 4 passed, 1 failed, 0 skipped"));
+        assert!(rendered.contains("FAILED parser::empty: boom"));
     }
 
     #[test]
@@ -63,12 +63,43 @@ pub fn status_document(status: &StatusSnapshot) -> Value {
     if let Some(tasks) = &status.tasks {
         doc["tasks"] = json!(tasks
             .iter()
-            .map(|task| json!({
-                "id": task.id,
-                "name": task.name,
-                "state": task.state,
-                "durationMs": task.duration_ms,
-            }))
+            .map(|task| {
+                let mut row = json!({
+                    "id": task.id,
+                    "name": task.name,
+                    "state": task.state,
+                    "durationMs": task.duration_ms,
+                });
+                if let Some(reason) = &task.waiting {
+                    row["waiting"] = json!(reason);
+                }
+                if let Some(reason) = &task.failure_reason {
+                    row["failureReason"] = json!(reason);
+                }
+                if let Some(restarts) = task.restarts {
+                    row["restarts"] = json!(restarts);
+                }
+                if let Some(health) = &task.health {
+                    row["health"] = json!(health);
+                }
+                if let Some(generation) = task.superseded_by {
+                    row["supersededBy"] = json!(generation);
+                }
+                if !task.steps.is_empty() {
+                    row["steps"] = json!(task
+                        .steps
+                        .iter()
+                        .map(|step| json!({
+                            "index": step.index,
+                            "command": step.command,
+                            "state": step.state,
+                            "exitCode": step.exit_code,
+                            "durationMs": step.duration_ms,
+                        }))
+                        .collect::<Vec<_>>());
+                }
+                row
+            })
             .collect::<Vec<_>>());
     }
     // TASK-0091, AC2: the frozen config revision of the latest generation
@@ -208,6 +239,18 @@ pub fn output_document(output: &OutputSnapshot) -> Value {
             .iter()
             .map(|task| {
                 let mut entry = json!({ "id": task.id });
+                if let Some(tests) = &task.tests {
+                    entry["tests"] = json!({
+                        "passed": tests.passed,
+                        "failed": tests.failed,
+                        "skipped": tests.skipped,
+                        "failures": tests
+                            .failures
+                            .iter()
+                            .map(|(name, message)| json!({ "name": name, "message": message }))
+                            .collect::<Vec<_>>(),
+                    });
+                }
                 for (name, stream) in [("stdout", &task.stdout), ("stderr", &task.stderr)] {
                     if let Some(stream) = stream {
                         entry[name] = json!({
@@ -294,6 +337,12 @@ mod tests {
                 name: "lint".to_owned(),
                 state: "failed".to_owned(),
                 duration_ms: Some(42),
+                waiting: None,
+                failure_reason: None,
+                restarts: None,
+                health: None,
+                steps: vec![],
+                superseded_by: None,
             }]),
             effective_concurrency: Some(1),
             concurrency_source: Some("control".to_owned()),
@@ -417,6 +466,7 @@ mod tests {
                     truncated: false,
                 }),
                 stderr: None,
+                tests: None,
             }],
         };
         let doc = output_document(&output);
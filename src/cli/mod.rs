@@ -3,6 +3,7 @@ pub mod control;
 pub mod format;
 pub mod init;
 pub mod migrate;
+pub mod replay;
 pub mod run;
 pub mod templates;
 pub mod watch;
@@ -11,6 +12,7 @@ pub mod watch_non_block;
 pub use crate::cli::control::{ControlAction, ControlCommand, OutputFormat};
 pub use crate::cli::init::InitCommand;
 pub use crate::cli::migrate::MigrateCommand;
+pub use crate::cli::replay::ReplayCommand;
 pub use crate::cli::run::RunCommand;
 pub use crate::cli::templates::Profile;
 pub use crate::cli::watch::WatchCommand;
@@ -0,0 +1,57 @@
+use crate::cli::Command;
+use crate::errors::FzzError;
+use crate::event_trace::Replay;
+use crate::watch_loop::{watch_loop, DryRunStrategy};
+use crate::watches::Watches;
+use std::path::PathBuf;
+
+/// # `ReplayCommand`
+///
+/// Feeds a recorded event trace through the watch loop with the dry-run
+/// strategy, printing the generations and job selection it would produce.
+///
+pub struct ReplayCommand {
+    watches: Watches,
+    trace: PathBuf,
+    verbose: bool,
+    run_on_init: bool,
+    /// Busy policy the replayed generations follow: `restart` when true,
+    /// `wait` otherwise.
+    restart: bool,
+}
+
+impl ReplayCommand {
+    pub fn new(
+        watches: Watches,
+        trace: PathBuf,
+        verbose: bool,
+        run_on_init: bool,
+        restart: bool,
+    ) -> Self {
+        ReplayCommand {
+            watches,
+            trace,
+            verbose,
+            run_on_init,
+            restart,
+        }
+    }
+}
+
+impl Command for ReplayCommand {
+    fn execute(&self) -> Result<(), FzzError> {
+        let replay = Replay::load(&self.trace).map_err(FzzError::GenericError)?;
+        let strategy = DryRunStrategy::new(replay, self.restart);
+        let shared = std::sync::Arc::new(std::sync::Mutex::new(self.watches.clone()));
+        watch_loop(
+            &shared,
+            self.run_on_init,
+            &strategy,
+            self.watches.debounce(),
+            self.verbose,
+            None,
+            None,
+            None,
+        )
+    }
+}
@@ -1,9 +1,13 @@
+use crate::arguments::AffectedSource;
 use crate::duration_recorder::DurationRecorder;
 use crate::executor::RunMetadata;
 use crate::plan::RunPlan;
 use crate::stdout;
+use crate::watches::AffectedJob;
 use crate::workflow::WorkflowRunner;
-use std::path::PathBuf;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::Command;
 use std::sync::Arc;
 
 /// Executes one selected configured workflow locally and returns whether its
@@ -77,6 +81,20 @@ impl RunCommand {
         // inside the workflow runner.
         let metadata = RunMetadata::new(0, format!("target:{}", target))
             .with_duration_profile(Some(target.to_owned()), None);
+        self.run(plan, metadata)
+    }
+
+    /// Runs an affected-only plan. The selection depends on the diff, so it
+    /// carries no duration profile: subsets never skew a target's history.
+    pub fn execute_affected(&self, plan: RunPlan, source: &AffectedSource) -> Result<bool, String> {
+        let trigger = match source {
+            AffectedSource::ChangedSince(reference) => format!("changed-since:{reference}"),
+            AffectedSource::PathsFrom(file) => format!("paths-from:{file}"),
+        };
+        self.run(plan, RunMetadata::new(0, trigger))
+    }
+
+    fn run(&self, plan: RunPlan, metadata: RunMetadata) -> Result<bool, String> {
         let completed = self.workflow.run(plan, metadata, None)?;
         let succeeded = completed.outcome.is_success();
         stdout::present_results(
@@ -88,3 +106,121 @@ impl RunCommand {
         Ok(succeeded)
     }
 }
+
+/// Reads the synthetic change batch for `fzz run --changed-since` /
+/// `--paths-from`. Git paths come from `git diff --name-only --relative`, so
+/// they are root-relative exactly like watcher paths: the branch's commits
+/// since its merge-base with REF (`REF...HEAD`), uncommitted changes and
+/// untracked files that are not ignored, so files changed only on REF are
+/// not affected. Git lists them NUL-separated, so names are never quoted.
+pub fn affected_paths(root: &Path, source: &AffectedSource) -> Result<Vec<String>, String> {
+    let listing = match source {
+        AffectedSource::ChangedSince(reference) => {
+            let range = format!("{reference}...HEAD");
+            let mut paths = git_names(root, &["diff", "--name-only", "--relative", &range, "--"])?;
+            paths.extend(git_names(
+                root,
+                &["diff", "--name-only", "--relative", "HEAD", "--"],
+            )?);
+            paths.extend(git_names(
+                root,
+                &["ls-files", "--others", "--exclude-standard"],
+            )?);
+            return Ok(paths);
+        }
+        AffectedSource::PathsFrom(file) if file == "-" => {
+            let mut listing = String::new();
+            std::io::stdin()
+                .read_to_string(&mut listing)
+                .map_err(|err| format!("cannot read paths from stdin: {err}"))?;
+            listing
+        }
+        AffectedSource::PathsFrom(file) => std::fs::read_to_string(file)
+            .map_err(|err| format!("cannot read paths from {file}: {err}"))?,
+    };
+    Ok(parse_path_list(&listing))
+}
+
+/// Runs a git listing command with `-z` and returns its paths.
+fn git_names(root: &Path, args: &[&str]) -> Result<Vec<String>, String> {
+    let output = Command::new("git")
+        .args(&args[..1])
+        .arg("-z")
+        .args(&args[1..])
+        .current_dir(root)
+        .output()
+        .map_err(|err| format!("cannot run git: {err}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(output
+        .stdout
+        .split(|byte| *byte == 0)
+        .filter(|name| !name.is_empty())
+        .map(|name| String::from_utf8_lossy(name).into_owned())
+        .collect())
+}
+
+fn parse_path_list(listing: &str) -> Vec<String> {
+    listing
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_owned)
+        .collect()
+}
+
+/// Human summary of an affected selection: one line per job with the path
+/// and change patterns that selected it.
+pub fn affected_summary(paths: usize, affected: &[AffectedJob]) -> String {
+    if affected.is_empty() {
+        return format!("No jobs affected by {paths} changed path(s).");
+    }
+    let mut output = format!(
+        "Selected {} job(s) from {paths} changed path(s):",
+        affected.len()
+    );
+    for job in affected {
+        output.push_str(&format!(
+            "\n  - {}: {} (change: {})",
+            job.name,
+            job.path,
+            job.change_patterns.join(", ")
+        ));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_list_skips_blank_lines_and_trims() {
+        assert_eq!(
+            parse_path_list("src/main.rs\n\n  docs/a.md  \r\n"),
+            vec!["src/main.rs".to_owned(), "docs/a.md".to_owned()]
+        );
+    }
+
+    #[test]
+    fn summary_names_each_job_with_its_reason() {
+        let affected = vec![AffectedJob {
+            name: "build".to_owned(),
+            path: "src/main.rs".to_owned(),
+            change_patterns: vec!["src/**".to_owned()],
+        }];
+        assert_eq!(
+            affected_summary(3, &affected),
+            "Selected 1 job(s) from 3 changed path(s):\n  - build: src/main.rs (change: src/**)"
+        );
+        assert_eq!(
+            affected_summary(2, &[]),
+            "No jobs affected by 2 changed path(s)."
+        );
+    }
+}
@@ -32,6 +32,9 @@ pub struct WatchNonBlockCommand {
     reload_ready: Option<std::sync::Arc<std::sync::Mutex<std::sync::mpsc::Receiver<()>>>>,
     /// Shared watcher shutdown coordinator (TASK-0101).
     shutdown: Option<Arc<crate::shutdown::ShutdownCoordinator>>,
+    /// `--on-busy restart-affected`: changes cancel only the jobs they
+    /// schedule again.
+    restart_affected: bool,
 }
 
 impl WatchNonBlockCommand {
@@ -72,6 +75,7 @@ impl WatchNonBlockCommand {
             reload: None,
             reload_ready: None,
             shutdown: None,
+            restart_affected: false,
         }
     }
 
@@ -88,6 +92,12 @@ impl WatchNonBlockCommand {
         self
     }
 
+    /// Selects `--on-busy restart-affected` instead of `restart`.
+    pub fn with_restart_affected(mut self, restart_affected: bool) -> Self {
+        self.restart_affected = restart_affected;
+        self
+    }
+
     pub fn with_shutdown(mut self, shutdown: Arc<crate::shutdown::ShutdownCoordinator>) -> Self {
         self.shutdown = Some(shutdown);
         self
@@ -100,6 +110,7 @@ impl Command for WatchNonBlockCommand {
         let coordinator = Arc::new(AwaitCoordinator::new());
         let outputs = Arc::new(OutputRegistry::new());
         let instance = Arc::new(WatcherInstance::new());
+        crate::global_lock::set_instance(&instance.token);
         // Duration recorder (TASK-0054): control-run targets record terminal
         // wall durations against their execution signature; fs/init/emit runs
         // carry no signature and are ignored by the recorder. The same
@@ -139,6 +150,7 @@ impl Command for WatchNonBlockCommand {
                 self.watches.concurrency(),
                 move |event| {
                     recorder_state.observe(&event);
+                    crate::event_trace::observe(&event);
                     coordinator_state.observe(&event);
                     if let Some(stream) = &events_state {
                         stream.emit_event(event.clone());
@@ -150,6 +162,7 @@ impl Command for WatchNonBlockCommand {
             )
             .with_hooks(hooks)
             .with_recovery_policy(self.watches.recovery_policy())
+            .with_restart_affected(self.restart_affected)
             .with_revision(self.watches.revision().cloned().unwrap_or(
                 crate::config_revision::ConfigRevision {
                     number: 0,
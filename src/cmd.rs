@@ -1,14 +1,16 @@
 use crate::diagnostics;
 use crate::logging;
 use crate::plan::TaskContext;
+use crate::rules::{CommandLine, ProcessLimits, Timestamps};
 use crate::stdout;
 use nix::sys::signal::{self, Signal};
 use nix::unistd::Pid;
+use std::collections::VecDeque;
 use std::io::{BufRead, BufReader, Write};
 use std::process::{Child, Command, ExitStatus, Stdio};
 use std::sync::{Arc, Mutex};
 use std::thread;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 /// Per-stream byte bound for retained task output (contract §6). The tail up
 /// to this size is kept; anything older is evicted and marked truncated.
@@ -23,6 +25,9 @@ pub struct CaptureBuffer {
     bytes: Vec<u8>,
     observed: u64,
     truncated: bool,
+    /// When each retained line was read, keyed by its offset in the whole
+    /// stream, for `execution.timestamps`. The bytes themselves stay raw.
+    stamps: VecDeque<(u64, Stamp)>,
 }
 
 impl CaptureBuffer {
@@ -32,16 +37,24 @@ impl CaptureBuffer {
             bytes: Vec::new(),
             observed: 0,
             truncated: false,
+            stamps: VecDeque::new(),
         }
     }
 
-    fn append(&mut self, chunk: &[u8]) {
+    fn append(&mut self, chunk: &[u8], stamp: Option<Stamp>) {
+        if let Some(stamp) = stamp {
+            self.stamps.push_back((self.observed, stamp));
+        }
         self.observed += chunk.len() as u64;
         self.bytes.extend_from_slice(chunk);
         let over = self.bytes.len().saturating_sub(self.bound);
         if over > 0 {
             self.bytes.drain(..over);
             self.truncated = true;
+            let first = self.observed - self.bytes.len() as u64;
+            while self.stamps.front().is_some_and(|(at, _)| *at < first) {
+                self.stamps.pop_front();
+            }
         }
     }
 
@@ -50,6 +63,50 @@ impl CaptureBuffer {
         &self.bytes
     }
 
+    /// Whether retained lines carry timestamps.
+    pub fn is_stamped(&self) -> bool {
+        !self.stamps.is_empty()
+    }
+
+    /// Renders retained bytes `begin..end` (lossy) with each stamped line
+    /// prefixed by its timestamp, for retrieval.
+    pub fn stamped_text(&self, begin: usize, end: usize) -> String {
+        let end = end.min(self.bytes.len());
+        let begin = begin.min(end);
+        let first = self.observed - self.bytes.len() as u64;
+        let mut stamps = self
+            .stamps
+            .iter()
+            .map(|(at, stamp)| ((at - first) as usize, stamp))
+            .skip_while(|(at, _)| *at < begin)
+            .peekable();
+        let mut rendered = Vec::with_capacity(end - begin);
+        for (offset, byte) in self.bytes[begin..end].iter().enumerate() {
+            if let Some((_, stamp)) = stamps.next_if(|(at, _)| *at == begin + offset) {
+                rendered.extend_from_slice(stamp.prefix().as_bytes());
+            }
+            rendered.push(*byte);
+        }
+        String::from_utf8_lossy(&rendered).into_owned()
+    }
+
+    /// Retained bytes from the start of the line holding stream offset
+    /// `from`, so a line still being written is scanned again once whole.
+    /// Offsets past the stream, as after a restart, begin at the first
+    /// retained byte.
+    fn lines_from(&self, from: u64) -> &[u8] {
+        let first = self.observed - self.bytes.len() as u64;
+        if from > self.observed {
+            return &self.bytes;
+        }
+        let at = from.saturating_sub(first) as usize;
+        let start = self.bytes[..at]
+            .iter()
+            .rposition(|byte| *byte == b'\n')
+            .map_or(0, |newline| newline + 1);
+        &self.bytes[start..]
+    }
+
     pub fn observed_bytes(&self) -> u64 {
         self.observed
     }
@@ -87,28 +144,150 @@ impl CaptureData {
     }
 }
 
+/// `--timestamps` override of every job's `execution.timestamps`.
+static TIMESTAMPS_OVERRIDE: Mutex<Option<Timestamps>> = Mutex::new(None);
+
+/// Applies `--timestamps` to every job spawned from now on.
+pub fn override_timestamps(mode: Timestamps) {
+    *TIMESTAMPS_OVERRIDE.lock().unwrap() = Some(mode);
+}
+
+/// The timestamp mode a job's output lines use.
+pub fn timestamps_for(context: &TaskContext) -> Timestamps {
+    TIMESTAMPS_OVERRIDE
+        .lock()
+        .unwrap()
+        .unwrap_or(context.timestamps)
+}
+
+/// When one output line was read: UTC milliseconds since the epoch for
+/// `wall`, milliseconds since the job started for `elapsed`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stamp {
+    pub mode: Timestamps,
+    pub millis: u64,
+}
+
+impl Stamp {
+    /// Stamps a line read now, or None when timestamps are off.
+    pub fn now(mode: Timestamps, started: Instant) -> Option<Stamp> {
+        let millis = match mode {
+            Timestamps::None => return None,
+            Timestamps::Wall => SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|since| since.as_millis() as u64)
+                .unwrap_or(0),
+            Timestamps::Elapsed => started.elapsed().as_millis() as u64,
+        };
+        Some(Stamp { mode, millis })
+    }
+
+    /// The line prefix: `[14:03:07.512] ` or `[+1.250s] `.
+    pub fn prefix(&self) -> String {
+        match self.mode {
+            Timestamps::Elapsed => {
+                format!("[+{}.{:03}s] ", self.millis / 1_000, self.millis % 1_000)
+            }
+            _ => {
+                let seconds = self.millis / 1_000;
+                format!(
+                    "[{:02}:{:02}:{:02}.{:03}] ",
+                    seconds / 3_600 % 24,
+                    seconds / 60 % 60,
+                    seconds % 60,
+                    self.millis % 1_000
+                )
+            }
+        }
+    }
+}
+
 /// Shared capture sink fed by the child's forwarding threads (single read,
 /// multiple consumers: live print + log file + bounded capture).
 pub struct CaptureHandle {
     data: Mutex<CaptureData>,
+    /// When the job's first command spawned: the origin of elapsed
+    /// timestamps.
+    started: Instant,
+    /// Live lines held back for `output: grouped`, in arrival order.
+    held: Option<Mutex<HeldOutput>>,
 }
 
 impl CaptureHandle {
     pub fn new() -> Self {
         Self {
             data: Mutex::new(CaptureData::new(CAPTURE_STREAM_BYTES, CAPTURE_STREAM_BYTES)),
+            started: Instant::now(),
+            held: None,
+        }
+    }
+
+    /// A capture that also holds the live lines of a grouped job until
+    /// [`CaptureHandle::replay`] prints them as one block.
+    pub fn grouped() -> Self {
+        Self {
+            held: Some(Mutex::new(HeldOutput::default())),
+            ..Self::new()
+        }
+    }
+
+    /// Holds one live line for a grouped job; a no-op for other captures.
+    pub fn hold(&self, line: &[u8], is_stderr: bool) {
+        if let Some(held) = &self.held {
+            held.lock().unwrap().push(line, is_stderr);
+        }
+    }
+
+    /// When the job's first command spawned.
+    pub fn started(&self) -> Instant {
+        self.started
+    }
+
+    /// Hands every held line to `emit` in arrival order and forgets them.
+    /// Returns how many bytes were dropped past [`GROUPED_SPILL_BYTES`].
+    pub fn replay(&self, emit: impl FnMut(&[u8], bool)) -> u64 {
+        match &self.held {
+            Some(held) => std::mem::take(&mut *held.lock().unwrap()).replay(emit),
+            None => 0,
         }
     }
 
     pub fn append(&self, bytes: &[u8], is_stderr: bool) {
+        self.append_stamped(bytes, is_stderr, None);
+    }
+
+    /// Appends one line read at `stamp`.
+    pub fn append_stamped(&self, bytes: &[u8], is_stderr: bool, stamp: Option<Stamp>) {
         let mut data = self.data.lock().unwrap();
         if is_stderr {
-            data.stderr.append(bytes);
+            data.stderr.append(bytes, stamp);
         } else {
-            data.stdout.append(bytes);
+            data.stdout.append(bytes, stamp);
         }
     }
 
+    /// Bytes observed so far on stdout and stderr, truncated ones included.
+    pub fn observed(&self) -> (u64, u64) {
+        let data = self.data.lock().unwrap();
+        (data.stdout.observed_bytes(), data.stderr.observed_bytes())
+    }
+
+    /// Whether a retained line of either stream written since `scanned`
+    /// matches `pattern`; `scanned` then moves to the end of both streams,
+    /// so repeated polls never rescan the whole capture.
+    pub fn any_new_line_matches(&self, pattern: &regex::Regex, scanned: &mut (u64, u64)) -> bool {
+        let data = self.data.lock().unwrap();
+        let matched = [(&data.stdout, scanned.0), (&data.stderr, scanned.1)]
+            .iter()
+            .any(|(buffer, from)| {
+                String::from_utf8_lossy(buffer.lines_from(*from))
+                    .lines()
+                    .any(|line| pattern.is_match(line))
+            });
+        *scanned = (data.stdout.observed, data.stderr.observed);
+        matched
+    }
+
     /// Extracts the final captured data (cloned; bounded by the stream caps).
     pub fn finish(&self) -> CaptureData {
         let data = self.data.lock().unwrap();
@@ -118,17 +297,116 @@ impl CaptureHandle {
                 bytes: data.stdout.bytes.clone(),
                 observed: data.stdout.observed,
                 truncated: data.stdout.truncated,
+                stamps: data.stdout.stamps.clone(),
             },
             stderr: CaptureBuffer {
                 bound: data.stderr.bound,
                 bytes: data.stderr.bytes.clone(),
                 observed: data.stderr.observed,
                 truncated: data.stderr.truncated,
+                stamps: data.stderr.stamps.clone(),
             },
         }
     }
 }
 
+/// Bytes of a grouped job's output held in memory before the rest spills
+/// to a temporary file.
+pub const GROUPED_MEMORY_BYTES: usize = 256 * 1024;
+
+/// Bytes of a grouped job's output spilled to disk; later lines are dropped
+/// and counted.
+pub const GROUPED_SPILL_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Output of one grouped job: the first lines in memory, the rest in a
+/// temporary file removed on drop. Spilled records are a stream byte, a
+/// little-endian `u32` length and the raw line.
+#[derive(Default)]
+struct HeldOutput {
+    lines: Vec<(Vec<u8>, bool)>,
+    memory: usize,
+    spill: Option<(std::path::PathBuf, std::fs::File)>,
+    spilled: u64,
+    dropped: u64,
+}
+
+impl HeldOutput {
+    fn push(&mut self, line: &[u8], is_stderr: bool) {
+        if self.spill.is_none() && self.memory + line.len() <= GROUPED_MEMORY_BYTES {
+            self.memory += line.len();
+            self.lines.push((line.to_vec(), is_stderr));
+            return;
+        }
+        if self.spilled + line.len() as u64 > GROUPED_SPILL_BYTES {
+            self.dropped += line.len() as u64;
+            return;
+        }
+        if self.spill.is_none() {
+            static SPILLS: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+            let path = std::env::temp_dir().join(format!(
+                "funzzy-grouped-{}-{}",
+                std::process::id(),
+                SPILLS.fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+            ));
+            // Held output may carry secrets; only the owner may read it.
+            use std::os::unix::fs::OpenOptionsExt;
+            match std::fs::File::options()
+                .create_new(true)
+                .read(true)
+                .write(true)
+                .mode(0o600)
+                .open(&path)
+            {
+                Ok(file) => self.spill = Some((path, file)),
+                Err(_) => {
+                    self.dropped += line.len() as u64;
+                    return;
+                }
+            }
+        }
+        let (_, file) = self.spill.as_mut().expect("spill file just opened");
+        let mut record = Vec::with_capacity(line.len() + 5);
+        record.push(is_stderr as u8);
+        record.extend_from_slice(&(line.len() as u32).to_le_bytes());
+        record.extend_from_slice(line);
+        match file.write_all(&record) {
+            Ok(()) => self.spilled += line.len() as u64,
+            Err(_) => self.dropped += line.len() as u64,
+        }
+    }
+
+    fn replay(mut self, mut emit: impl FnMut(&[u8], bool)) -> u64 {
+        for (line, is_stderr) in &self.lines {
+            emit(line, *is_stderr);
+        }
+        if let Some((_, file)) = self.spill.as_mut() {
+            use std::io::{Read, Seek};
+            if file.seek(std::io::SeekFrom::Start(0)).is_ok() {
+                let mut reader = BufReader::new(&*file);
+                let mut header = [0u8; 5];
+                let mut line = Vec::new();
+                while reader.read_exact(&mut header).is_ok() {
+                    let len = u32::from_le_bytes([header[1], header[2], header[3], header[4]]);
+                    line.resize(len as usize, 0);
+                    if reader.read_exact(&mut line).is_err() {
+                        break;
+                    }
+                    emit(&line, header[0] == 1);
+                }
+            }
+        }
+        self.dropped
+    }
+}
+
+impl Drop for HeldOutput {
+    fn drop(&mut self) {
+        if let Some((path, _)) = self.spill.take() {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
 impl Default for CaptureHandle {
     fn default() -> Self {
         Self::new()
@@ -173,6 +451,9 @@ pub struct LoggedChild {
     child: Child,
     forward_handles: ForwardHandles,
     has_finished: bool,
+    /// An interactive job owns the terminal; on exit it goes back to the
+    /// foreground group recorded before the hand-over.
+    holds_terminal: Option<Pid>,
 }
 
 /// Outcome of a graceful process-group shutdown (TASK-0030).
@@ -190,15 +471,19 @@ pub enum ShutdownOutcome {
 impl LoggedChild {
     fn new(
         mut child: Child,
+        pty: Option<std::fs::File>,
         capture: Option<Arc<CaptureHandle>>,
         label: Option<String>,
         quiet: bool,
+        timestamps: Timestamps,
     ) -> Self {
-        let forward_handles = forward_child_output(&mut child, capture, label, quiet);
+        let forward_handles =
+            forward_child_output(&mut child, pty, capture, label, quiet, timestamps);
         Self {
             child,
             forward_handles,
             has_finished: false,
+            holds_terminal: None,
         }
     }
 
@@ -225,6 +510,7 @@ impl LoggedChild {
             }
             Err(_) => {
                 self.forward_handles.discard();
+                self.take_terminal_back();
             }
         }
 
@@ -236,13 +522,35 @@ impl LoggedChild {
         self.child.kill()
     }
 
+    /// Runs once the child has exited: joins the forwarding threads and
+    /// takes the terminal back from an interactive job.
     fn join_forwarding_threads(&mut self) {
         if !self.has_finished {
             self.forward_handles.join();
             self.has_finished = true;
+            self.take_terminal_back();
         }
     }
 
+    /// Returns the terminal to the group that had it before this job, unless
+    /// another job has taken it since.
+    fn take_terminal_back(&mut self) {
+        if let Some(previous) = self.holds_terminal.take() {
+            let own = Pid::from_raw(self.child.id() as i32);
+            match nix::unistd::tcgetpgrp(std::io::stdin()) {
+                Ok(group) if group != own && group != previous => {}
+                _ => {
+                    let _ = hand_terminal_to(previous);
+                }
+            }
+        }
+    }
+
+    /// Delivers `signal` to the whole process group without waiting.
+    pub fn signal(&mut self, signal: Signal) -> std::io::Result<()> {
+        signal::kill(Pid::from_raw(-(self.child.id() as i32)), signal).map_err(std::io::Error::from)
+    }
+
     /// Gracefully shuts the owned process group down: sends `signal` to the
     /// whole group (shell + descendants), waits up to `grace` for exit, then
     /// escalates to `SIGKILL` on the group and reaps. Forwarding threads are
@@ -352,7 +660,8 @@ fn run_to_completion(cmd: &mut Command, display: &str) -> Result<(), String> {
             .spawn()
             .map_err(|error| format!("Command {} has errored with {}", display, error))?;
 
-        let mut handles = forward_child_output(&mut child, None, None, false);
+        let mut handles =
+            forward_child_output(&mut child, None, None, None, false, Timestamps::None);
 
         match child.wait() {
             Ok(status) if status.success() => {
@@ -382,6 +691,70 @@ fn run_to_completion(cmd: &mut Command, display: &str) -> Result<(), String> {
     }
 }
 
+pub trait ChildProcess: Send {
+    fn try_wait(&mut self) -> std::io::Result<Option<ExitStatus>>;
+    /// Delivers `signal` to the child's whole process group.
+    fn signal(&mut self, signal: Signal) -> std::io::Result<()>;
+    fn shutdown(&mut self, signal: Signal, grace: Duration, verbose: bool) -> ShutdownOutcome;
+    /// The owned process group, when the child leads one.
+    fn group(&self) -> Option<i32> {
+        None
+    }
+}
+
+impl ChildProcess for LoggedChild {
+    fn try_wait(&mut self) -> std::io::Result<Option<ExitStatus>> {
+        LoggedChild::try_wait(self)
+    }
+
+    fn signal(&mut self, signal: Signal) -> std::io::Result<()> {
+        LoggedChild::signal(self, signal)
+    }
+
+    fn shutdown(&mut self, signal: Signal, grace: Duration, verbose: bool) -> ShutdownOutcome {
+        LoggedChild::shutdown(self, signal, grace, verbose)
+    }
+
+    fn group(&self) -> Option<i32> {
+        Some(self.child.id() as i32)
+    }
+}
+
+pub trait ProcessRunner: Send + Sync {
+    fn spawn(
+        &self,
+        task: &str,
+        command: &CommandLine,
+        context: &TaskContext,
+        capture: Option<Arc<CaptureHandle>>,
+        label: Option<String>,
+        quiet: bool,
+    ) -> Result<Box<dyn ChildProcess>, String>;
+}
+
+pub struct SystemProcessRunner;
+
+impl ProcessRunner for SystemProcessRunner {
+    fn spawn(
+        &self,
+        task: &str,
+        command: &CommandLine,
+        context: &TaskContext,
+        capture: Option<Arc<CaptureHandle>>,
+        label: Option<String>,
+        quiet: bool,
+    ) -> Result<Box<dyn ChildProcess>, String> {
+        let child = match command {
+            CommandLine::Shell(command) => {
+                spawn_in_with_capture_quiet(command, context, capture, label, quiet)
+            }
+            CommandLine::Argv(argv) => spawn_argv_in(argv, context),
+        }?;
+        crate::process_owner::set_teardown(child.id() as i32, task, context);
+        Ok(Box::new(child))
+    }
+}
+
 pub fn spawn(command: &String) -> Result<LoggedChild, String> {
     spawn_in(command, &TaskContext::default())
 }
@@ -407,7 +780,7 @@ pub fn spawn_in_with_capture_quiet(
     let mut cmd = prepare_command(command);
     apply_context(&mut cmd, context);
 
-    spawn_configured(&mut cmd, command, capture, label, quiet)
+    spawn_configured(&mut cmd, command, context, capture, label, quiet)
 }
 
 /// Spawns with an optional bounded output capture (TASK-0045, contract §6).
@@ -428,7 +801,7 @@ pub fn spawn_in_with_capture(
     let mut cmd = prepare_command(command);
     apply_context(&mut cmd, context);
 
-    spawn_configured(&mut cmd, command, capture, label, false)
+    spawn_configured(&mut cmd, command, context, capture, label, false)
 }
 
 /// Spawns an exact argv (program plus arguments) directly, without a shell.
@@ -446,7 +819,7 @@ pub fn spawn_argv_in(argv: &[String], context: &TaskContext) -> Result<LoggedChi
     let mut cmd = prepare_argv_command(argv);
     apply_context(&mut cmd, context);
 
-    spawn_configured(&mut cmd, &display, None, None, false)
+    spawn_configured(&mut cmd, &display, context, None, None, false)
 }
 
 fn apply_context(command: &mut Command, context: &TaskContext) {
@@ -459,19 +832,59 @@ fn apply_context(command: &mut Command, context: &TaskContext) {
 fn spawn_configured(
     cmd: &mut Command,
     display: &str,
+    context: &TaskContext,
     capture: Option<Arc<CaptureHandle>>,
     label: Option<String>,
     quiet: bool,
 ) -> Result<LoggedChild, String> {
-    // Pipe child output whenever we need to forward it: logging, bounded
-    // capture, quiet suppression (TASK-0041), or live task attribution
-    // (TASK-0028). Attribution requires the forwarding thread, so
-    // parallel-group tasks always pipe even without `--log-file`; serial
-    // tasks without logging/capture/quiet keep inherited stdout passthrough.
-    if logging::is_enabled() || capture.is_some() || label.is_some() || quiet {
-        cmd.stdout(Stdio::piped());
-        cmd.stderr(Stdio::piped());
-    }
+    let limits = context.limits;
+    let timestamps = timestamps_for(context);
+    // `jobs[].interactive`: the job becomes the terminal's foreground group
+    // while it runs, but only when funzzy holds the terminal itself; in the
+    // background or without a terminal the job just inherits stdin.
+    let interactive = context.interactive && owns_terminal();
+    // The group to give the terminal back to once the job exits.
+    let foreground = interactive
+        .then(|| nix::unistd::tcgetpgrp(std::io::stdin()).ok())
+        .flatten();
+    // `jobs[].tty`: stdout and stderr share one pseudo-terminal so tools
+    // that probe isatty keep colors and progress output. The master side is
+    // read by the same forwarding thread a pipe would use.
+    let pty = if context.tty {
+        let pty = open_pty().map_err(|err| {
+            format!(
+                "Command {} could not open a pseudo-terminal: {}",
+                display, err
+            )
+        })?;
+        let slave = std::fs::File::from(pty.slave);
+        let slave_stderr = slave
+            .try_clone()
+            .map_err(|err| format!("Command {} has errored with {}", display, err))?;
+        cmd.stdout(Stdio::from(slave));
+        cmd.stderr(Stdio::from(slave_stderr));
+        Some(std::fs::File::from(pty.master))
+    } else {
+        // Pipe child output whenever we need to forward it: logging, bounded
+        // capture, quiet suppression (TASK-0041), or live task attribution
+        // (TASK-0028). Attribution requires the forwarding thread, so
+        // parallel-group tasks always pipe even without `--log-file`; serial
+        // tasks without logging/capture/quiet keep inherited stdout passthrough.
+        // Interactive jobs always write straight to the terminal: prompts
+        // without a trailing newline must show before the user types.
+        if !interactive
+            && (logging::is_enabled()
+                || capture.is_some()
+                || label.is_some()
+                || quiet
+                || timestamps != Timestamps::None)
+        {
+            cmd.stdout(Stdio::piped());
+            cmd.stderr(Stdio::piped());
+        }
+        None
+    };
+    let tty = pty.is_some();
 
     // Each task leads its own process group so cancellation can signal the
     // whole tree (shell + descendants) without touching the funzzy process
@@ -481,11 +894,27 @@ fn spawn_configured(
     #[cfg(unix)]
     unsafe {
         use std::os::unix::process::CommandExt;
-        cmd.pre_exec(|| {
+        cmd.pre_exec(move || {
             use nix::sys::signal::{sigprocmask, SigSet, SigmaskHow};
 
-            nix::unistd::setpgid(nix::unistd::Pid::from_raw(0), nix::unistd::Pid::from_raw(0))
-                .map_err(|err| std::io::Error::from_raw_os_error(err as i32))?;
+            if tty {
+                // A new session leads a new group too (pgid == pid), and
+                // lets the pseudo-terminal become the controlling terminal.
+                nix::unistd::setsid()
+                    .map_err(|err| std::io::Error::from_raw_os_error(err as i32))?;
+                if nix::libc::ioctl(1, nix::libc::TIOCSCTTY as _, 0) != 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+            } else {
+                nix::unistd::setpgid(nix::unistd::Pid::from_raw(0), nix::unistd::Pid::from_raw(0))
+                    .map_err(|err| std::io::Error::from_raw_os_error(err as i32))?;
+                // The child takes the terminal itself, so it can never read
+                // stdin before the hand-over and stop on SIGTTIN.
+                if interactive {
+                    hand_terminal_to(nix::unistd::getpgrp())
+                        .map_err(|err| std::io::Error::from_raw_os_error(err as i32))?;
+                }
+            }
 
             // The non-block parent blocks SIGINT/SIGTERM for its sigwait
             // thread. Signal masks are inherited across fork/exec, so reset
@@ -497,22 +926,146 @@ fn spawn_configured(
             signals.add(Signal::SIGTERM);
             sigprocmask(SigmaskHow::SIG_UNBLOCK, Some(&signals), None)
                 .map_err(|err| std::io::Error::from_raw_os_error(err as i32))?;
-            Ok(())
+            apply_limits(&limits)
         });
     }
 
-    match cmd.spawn() {
+    let spawned = cmd.spawn();
+    if tty {
+        // Close the parent's copies of the slave: the master only reports
+        // end of output once every holder of the slave is gone.
+        cmd.stdout(Stdio::null());
+        cmd.stderr(Stdio::null());
+    }
+    match spawned {
         Ok(child) => {
             // The child leads its own group (pgid == pid). Track it so every
             // shutdown path can reach the whole task tree (TASK-0030).
             let pid = child.id() as i32;
             crate::process_owner::register(pid);
-            Ok(LoggedChild::new(child, capture, label, quiet))
+            crate::process_owner::set_stop_policy(pid, context.stop);
+            let mut child = LoggedChild::new(child, pty, capture, label, quiet, timestamps);
+            child.holds_terminal = foreground;
+            Ok(child)
         }
         Err(error) => Err(format!("Command {} has errored with {}", display, error)),
     }
 }
 
+/// Whether stdin is a terminal whose foreground group is funzzy's own.
+fn owns_terminal() -> bool {
+    let stdin = std::io::stdin();
+    nix::unistd::isatty(&stdin).unwrap_or(false)
+        && nix::unistd::tcgetpgrp(&stdin).is_ok_and(|group| group == nix::unistd::getpgrp())
+}
+
+/// Makes `group` the foreground process group of the terminal on stdin.
+/// Async-signal-safe, so the forked child can call it before exec.
+fn hand_terminal_to(group: Pid) -> nix::Result<()> {
+    use nix::sys::signal::{sigprocmask, SigSet, SigmaskHow};
+    // A background group that changes the foreground group is sent SIGTTOU
+    // (which stops it) unless the signal is blocked.
+    let mut ttou = SigSet::empty();
+    ttou.add(Signal::SIGTTOU);
+    let mut previous = SigSet::empty();
+    sigprocmask(SigmaskHow::SIG_BLOCK, Some(&ttou), Some(&mut previous))?;
+    // SAFETY: fd 0 stays open for the whole call.
+    let stdin = unsafe { std::os::fd::BorrowedFd::borrow_raw(0) };
+    let result = nix::unistd::tcsetpgrp(stdin, group);
+    sigprocmask(SigmaskHow::SIG_SETMASK, Some(&previous), None)?;
+    result
+}
+
+/// Opens a pseudo-terminal sized like the watcher's own terminal, so line
+/// wrapping and progress bars match; 80x24 when stdout is not a terminal.
+fn open_pty() -> nix::Result<nix::pty::OpenptyResult> {
+    // SAFETY: winsize is plain data and TIOCGWINSZ only writes into it.
+    let mut size: nix::libc::winsize = unsafe { std::mem::zeroed() };
+    let inherited =
+        unsafe { nix::libc::ioctl(1, nix::libc::TIOCGWINSZ, &mut size) } == 0 && size.ws_col > 0;
+    if !inherited {
+        size.ws_row = 24;
+        size.ws_col = 80;
+    }
+    nix::pty::openpty(&size, None)
+}
+
+/// Applies `jobs[].limits`, `nice` and `ionice` in the forked child, before
+/// exec, so they cover the shell and everything it starts. Only
+/// async-signal-safe calls: `setrlimit`, `setpriority` and `ioprio_set`.
+#[cfg(unix)]
+fn apply_limits(limits: &ProcessLimits) -> std::io::Result<()> {
+    use nix::sys::resource::{setrlimit, Resource};
+
+    // The CPU hard limit sits one second past the soft one: at the soft
+    // limit the kernel sends SIGXCPU, which `limit_violation` recognises,
+    // instead of an anonymous SIGKILL.
+    for (resource, soft, hard) in [
+        (Resource::RLIMIT_AS, limits.memory, limits.memory),
+        (
+            Resource::RLIMIT_CPU,
+            limits.cpu_seconds,
+            limits.cpu_seconds.map(|seconds| seconds + 1),
+        ),
+        (
+            Resource::RLIMIT_NOFILE,
+            limits.open_files,
+            limits.open_files,
+        ),
+        (Resource::RLIMIT_CORE, limits.core, limits.core),
+    ] {
+        if let (Some(soft), Some(hard)) = (soft, hard) {
+            setrlimit(resource, soft, hard)
+                .map_err(|err| std::io::Error::from_raw_os_error(err as i32))?;
+        }
+    }
+    if let Some(nice) = limits.nice {
+        // SAFETY: plain syscall on the calling (child) process.
+        if unsafe { nix::libc::setpriority(nix::libc::PRIO_PROCESS as _, 0, nice) } != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+    }
+    #[cfg(target_os = "linux")]
+    if let Some(priority) = limits.ionice {
+        const IOPRIO_WHO_PROCESS: nix::libc::c_long = 1;
+        const IOPRIO_CLASS_SHIFT: u32 = 13;
+        let value = match priority {
+            crate::rules::IoPriority::BestEffort(level) => {
+                (2 << IOPRIO_CLASS_SHIFT) | level as nix::libc::c_long
+            }
+            crate::rules::IoPriority::Idle => 3 << IOPRIO_CLASS_SHIFT,
+        };
+        // SAFETY: plain syscall on the calling (child) process.
+        if unsafe { nix::libc::syscall(nix::libc::SYS_ioprio_set, IOPRIO_WHO_PROCESS, 0, value) }
+            != 0
+        {
+            return Err(std::io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+/// Names the limit a failed command most likely hit, from the signal that
+/// terminated it: `SIGXCPU` (or the `SIGKILL` at the hard limit a second
+/// later) means the CPU limit; an abort or segfault under a memory limit is
+/// how allocators report `ENOMEM`. Exit codes are never read as signals, so
+/// a command's own `exit 137` is an ordinary failure. Callers skip children
+/// that funzzy itself stopped.
+pub fn limit_violation(limits: &ProcessLimits, status: &ExitStatus) -> Option<String> {
+    use std::os::unix::process::ExitStatusExt;
+    let signal = Signal::try_from(status.signal()?).ok()?;
+    match (signal, limits.cpu_seconds, limits.memory) {
+        (Signal::SIGXCPU | Signal::SIGKILL, Some(seconds), _) => {
+            Some(format!("cpu limit of {}s exceeded", seconds))
+        }
+        (Signal::SIGABRT | Signal::SIGSEGV | Signal::SIGBUS, _, Some(bytes)) => Some(format!(
+            "memory limit of {} likely exceeded",
+            crate::config::format_size(bytes)
+        )),
+        _ => None,
+    }
+}
+
 #[test]
 fn it_spawn_a_command_returning_a_child_ref() {
     let result = match spawn(&String::from("echo 'foo'")) {
@@ -523,6 +1076,204 @@ fn it_spawn_a_command_returning_a_child_ref() {
     assert_eq!(format!("{}", result), "exit status: 0")
 }
 
+#[test]
+fn limits_apply_to_the_spawned_shell() {
+    let out = std::env::temp_dir().join(format!("funzzy-limits-{}", std::process::id()));
+    let context = TaskContext {
+        limits: ProcessLimits {
+            memory: Some(1 << 30),
+            open_files: Some(64),
+            nice: Some(5),
+            ..Default::default()
+        },
+        ..TaskContext::default()
+    };
+    let command = format!(
+        "echo $(ulimit -n) $(ulimit -v) $(ps -o ni= -p $$) > {}",
+        out.display()
+    );
+    let status = spawn_in(&command, &context)
+        .expect("spawn limited shell")
+        .wait()
+        .expect("wait");
+    assert!(status.success());
+    let reported = std::fs::read_to_string(&out).expect("limits written");
+    let _ = std::fs::remove_file(&out);
+    let fields: Vec<&str> = reported.split_whitespace().collect();
+    assert_eq!(fields, ["64", "1048576", "5"]);
+}
+
+#[test]
+fn tty_jobs_see_a_terminal_and_retain_plain_text() {
+    let context = TaskContext {
+        tty: true,
+        ..TaskContext::default()
+    };
+    let capture = Arc::new(CaptureHandle::new());
+    let command =
+        "test -t 1 && test -t 2 && printf '\\033[31mred\\033[0m 50%%\\r100%%\\n'".to_owned();
+    let status =
+        spawn_in_with_capture_quiet(&command, &context, Some(Arc::clone(&capture)), None, true)
+            .expect("spawn under a pty")
+            .wait()
+            .expect("wait");
+    assert!(status.success(), "stdout and stderr must be terminals");
+    assert_eq!(capture.finish().stdout.bytes(), b"100%\n");
+}
+
+#[test]
+fn line_matching_scans_only_output_written_since_the_last_scan() {
+    let capture = CaptureHandle::new();
+    let ready = regex::Regex::new("^listening$").unwrap();
+    let mut scanned = (0, 0);
+    capture.append(b"booting\nlisten", false);
+    assert!(!capture.any_new_line_matches(&ready, &mut scanned));
+    assert_eq!(scanned, (14, 0));
+    // The partial line is scanned again once it is whole.
+    capture.append(b"ing\n", false);
+    assert!(capture.any_new_line_matches(&ready, &mut scanned));
+    // Lines already scanned do not match again.
+    capture.append(b"serving\n", false);
+    assert!(!capture.any_new_line_matches(&ready, &mut scanned));
+}
+
+#[test]
+fn grouped_spill_files_are_private_to_the_owner() {
+    use std::os::unix::fs::PermissionsExt;
+    let mut held = HeldOutput::default();
+    let line = vec![b'x'; GROUPED_MEMORY_BYTES + 1];
+    held.push(&line, false);
+    let (path, _) = held.spill.as_ref().expect("spilled past the memory bound");
+    let mode = std::fs::metadata(path).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o600);
+    assert_eq!(held.replay(|_, _| {}), 0);
+}
+
+#[test]
+fn grouped_capture_holds_lines_in_order_past_the_memory_bound() {
+    let capture = Arc::new(CaptureHandle::new());
+    capture.hold(b"ignored\n", false);
+    assert_eq!(capture.replay(|_, _| panic!("nothing is held")), 0);
+
+    let capture = Arc::new(CaptureHandle::grouped());
+    let command =
+        "echo first; echo oops >&2; head -c 300000 /dev/zero | tr '\\0' x; echo; echo last"
+            .to_owned();
+    let status = spawn_in_with_capture_quiet(
+        &command,
+        &TaskContext::default(),
+        Some(Arc::clone(&capture)),
+        None,
+        true,
+    )
+    .expect("spawn")
+    .wait()
+    .expect("wait");
+    assert!(status.success());
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let dropped = capture.replay(|line, is_stderr| {
+        let lines = if is_stderr { &mut stderr } else { &mut stdout };
+        lines.push(String::from_utf8_lossy(line).into_owned());
+    });
+    assert_eq!(dropped, 0);
+    assert_eq!(stderr, ["oops\n"]);
+    assert_eq!(stdout.len(), 3);
+    assert_eq!(stdout[0], "first\n");
+    assert_eq!(stdout[1].len(), 300_001, "spilled line replays whole");
+    assert_eq!(stdout[2], "last\n");
+    assert_eq!(capture.replay(|_, _| panic!("replay forgets")), 0);
+    // The retained capture is unaffected.
+    assert!(capture.finish().stdout.bytes().ends_with(b"last\n"));
+}
+
+#[test]
+fn tty_jobs_still_cancel_as_a_group() {
+    let context = TaskContext {
+        tty: true,
+        ..TaskContext::default()
+    };
+    let mut child =
+        spawn_in(&"sleep 30 & sleep 30".to_owned(), &context).expect("spawn under a pty");
+    let started = Instant::now();
+    let outcome = child.shutdown(Signal::SIGTERM, Duration::from_secs(2), false);
+    assert!(
+        matches!(outcome, ShutdownOutcome::Terminated(_)),
+        "pty group must stop on SIGTERM: {:?}",
+        outcome
+    );
+    assert!(started.elapsed() < Duration::from_secs(2));
+}
+
+#[test]
+fn interactive_jobs_without_a_terminal_run_as_ordinary_jobs() {
+    let context = TaskContext {
+        interactive: true,
+        ..TaskContext::default()
+    };
+    let mut child = spawn_in(&"exit 3".to_owned(), &context).expect("spawn interactive job");
+    assert_eq!(child.wait().expect("wait").code(), Some(3));
+}
+
+#[test]
+fn terminal_codes_are_stripped_from_retained_output() {
+    assert_eq!(
+        strip_terminal_codes(b"\x1b[1;32mok\x1b[0m\r\n"),
+        b"ok\n".to_vec()
+    );
+    assert_eq!(
+        strip_terminal_codes(b"\x1b]0;title\x07[==  ]\r[====]\r\n"),
+        b"[====]\n".to_vec()
+    );
+}
+
+#[test]
+fn limit_violation_names_the_limit_from_the_exit() {
+    use std::os::unix::process::ExitStatusExt;
+    let limits = ProcessLimits {
+        cpu_seconds: Some(10),
+        memory: Some(4 << 30),
+        ..Default::default()
+    };
+    let signalled = |signal: Signal| ExitStatus::from_raw(signal as i32);
+    let shell_reported = |signal: Signal| ExitStatus::from_raw((128 + signal as i32) << 8);
+
+    assert_eq!(
+        limit_violation(&limits, &signalled(Signal::SIGXCPU)).as_deref(),
+        Some("cpu limit of 10s exceeded")
+    );
+    assert_eq!(
+        limit_violation(&limits, &signalled(Signal::SIGABRT)).as_deref(),
+        Some("memory limit of 4G likely exceeded")
+    );
+    // A shell's `128 + signal` exit code is the command's own exit.
+    assert_eq!(
+        limit_violation(&limits, &shell_reported(Signal::SIGABRT)),
+        None
+    );
+    assert_eq!(
+        limit_violation(&limits, &shell_reported(Signal::SIGKILL)),
+        None
+    );
+    let memory_only = ProcessLimits {
+        memory: Some(4 << 30),
+        ..Default::default()
+    };
+    assert_eq!(
+        limit_violation(&memory_only, &signalled(Signal::SIGKILL)),
+        None
+    );
+    assert_eq!(
+        limit_violation(&limits, &ExitStatus::from_raw(1 << 8)),
+        None
+    );
+    assert_eq!(
+        limit_violation(&ProcessLimits::default(), &signalled(Signal::SIGXCPU)),
+        None
+    );
+}
+
 #[test]
 fn graceful_shutdown_escalates_when_group_ignores_sigterm() {
     let ready =
@@ -639,24 +1390,44 @@ fn prepare_argv_command(argv: &[String]) -> Command {
 
 fn forward_child_output(
     child: &mut Child,
+    pty: Option<std::fs::File>,
     capture: Option<Arc<CaptureHandle>>,
     label: Option<String>,
     quiet: bool,
+    timestamps: Timestamps,
 ) -> ForwardHandles {
     let mut handles = ForwardHandles::new();
+    // Elapsed time counts from the job's first command, which created its
+    // capture; uncaptured commands count from their own spawn.
+    let started = capture
+        .as_ref()
+        .map_or_else(Instant::now, |capture| capture.started());
+    let stamper = (timestamps, started);
+
+    // A pseudo-terminal merges both streams; they are forwarded as stdout.
+    if let Some(master) = pty {
+        handles.stdout = Some(spawn_forwarding_thread(
+            master, false, true, capture, label, quiet, stamper,
+        ));
+        return handles;
+    }
 
     if let Some(stdout) = child.stdout.take() {
         handles.stdout = Some(spawn_forwarding_thread(
             stdout,
             false,
+            false,
             capture.clone(),
             label.clone(),
             quiet,
+            stamper,
         ));
     }
 
     if let Some(stderr) = child.stderr.take() {
-        handles.stderr = Some(spawn_forwarding_thread(stderr, true, capture, label, quiet));
+        handles.stderr = Some(spawn_forwarding_thread(
+            stderr, true, false, capture, label, quiet, stamper,
+        ));
     }
 
     handles
@@ -673,20 +1444,79 @@ fn forward_child_output(
 /// newline, or a partial final line) for live forwarding: lossy UTF-8 so
 /// binary output never drops the stream, with an optional `[label] ` prefix
 /// for parallel-task attribution (TASK-0028). Pure and testable.
-fn render_live_line(raw: &[u8], label: Option<&str>) -> String {
+fn render_live_line(raw: &[u8], label: Option<&str>, stamp: Option<Stamp>) -> String {
     let rendered = String::from_utf8_lossy(raw);
+    let stamp = stamp.map(|stamp| stamp.prefix()).unwrap_or_default();
     match label {
-        Some(label) => format!("[{}] {}", label, rendered),
-        None => rendered.into_owned(),
+        Some(label) => format!("{}[{}] {}", stamp, label, rendered),
+        None if stamp.is_empty() => rendered.into_owned(),
+        None => format!("{}{}", stamp, rendered),
     }
 }
 
+/// Renders pseudo-terminal output for retention: drops ANSI escape
+/// sequences (CSI, OSC and two-byte escapes), turns CRLF into LF and keeps
+/// only the last carriage-return redraw of a line, so agents read what the
+/// terminal finally showed rather than colour codes and progress frames.
+fn strip_terminal_codes(raw: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(raw.len());
+    let mut index = 0;
+    while index < raw.len() {
+        match raw[index] {
+            0x1b => {
+                index += 1;
+                match raw.get(index) {
+                    Some(b'[') => {
+                        index += 1;
+                        while index < raw.len() && !(0x40..=0x7e).contains(&raw[index]) {
+                            index += 1;
+                        }
+                        index += 1;
+                    }
+                    Some(b']') => {
+                        index += 1;
+                        while index < raw.len() {
+                            if raw[index] == 0x07 {
+                                index += 1;
+                                break;
+                            }
+                            if raw[index] == 0x1b && raw.get(index + 1) == Some(&b'\\') {
+                                index += 2;
+                                break;
+                            }
+                            index += 1;
+                        }
+                    }
+                    Some(_) => index += 1,
+                    None => {}
+                }
+            }
+            b'\r' if raw.get(index + 1) == Some(&b'\n') => index += 1,
+            b'\r' => {
+                let line_start = out
+                    .iter()
+                    .rposition(|byte| *byte == b'\n')
+                    .map_or(0, |position| position + 1);
+                out.truncate(line_start);
+                index += 1;
+            }
+            byte => {
+                out.push(byte);
+                index += 1;
+            }
+        }
+    }
+    out
+}
+
 fn spawn_forwarding_thread<R: std::io::Read + Send + 'static>(
     reader: R,
     is_stderr: bool,
+    terminal: bool,
     capture: Option<Arc<CaptureHandle>>,
     label: Option<String>,
     quiet: bool,
+    (timestamps, started): (Timestamps, Instant),
 ) -> thread::JoinHandle<()> {
     thread::spawn(move || {
         let mut reader = BufReader::new(reader);
@@ -701,7 +1531,8 @@ fn spawn_forwarding_thread<R: std::io::Read + Send + 'static>(
                     // Lossy render: binary or invalid UTF-8 output still
                     // reaches the console instead of silently dropping the
                     // rest of the stream.
-                    let attributed = render_live_line(&line, label.as_deref());
+                    let stamp = Stamp::now(timestamps, started);
+                    let attributed = render_live_line(&line, label.as_deref(), stamp);
                     // TASK-0041: quiet/capture/show-on-failure suppress the
                     // live stream; the capture still keeps raw bytes so the
                     // output is retrievable and revealable on failure.
@@ -720,7 +1551,21 @@ fn spawn_forwarding_thread<R: std::io::Read + Send + 'static>(
                     if let Some(capture) = &capture {
                         // Raw bytes: no secret inference, no UTF-8 validation
                         // here (retrieval renders lossy), and no prefix.
-                        capture.append(&line, is_stderr);
+                        // Terminal output is reduced to its visible text.
+                        if terminal {
+                            capture.append_stamped(&strip_terminal_codes(&line), is_stderr, stamp);
+                        } else {
+                            capture.append_stamped(&line, is_stderr, stamp);
+                        }
+                        // A grouped job's live lines wait for its block.
+                        match stamp {
+                            Some(stamp) => {
+                                let mut stamped = stamp.prefix().into_bytes();
+                                stamped.extend_from_slice(&line);
+                                capture.hold(&stamped, is_stderr);
+                            }
+                            None => capture.hold(&line, is_stderr),
+                        }
                     }
                 }
                 Err(_) => break,
@@ -735,10 +1580,10 @@ mod tests {
 
     #[test]
     fn render_live_line_preserves_plain_output_byte_for_byte() {
-        assert_eq!(render_live_line(b"hello\n", None), "hello\n");
-        assert_eq!(render_live_line(b"", None), "");
+        assert_eq!(render_live_line(b"hello\n", None, None), "hello\n");
+        assert_eq!(render_live_line(b"", None, None), "");
         assert_eq!(
-            render_live_line(b"partial without newline", None),
+            render_live_line(b"partial without newline", None, None),
             "partial without newline"
         );
     }
@@ -746,11 +1591,11 @@ mod tests {
     #[test]
     fn render_live_line_prefixes_label_on_complete_and_partial_lines() {
         assert_eq!(
-            render_live_line(b"line one\n", Some("lint")),
+            render_live_line(b"line one\n", Some("lint"), None),
             "[lint] line one\n"
         );
         assert_eq!(
-            render_live_line(b"trailing partial", Some("test")),
+            render_live_line(b"trailing partial", Some("test"), None),
             "[test] trailing partial"
         );
     }
@@ -759,7 +1604,7 @@ mod tests {
     fn render_live_line_is_lossy_for_non_utf8_but_never_drops_bytes() {
         // 0xFF is invalid UTF-8: rendered lossily (never kills the stream),
         // and every byte still appears in the rendered line.
-        let rendered = render_live_line(b"ok \xff done\n", Some("check"));
+        let rendered = render_live_line(b"ok \xff done\n", Some("check"), None);
         assert!(rendered.contains("[check] ok "), "rendered: {rendered}");
         assert!(rendered.contains(" done\n"), "rendered: {rendered}");
     }
@@ -769,8 +1614,51 @@ mod tests {
         // The prefix must not split a line: one whole line in, one whole
         // attributed line out, with the newline preserved at the end.
         let line = b"interleaved output stays whole\n";
-        let rendered = render_live_line(line, Some("task a"));
+        let rendered = render_live_line(line, Some("task a"), None);
         assert_eq!(rendered, "[task a] interleaved output stays whole\n");
         assert_eq!(rendered.matches('\n').count(), 1);
     }
+    #[test]
+    fn timestamps_prefix_live_lines_and_retained_pages_but_not_raw_bytes() {
+        let elapsed = Stamp {
+            mode: Timestamps::Elapsed,
+            millis: 61_250,
+        };
+        let wall = Stamp {
+            mode: Timestamps::Wall,
+            millis: 1_700_000_000_512,
+        };
+        assert_eq!(elapsed.prefix(), "[+61.250s] ");
+        assert_eq!(wall.prefix(), "[22:13:20.512] ");
+        assert_eq!(
+            render_live_line(b"ok\n", Some("lint"), Some(elapsed)),
+            "[+61.250s] [lint] ok\n"
+        );
+        assert_eq!(
+            render_live_line(b"ok\n", None, Some(wall)),
+            "[22:13:20.512] ok\n"
+        );
+        assert!(Stamp::now(Timestamps::None, Instant::now()).is_none());
+
+        let mut buffer = CaptureBuffer::new(12);
+        buffer.append(b"first\n", Some(elapsed));
+        buffer.append(b"second\n", Some(wall));
+        buffer.append(b"third\n", None);
+        assert_eq!(buffer.bytes(), b"econd\nthird\n");
+        assert_eq!(
+            buffer.stamped_text(0, 12),
+            "econd\nthird\n",
+            "the evicted line start takes its stamp with it"
+        );
+
+        let mut buffer = CaptureBuffer::new(64);
+        buffer.append(b"a\n", Some(elapsed));
+        buffer.append(b"b\n", Some(wall));
+        assert_eq!(buffer.bytes(), b"a\nb\n");
+        assert_eq!(
+            buffer.stamped_text(0, 4),
+            "[+61.250s] a\n[22:13:20.512] b\n"
+        );
+        assert_eq!(buffer.stamped_text(1, 4), "\n[22:13:20.512] b\n");
+    }
 }
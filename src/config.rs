@@ -10,8 +10,13 @@ extern crate yaml_rust2;
 
 use crate::cli;
 use crate::errors;
-use crate::rules::{OutputPolicy, Rules};
+use crate::rules::{
+    Backoff, GitTrigger, HealthCheck, IoPriority, OnChange, OutputPolicy, ProblemMatcher,
+    ProcessLimits, Readiness, ReadyProbe, ResourceLock, RestartPolicy, Rules, Severity, StopPolicy,
+    TestReports, Timestamps, When,
+};
 use crate::yaml;
+use nix::sys::signal::Signal;
 
 use self::yaml_rust2::Yaml;
 use self::yaml_rust2::YamlLoader;
@@ -146,9 +151,16 @@ pub fn from_yaml(file_content: &str) -> errors::Result<Vec<Rules>> {
 struct CommonRules {
     change: Vec<String>,
     ignore: Vec<String>,
+    /// Repository events (`on.git`) every job runs on, merged first.
+    git: Vec<GitTrigger>,
     /// Execution-level default output policy, applied to jobs without their
     /// own `output:`.
     output_policy: OutputPolicy,
+    /// Line timestamps for every job (`execution.timestamps`).
+    timestamps: Timestamps,
+    /// Declared lock capacities (`execution.resources`); undeclared locks
+    /// are exclusive.
+    resources: std::collections::BTreeMap<String, usize>,
 }
 
 fn validate_section(
@@ -241,8 +253,9 @@ fn output_policy_from_root(root: &Yaml) -> errors::Result<OutputPolicy> {
             "quiet" => Ok(OutputPolicy::Quiet),
             "capture" => Ok(OutputPolicy::Capture),
             "show-on-failure" => Ok(OutputPolicy::ShowOnFailure),
+            "grouped" => Ok(OutputPolicy::Grouped),
             _ => Err(errors::FzzError::InvalidConfigError(
-                format!("Property 'execution.output' has invalid value '{raw}': expected inherit, quiet, capture, or show-on-failure"), None, None,
+                format!("Property 'execution.output' has invalid value '{raw}': expected inherit, quiet, capture, show-on-failure, or grouped"), None, None,
             )),
         },
         _ => Err(errors::FzzError::InvalidConfigError("Property 'execution.output' must be a string".to_owned(), None, None)),
@@ -264,15 +277,13 @@ fn parse_hash_format(yaml: &Yaml, allow_empty: bool) -> errors::Result<Vec<Rules
     }
     let key = if has_jobs { "jobs" } else { "tasks" };
 
-    if has_tasks {
-        if yaml["execution"]["recovery_policy"] != Yaml::BadValue {
-            return Err(errors::FzzError::InvalidConfigError(
-                "Property 'execution.recovery_policy' is supported only in preferred V2 jobs"
-                    .to_owned(),
-                None,
-                Some("Rename 'tasks' to 'jobs' before configuring recovery policy.".to_owned()),
-            ));
-        }
+    if has_tasks && yaml["execution"]["recovery_policy"] != Yaml::BadValue {
+        return Err(errors::FzzError::InvalidConfigError(
+            "Property 'execution.recovery_policy' is supported only in preferred V2 jobs"
+                .to_owned(),
+            None,
+            Some("Rename 'tasks' to 'jobs' before configuring recovery policy.".to_owned()),
+        ));
     }
 
     // Extract the jobs/tasks array (ordered list; mapping form is rejected so
@@ -330,6 +341,8 @@ fn parse_hash_format(yaml: &Yaml, allow_empty: bool) -> errors::Result<Vec<Rules
     // has its own V2 owner and is inherited by jobs that omit `output`.
     let mut common_rules = extract_common_rules(&yaml["on"])?;
     common_rules.output_policy = output_policy_from_root(yaml)?;
+    common_rules.timestamps = timestamps_from_root(yaml)?;
+    common_rules.resources = resources_from_root(yaml)?;
 
     // Parse each task and merge with common rules; duplicate names are a
     // config bug (TASK-0075/0076), never a silent merge or reorder.
@@ -358,6 +371,24 @@ fn parse_hash_format(yaml: &Yaml, allow_empty: bool) -> errors::Result<Vec<Rules
         rules.push(rule);
     }
 
+    // Take-back always returns the terminal to funzzy's own group, so a
+    // second interactive service would silently lose it to the first.
+    let interactive_services: Vec<&str> = rules
+        .iter()
+        .filter(|rule| rule.interactive() && rule.service())
+        .map(|rule| rule.name.as_str())
+        .collect();
+    if interactive_services.len() > 1 {
+        return Err(errors::FzzError::InvalidConfigError(
+            format!(
+                "Only one interactive service is allowed, found: {}",
+                interactive_services.join(", ")
+            ),
+            None,
+            Some("Keep 'interactive' on a single service job.".to_owned()),
+        ));
+    }
+
     Ok(rules)
 }
 
@@ -369,7 +400,10 @@ fn extract_common_rules(yaml: &Yaml) -> errors::Result<CommonRules> {
             Ok(CommonRules {
                 change: vec![],
                 ignore: vec![],
+                git: vec![],
                 output_policy: OutputPolicy::Inherit,
+                timestamps: Timestamps::None,
+                resources: std::collections::BTreeMap::new(),
             })
         }
         Yaml::Hash(_) => {
@@ -379,7 +413,10 @@ fn extract_common_rules(yaml: &Yaml) -> errors::Result<CommonRules> {
             Ok(CommonRules {
                 change: ensure_glob_only(change, "on.change")?,
                 ignore: ensure_glob_only(ignore, "on.ignore")?,
+                git: git_triggers_from_yaml(yaml, "on.git")?,
                 output_policy: OutputPolicy::Inherit,
+                timestamps: Timestamps::None,
+                resources: std::collections::BTreeMap::new(),
             })
         }
         _ => Err(errors::FzzError::InvalidConfigError(
@@ -422,7 +459,7 @@ fn rule_from_with_common(yaml: &Yaml, common: &CommonRules) -> errors::Result<Ru
     }
 
     let name = yaml::extract_string(yaml, "name")?;
-    let commands = yaml::extract_list(yaml, "run")?;
+    let (commands, continue_on_error) = run_steps_from_yaml(yaml, &name)?;
 
     // Tasks EXTEND the shared `on` rules; they never replace them. A task's
     // own `change` and `ignore` are appended to (and deduped against) the
@@ -437,7 +474,7 @@ fn rule_from_with_common(yaml: &Yaml, common: &CommonRules) -> errors::Result<Ru
     let parallel = yaml::extract_optional_string(yaml, "parallel")?;
     let cwd = yaml::extract_optional_string(yaml, "cwd")?;
     let environment = yaml::extract_optional_string_map(yaml, "env")?;
-    let recovery = recovery_commands_from_yaml(yaml, &name)?;
+    let recovery = commands_from_yaml(yaml, &name, "recovery")?;
     // Strict: `service` must be a boolean when present (TASK-0035); a typo
     // like `yes` must not silently disable service management.
     let service = match &yaml["service"] {
@@ -454,6 +491,27 @@ fn rule_from_with_common(yaml: &Yaml, common: &CommonRules) -> errors::Result<Ru
             ))
         }
     };
+    let tty = job_flag(yaml, "tty", &name)?;
+    let interactive = job_flag(yaml, "interactive", &name)?;
+    if interactive {
+        // The job must be the only thing reading the terminal, and it needs
+        // the terminal itself rather than a pseudo-terminal of its own.
+        let conflict = match (&parallel, tty) {
+            (Some(group), _) => Some(format!("it runs in parallel group '{}'", group)),
+            (None, true) => Some("tty is true".to_owned()),
+            (None, false) => None,
+        };
+        if let Some(conflict) = conflict {
+            return Err(errors::FzzError::InvalidConfigError(
+                format!("Job '{}' cannot be interactive because {}", name, conflict),
+                None,
+                Some(
+                    "Interactive jobs run alone in the foreground: remove 'parallel' and 'tty'."
+                        .to_owned(),
+                ),
+            ));
+        }
+    }
     if service && recovery.is_some() {
         return Err(errors::FzzError::InvalidConfigError(
             format!(
@@ -475,10 +533,11 @@ fn rule_from_with_common(yaml: &Yaml, common: &CommonRules) -> errors::Result<Ru
             "quiet" => OutputPolicy::Quiet,
             "capture" => OutputPolicy::Capture,
             "show-on-failure" => OutputPolicy::ShowOnFailure,
+            "grouped" => OutputPolicy::Grouped,
             other => {
                 return Err(errors::FzzError::InvalidConfigError(
                     format!(
-                        "Invalid output policy '{}' for job '{}': expected inherit, quiet, capture, or show-on-failure",
+                        "Invalid output policy '{}' for job '{}': expected inherit, quiet, capture, show-on-failure, or grouped",
                         other, name
                     ),
                     None,
@@ -488,10 +547,99 @@ fn rule_from_with_common(yaml: &Yaml, common: &CommonRules) -> errors::Result<Ru
         },
     };
 
+    let mut git = common.git.clone();
+    for trigger in git_triggers_from_yaml(yaml, "git")? {
+        if !git.contains(&trigger) {
+            git.push(trigger);
+        }
+    }
+
+    let locks = locks_from_yaml(yaml, &name, &common.resources)?;
+    if service && !locks.is_empty() {
+        return Err(errors::FzzError::InvalidConfigError(
+            format!("Job '{}' cannot declare 'locks' when service is true", name),
+            None,
+            Some(
+                "A service holds its locks for the whole run, so jobs sharing them would never start."
+                    .to_owned(),
+            ),
+        ));
+    }
+    let global_lock = global_lock_from_yaml(yaml, &name)?;
+    if service && global_lock.is_some() {
+        return Err(errors::FzzError::InvalidConfigError(
+            format!("Job '{}' cannot declare 'global_lock' when service is true", name),
+            None,
+            Some(
+                "A service would hold the global lock for the whole run, so other funzzy instances sharing it would never start."
+                    .to_owned(),
+            ),
+        ));
+    }
+    let limits = limits_from_yaml(yaml, &name)?;
+    let stop = stop_from_yaml(yaml, &name)?;
+    let ready = ready_from_yaml(yaml, &name)?;
+    let restart = restart_from_yaml(yaml, &name)?;
+    let health = health_from_yaml(yaml, &name)?;
+    let on_change = on_change_from_yaml(yaml, &name)?;
+    let teardown = commands_from_yaml(yaml, &name, "stop")?;
+    let matchers = matchers_from_yaml(yaml, &name)?;
+    let reports = reports_from_yaml(yaml, &name)?;
+    let success_codes = success_codes_from_yaml(yaml, &name)?;
+    let allow_failure = job_flag(yaml, "allow_failure", &name)?;
+    let when = when_from_yaml(yaml, &name)?;
+    if service && when != When::OnSuccess {
+        return Err(errors::FzzError::InvalidConfigError(
+            format!("Job '{}' cannot declare 'when' when service is true", name),
+            None,
+            Some(
+                "Services keep running across stages; use `stop` commands to clean up after them."
+                    .to_owned(),
+            ),
+        ));
+    }
+    for (property, declared) in [
+        ("ready", ready.is_some()),
+        ("restart", restart.is_some()),
+        ("health", health.is_some()),
+        ("on_change", on_change.is_some()),
+        ("stop", teardown.is_some()),
+    ] {
+        if declared && !service {
+            return Err(errors::FzzError::InvalidConfigError(
+                format!("Job '{}' declares '{}' but is not a service", name, property),
+                None,
+                Some(
+                    "Readiness, restarts, health checks, change actions and stop commands apply to running services; add `service: true`."
+                        .to_owned(),
+                ),
+            ));
+        }
+    }
+
     let rule = Rules::new(name, commands, watch_patterns, ignore_patterns, run_on_init)
         .with_execution_context(cwd, environment)
         .with_inherited_patterns(inherited_patterns(common))
+        .with_git_triggers(git)
+        .with_locks(locks)
+        .with_global_lock(global_lock)
+        .with_limits(limits)
+        .with_tty(tty)
+        .with_interactive(interactive)
+        .with_stop(stop)
+        .with_ready(ready)
+        .with_restart(restart.unwrap_or_default())
+        .with_health(health)
+        .with_on_change(on_change.unwrap_or_default())
+        .with_teardown(teardown.unwrap_or_default())
+        .with_matchers(matchers)
+        .with_reports(reports)
+        .with_success_codes(success_codes)
+        .with_allow_failure(allow_failure)
+        .with_when(when)
+        .with_continue_on_error(continue_on_error)
         .with_output(output)
+        .with_timestamps(common.timestamps)
         .with_service(service);
     let rule = match recovery {
         Some(commands) => rule.with_recovery(commands),
@@ -503,19 +651,76 @@ fn rule_from_with_common(yaml: &Yaml, common: &CommonRules) -> errors::Result<Ru
     })
 }
 
-fn recovery_commands_from_yaml(yaml: &Yaml, name: &str) -> errors::Result<Option<Vec<String>>> {
-    match &yaml["recovery"] {
+/// Reads `run`: a command, or a list whose items are commands or
+/// `{ run, continue_on_error }` steps. Returns the commands and the indexes
+/// of the steps whose failure does not fail the job.
+fn run_steps_from_yaml(yaml: &Yaml, name: &str) -> errors::Result<(Vec<String>, Vec<usize>)> {
+    let Yaml::Array(items) = &yaml["run"] else {
+        return Ok((yaml::extract_list(yaml, "run")?, vec![]));
+    };
+    let mut commands = Vec::with_capacity(items.len());
+    let mut continue_on_error = vec![];
+    for (index, item) in items.iter().enumerate() {
+        let Yaml::Hash(step) = item else {
+            commands.push(item.as_str().unwrap_or("_invalid_value_").to_owned());
+            continue;
+        };
+        let invalid = |detail: &str| {
+            errors::FzzError::InvalidConfigError(
+                format!(
+                    "Invalid step {} of 'run' in job '{}': {}",
+                    index + 1,
+                    name,
+                    detail
+                ),
+                None,
+                Some(
+                    "Example:\nrun:\n  - cargo build\n  - run: cargo clippy\n    continue_on_error: true"
+                        .to_owned(),
+                ),
+            )
+        };
+        if step
+            .keys()
+            .any(|key| !matches!(key.as_str(), Some("run" | "continue_on_error")))
+        {
+            return Err(invalid("only 'run' and 'continue_on_error' are allowed"));
+        }
+        match item["run"].as_str() {
+            Some(command) if !command.trim().is_empty() => commands.push(command.to_owned()),
+            _ => return Err(invalid("expected a non-empty 'run' command")),
+        }
+        match &item["continue_on_error"] {
+            Yaml::BadValue | Yaml::Boolean(false) => {}
+            Yaml::Boolean(true) => continue_on_error.push(index),
+            _ => return Err(invalid("'continue_on_error' must be a boolean")),
+        }
+    }
+    Ok((commands, continue_on_error))
+}
+
+/// Reads an optional command string or ordered command list, such as
+/// `recovery` or `stop`.
+fn commands_from_yaml(
+    yaml: &Yaml,
+    name: &str,
+    property: &str,
+) -> errors::Result<Option<Vec<String>>> {
+    match &yaml[property] {
         Yaml::BadValue => Ok(None),
         Yaml::String(command) if command.trim().is_empty() => {
             Err(errors::FzzError::InvalidConfigError(
-                format!("Job '{}' recovery must be a non-empty command", name),
+                format!("Job '{}' {} must be a non-empty command", name, property),
                 None,
                 None,
             ))
         }
         Yaml::String(command) => Ok(Some(vec![command.clone()])),
         Yaml::Array(commands) if commands.is_empty() => Err(errors::FzzError::InvalidConfigError(
-            format!("Job '{}' recovery must contain at least one command", name),
+            format!(
+                "Job '{}' {} must contain at least one command",
+                name, property
+            ),
             None,
             None,
         )),
@@ -525,8 +730,9 @@ fn recovery_commands_from_yaml(yaml: &Yaml, name: &str) -> errors::Result<Option
                 let Some(command) = command.as_str() else {
                     return Err(errors::FzzError::InvalidConfigError(
                         format!(
-                            "Job '{}' recovery command {} must be a string",
+                            "Job '{}' {} command {} must be a string",
                             name,
+                            property,
                             index + 1
                         ),
                         None,
@@ -536,8 +742,9 @@ fn recovery_commands_from_yaml(yaml: &Yaml, name: &str) -> errors::Result<Option
                 if command.trim().is_empty() {
                     return Err(errors::FzzError::InvalidConfigError(
                         format!(
-                            "Job '{}' recovery command {} must be non-empty",
+                            "Job '{}' {} command {} must be non-empty",
                             name,
+                            property,
                             index + 1
                         ),
                         None,
@@ -550,8 +757,834 @@ fn recovery_commands_from_yaml(yaml: &Yaml, name: &str) -> errors::Result<Option
         }
         _ => Err(errors::FzzError::InvalidConfigError(
             format!(
-                "Job '{}' recovery must be a command string or ordered string list",
-                name
+                "Job '{}' {} must be a command string or ordered string list",
+                name, property
+            ),
+            None,
+            None,
+        )),
+    }
+}
+
+/// Parses `git:` repository triggers (a scalar or list of `head_change`,
+/// `commit`, `index_change`). Unknown events are a config bug, never ignored.
+fn git_triggers_from_yaml(yaml: &Yaml, field_name: &str) -> errors::Result<Vec<GitTrigger>> {
+    let raw = match &yaml["git"] {
+        Yaml::BadValue => return Ok(vec![]),
+        Yaml::String(value) => vec![value.clone()],
+        Yaml::Array(values) => {
+            let mut raw = Vec::with_capacity(values.len());
+            for value in values {
+                let Some(value) = value.as_str() else {
+                    return Err(errors::FzzError::InvalidConfigError(
+                        format!("Property '{field_name}' must contain only strings"),
+                        None,
+                        None,
+                    ));
+                };
+                raw.push(value.to_owned());
+            }
+            raw
+        }
+        _ => {
+            return Err(errors::FzzError::InvalidConfigError(
+                format!("Property '{field_name}' must be a string or a list of strings"),
+                None,
+                None,
+            ))
+        }
+    };
+    let mut triggers = vec![];
+    for value in raw {
+        let Some(trigger) = GitTrigger::parse(&value) else {
+            return Err(errors::FzzError::InvalidConfigError(
+                format!(
+                    "Property '{field_name}' has invalid value '{value}': expected {}",
+                    GitTrigger::VALUES.join(", ")
+                ),
+                None,
+                None,
+            ));
+        };
+        if !triggers.contains(&trigger) {
+            triggers.push(trigger);
+        }
+    }
+    Ok(triggers)
+}
+
+/// Reads `execution.resources`: lock name -> how many jobs may hold it at once.
+fn timestamps_from_root(root: &Yaml) -> errors::Result<Timestamps> {
+    match &root["execution"]["timestamps"] {
+        Yaml::BadValue => Ok(Timestamps::None),
+        Yaml::String(raw) => Timestamps::parse(raw).ok_or_else(|| {
+            errors::FzzError::InvalidConfigError(
+                format!("Property 'execution.timestamps' has invalid value '{raw}': expected wall, elapsed, or none"),
+                None,
+                None,
+            )
+        }),
+        _ => Err(errors::FzzError::InvalidConfigError(
+            "Property 'execution.timestamps' must be a string".to_owned(),
+            None,
+            None,
+        )),
+    }
+}
+
+fn resources_from_root(root: &Yaml) -> errors::Result<std::collections::BTreeMap<String, usize>> {
+    let invalid = |message: String| errors::FzzError::InvalidConfigError(message, None, None);
+    let mut resources = std::collections::BTreeMap::new();
+    let properties = match &root["execution"]["resources"] {
+        Yaml::BadValue => return Ok(resources),
+        Yaml::Hash(properties) => properties,
+        _ => {
+            return Err(invalid(
+                "Property 'execution.resources' must map lock names to capacities".to_owned(),
+            ))
+        }
+    };
+    for (key, value) in properties {
+        let Some(name) = key.as_str().filter(|name| !name.trim().is_empty()) else {
+            return Err(invalid(
+                "Property 'execution.resources' keys must be non-empty lock names".to_owned(),
+            ));
+        };
+        match value {
+            Yaml::Integer(capacity) if *capacity > 0 => {
+                resources.insert(name.to_owned(), *capacity as usize);
+            }
+            _ => {
+                return Err(invalid(format!(
+                    "Property 'execution.resources.{name}' must be a positive integer"
+                )))
+            }
+        }
+    }
+    Ok(resources)
+}
+
+/// Reads `jobs[].locks` (a name or a list), resolving each capacity from
+/// `execution.resources`. Duplicates collapse in declaration order.
+fn locks_from_yaml(
+    yaml: &Yaml,
+    name: &str,
+    resources: &std::collections::BTreeMap<String, usize>,
+) -> errors::Result<Vec<ResourceLock>> {
+    let invalid = || {
+        errors::FzzError::InvalidConfigError(
+            format!("Invalid 'locks' for job '{name}': expected a lock name or a list of names"),
+            None,
+            Some("Example:\nlocks: [db]".to_owned()),
+        )
+    };
+    let raw: Vec<&str> = match &yaml["locks"] {
+        Yaml::BadValue => return Ok(vec![]),
+        Yaml::String(lock) => vec![lock.as_str()],
+        Yaml::Array(values) => values
+            .iter()
+            .map(|value| value.as_str().ok_or_else(invalid))
+            .collect::<errors::Result<_>>()?,
+        _ => return Err(invalid()),
+    };
+    let mut locks: Vec<ResourceLock> = vec![];
+    for lock in raw {
+        let lock = lock.trim();
+        if lock.is_empty() {
+            return Err(invalid());
+        }
+        if locks.iter().any(|held| held.name == lock) {
+            continue;
+        }
+        locks.push(ResourceLock {
+            name: lock.to_owned(),
+            capacity: resources.get(lock).copied().unwrap_or(1),
+        });
+    }
+    Ok(locks)
+}
+
+/// Reads `jobs[].limits`, `jobs[].nice` and `jobs[].ionice`.
+fn limits_from_yaml(yaml: &Yaml, name: &str) -> errors::Result<ProcessLimits> {
+    let invalid = |property: &str, expected: &str, example: &str| {
+        errors::FzzError::InvalidConfigError(
+            format!("Invalid '{property}' for job '{name}': expected {expected}"),
+            None,
+            Some(format!("Example:\n{example}")),
+        )
+    };
+    let mut limits = ProcessLimits::default();
+    match &yaml["limits"] {
+        Yaml::BadValue => {}
+        Yaml::Hash(hash) => {
+            for (key, value) in hash {
+                let key = key.as_str().unwrap_or_default();
+                let raw = match value {
+                    Yaml::Integer(number) => number.to_string(),
+                    Yaml::String(text) => text.clone(),
+                    _ => String::new(),
+                };
+                let property = format!("limits.{key}");
+                match key {
+                    "memory" => {
+                        limits.memory =
+                            Some(parse_size(&raw).filter(|bytes| *bytes > 0).ok_or_else(|| {
+                                invalid(
+                                    &property,
+                                    "a size such as 512M or 4G",
+                                    "limits:\n  memory: 4G",
+                                )
+                            })?)
+                    }
+                    "core" => {
+                        limits.core = Some(parse_size(&raw).ok_or_else(|| {
+                            invalid(&property, "a size such as 0 or 64M", "limits:\n  core: 0")
+                        })?)
+                    }
+                    "cpu" => {
+                        let duration = parse_debounce(&raw).ok().flatten().ok_or_else(|| {
+                            invalid(
+                                &property,
+                                "a duration such as 90s or 10m",
+                                "limits:\n  cpu: 10m",
+                            )
+                        })?;
+                        limits.cpu_seconds = Some(duration.as_millis().div_ceil(1000) as u64);
+                    }
+                    "open_files" => {
+                        limits.open_files = Some(
+                            raw.parse::<u64>()
+                                .ok()
+                                .filter(|count| *count > 0)
+                                .ok_or_else(|| {
+                                    invalid(
+                                        &property,
+                                        "a positive integer",
+                                        "limits:\n  open_files: 1024",
+                                    )
+                                })?,
+                        )
+                    }
+                    _ => {
+                        return Err(errors::FzzError::InvalidConfigError(
+                            format!(
+                            "Invalid property '{property}' in job '{name}'. Only {} are allowed.",
+                            crate::option_catalog::LIMIT_FIELDS.join(", ")
+                        ),
+                            None,
+                            Some("Example:\nlimits:\n  memory: 4G\n  cpu: 10m".to_owned()),
+                        ))
+                    }
+                }
+            }
+        }
+        _ => {
+            return Err(invalid(
+                "limits",
+                "a mapping of memory, cpu, open_files or core",
+                "limits:\n  memory: 4G",
+            ))
+        }
+    }
+    match &yaml["nice"] {
+        Yaml::BadValue => {}
+        Yaml::Integer(nice) if (-20..=19).contains(nice) => limits.nice = Some(*nice as i32),
+        _ => return Err(invalid("nice", "an integer from -20 to 19", "nice: 10")),
+    }
+    match &yaml["ionice"] {
+        Yaml::BadValue => {}
+        Yaml::String(class) => {
+            limits.ionice = Some(parse_ionice(class).ok_or_else(|| {
+                invalid(
+                    "ionice",
+                    "idle, best-effort or best-effort:LEVEL (0-7)",
+                    "ionice: idle",
+                )
+            })?)
+        }
+        _ => {
+            return Err(invalid(
+                "ionice",
+                "idle, best-effort or best-effort:LEVEL (0-7)",
+                "ionice: idle",
+            ))
+        }
+    }
+    Ok(limits)
+}
+
+/// How long a service may take to become ready when `ready.timeout` is unset.
+pub const DEFAULT_READY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Reads `jobs[].ready`: one probe plus an optional `timeout`.
+fn ready_from_yaml(yaml: &Yaml, name: &str) -> errors::Result<Option<Readiness>> {
+    let invalid = |property: &str, expected: &str| {
+        errors::FzzError::InvalidConfigError(
+            format!("Invalid '{property}' for job '{name}': expected {expected}"),
+            None,
+            Some("Example:\nready:\n  tcp: 3000\n  timeout: 60s".to_owned()),
+        )
+    };
+    let hash = match &yaml["ready"] {
+        Yaml::BadValue => return Ok(None),
+        Yaml::Hash(hash) => hash,
+        _ => {
+            return Err(invalid(
+                "ready",
+                "a mapping with one of tcp, log, file or command",
+            ))
+        }
+    };
+    let mut probe = None;
+    let mut timeout = DEFAULT_READY_TIMEOUT;
+    for (key, value) in hash {
+        let key = key.as_str().unwrap_or_default();
+        let property = format!("ready.{key}");
+        if key == "timeout" {
+            timeout = duration_from_yaml(value)
+                .ok_or_else(|| invalid(&property, "a duration such as 30s or 2m"))?;
+            continue;
+        }
+        if !["tcp", "log", "file", "command"].contains(&key) {
+            return Err(errors::FzzError::InvalidConfigError(
+                format!(
+                    "Invalid property '{property}' in job '{name}'. Only {} are allowed.",
+                    crate::option_catalog::READY_FIELDS.join(", ")
+                ),
+                None,
+                Some("Example:\nready:\n  tcp: 3000".to_owned()),
+            ));
+        }
+        let next = probe_from_yaml(key, value)
+            .ok_or_else(|| invalid(&property, probe_expectation(key)))?;
+        if probe.replace(next).is_some() {
+            return Err(invalid("ready", "exactly one of tcp, log, file or command"));
+        }
+    }
+    let probe = probe.ok_or_else(|| invalid("ready", "one of tcp, log, file or command"))?;
+    Ok(Some(Readiness { probe, timeout }))
+}
+
+/// Reads one `tcp`, `log`, `file` or `command` probe value.
+fn probe_from_yaml(key: &str, value: &Yaml) -> Option<ReadyProbe> {
+    match (key, value) {
+        ("tcp", Yaml::Integer(port)) => {
+            u16::try_from(*port)
+                .ok()
+                .filter(|port| *port > 0)
+                .map(|port| ReadyProbe::Tcp {
+                    host: "localhost".to_owned(),
+                    port,
+                })
+        }
+        ("tcp", Yaml::String(address)) => address.rsplit_once(':').and_then(|(host, port)| {
+            let port = port.parse::<u16>().ok().filter(|port| *port > 0)?;
+            (!host.is_empty()).then(|| ReadyProbe::Tcp {
+                host: host.to_owned(),
+                port,
+            })
+        }),
+        ("log", Yaml::String(pattern)) => regex::Regex::new(pattern)
+            .ok()
+            .map(|_| ReadyProbe::Log(pattern.clone())),
+        ("file", Yaml::String(path)) if !path.trim().is_empty() => {
+            Some(ReadyProbe::File(path.clone()))
+        }
+        ("command", Yaml::String(command)) if !command.trim().is_empty() => {
+            Some(ReadyProbe::Command(command.clone()))
+        }
+        _ => None,
+    }
+}
+
+fn probe_expectation(key: &str) -> &'static str {
+    match key {
+        "tcp" => "a port or host:port",
+        "log" => "a valid regular expression",
+        "file" => "a path",
+        _ => "a shell command",
+    }
+}
+
+/// Reads a duration written as seconds or with a unit (`30s`, `2m`).
+fn duration_from_yaml(value: &Yaml) -> Option<Duration> {
+    let raw = match value {
+        Yaml::Integer(seconds) => seconds.to_string(),
+        Yaml::String(raw) => raw.clone(),
+        _ => return None,
+    };
+    parse_debounce(&raw).ok().flatten()
+}
+
+/// Reads `jobs[].restart`: `max`, `backoff`, `delay` and `reset_after`.
+/// Unset fields keep the default policy.
+fn restart_from_yaml(yaml: &Yaml, name: &str) -> errors::Result<Option<RestartPolicy>> {
+    let invalid = |property: &str, expected: &str| {
+        errors::FzzError::InvalidConfigError(
+            format!("Invalid '{property}' for job '{name}': expected {expected}"),
+            None,
+            Some(
+                "Example:\nrestart:\n  max: 10\n  backoff: exponential\n  reset_after: 1m"
+                    .to_owned(),
+            ),
+        )
+    };
+    let hash = match &yaml["restart"] {
+        Yaml::BadValue => return Ok(None),
+        Yaml::Hash(hash) => hash,
+        _ => return Err(invalid("restart", "a mapping")),
+    };
+    let mut restart = RestartPolicy::default();
+    for (key, value) in hash {
+        let key = key.as_str().unwrap_or_default();
+        let property = format!("restart.{key}");
+        match key {
+            "max" => {
+                restart.max = value
+                    .as_i64()
+                    .and_then(|max| usize::try_from(max).ok())
+                    .ok_or_else(|| invalid(&property, "a number of restarts"))?
+            }
+            "backoff" => {
+                restart.backoff = match value.as_str().map(str::trim) {
+                    Some("fixed") => Backoff::Fixed,
+                    Some("exponential") => Backoff::Exponential,
+                    _ => return Err(invalid(&property, "one of fixed, exponential")),
+                }
+            }
+            "delay" => {
+                restart.delay = duration_from_yaml(value)
+                    .ok_or_else(|| invalid(&property, "a duration such as 500ms or 2s"))?
+            }
+            "reset_after" => {
+                restart.reset_after = Some(
+                    duration_from_yaml(value)
+                        .ok_or_else(|| invalid(&property, "a duration such as 1m"))?,
+                )
+            }
+            _ => {
+                return Err(errors::FzzError::InvalidConfigError(
+                    format!(
+                        "Invalid property '{property}' in job '{name}'. Only {} are allowed.",
+                        crate::option_catalog::RESTART_FIELDS.join(", ")
+                    ),
+                    None,
+                    Some("Example:\nrestart:\n  max: 10".to_owned()),
+                ))
+            }
+        }
+    }
+    Ok(Some(restart))
+}
+
+/// Reads `jobs[].reports`: `junit: GLOB` and/or `tap: stdout`.
+fn reports_from_yaml(yaml: &Yaml, name: &str) -> errors::Result<TestReports> {
+    let example = "Example:\nreports:\n  junit: 'target/nextest/**/junit.xml'\n  tap: stdout";
+    let invalid = |property: &str, expected: &str| {
+        errors::FzzError::InvalidConfigError(
+            format!("Invalid '{property}' for job '{name}': expected {expected}"),
+            None,
+            Some(example.to_owned()),
+        )
+    };
+    let hash = match &yaml["reports"] {
+        Yaml::BadValue => return Ok(TestReports::default()),
+        Yaml::Hash(hash) => hash,
+        _ => return Err(invalid("reports", "a mapping")),
+    };
+    let mut reports = TestReports::default();
+    for (key, value) in hash {
+        let key = key.as_str().unwrap_or_default();
+        let property = format!("reports.{key}");
+        match key {
+            "junit" => {
+                let pattern = value
+                    .as_str()
+                    .map(str::trim)
+                    .filter(|pattern| glob::Pattern::new(pattern).is_ok() && !pattern.is_empty())
+                    .ok_or_else(|| invalid(&property, "a glob of JUnit XML files"))?;
+                reports.junit = Some(pattern.to_owned());
+            }
+            "tap" => {
+                if value.as_str().map(str::trim) != Some("stdout") {
+                    return Err(invalid(&property, "stdout"));
+                }
+                reports.tap = true;
+            }
+            _ => {
+                return Err(errors::FzzError::InvalidConfigError(
+                    format!(
+                        "Invalid property '{property}' in job '{name}'. Only {} are allowed.",
+                        crate::option_catalog::REPORT_FIELDS.join(", ")
+                    ),
+                    None,
+                    Some(example.to_owned()),
+                ))
+            }
+        }
+    }
+    Ok(reports)
+}
+
+/// Reads `jobs[].success_codes`: one exit code or a list, `[0]` when unset.
+fn success_codes_from_yaml(yaml: &Yaml, name: &str) -> errors::Result<Vec<i32>> {
+    let invalid = || {
+        errors::FzzError::InvalidConfigError(
+            format!("Invalid 'success_codes' for job '{name}': expected exit codes from 0 to 255"),
+            None,
+            Some("Example:\nsuccess_codes: [0, 1]".to_owned()),
+        )
+    };
+    let values = match &yaml["success_codes"] {
+        Yaml::BadValue => return Ok(vec![0]),
+        Yaml::Array(values) if !values.is_empty() => values.as_slice(),
+        Yaml::Array(_) => return Err(invalid()),
+        value => std::slice::from_ref(value),
+    };
+    let mut codes = vec![];
+    for value in values {
+        let code = value
+            .as_i64()
+            .filter(|code| (0..=255).contains(code))
+            .ok_or_else(invalid)?;
+        if !codes.contains(&(code as i32)) {
+            codes.push(code as i32);
+        }
+    }
+    Ok(codes)
+}
+
+/// Reads `jobs[].when`, `on_success` when unset.
+fn when_from_yaml(yaml: &Yaml, name: &str) -> errors::Result<When> {
+    let raw = match &yaml["when"] {
+        Yaml::BadValue => return Ok(When::OnSuccess),
+        Yaml::String(raw) => raw.as_str(),
+        _ => "",
+    };
+    When::parse(raw).ok_or_else(|| {
+        errors::FzzError::InvalidConfigError(
+            format!(
+                "Invalid 'when' for job '{name}': expected {}",
+                When::VALUES.join(", ")
+            ),
+            None,
+            Some("Example:\nwhen: always".to_owned()),
+        )
+    })
+}
+
+/// How often a service's health is checked when `health.interval` is unset.
+pub const DEFAULT_HEALTH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Failed checks in a row that restart a service when `health.failures` is
+/// unset.
+pub const DEFAULT_HEALTH_FAILURES: usize = 3;
+
+/// Reads `jobs[].health`: one tcp, file or command probe plus optional
+/// `interval` and `failures`.
+fn health_from_yaml(yaml: &Yaml, name: &str) -> errors::Result<Option<HealthCheck>> {
+    let invalid = |property: &str, expected: &str| {
+        errors::FzzError::InvalidConfigError(
+            format!("Invalid '{property}' for job '{name}': expected {expected}"),
+            None,
+            Some(
+                "Example:\nhealth:\n  command: curl -sf localhost:3000/health\n  interval: 10s"
+                    .to_owned(),
+            ),
+        )
+    };
+    let hash = match &yaml["health"] {
+        Yaml::BadValue => return Ok(None),
+        Yaml::Hash(hash) => hash,
+        _ => {
+            return Err(invalid(
+                "health",
+                "a mapping with one of tcp, file or command",
+            ))
+        }
+    };
+    let mut probe = None;
+    let mut interval = DEFAULT_HEALTH_INTERVAL;
+    let mut failures = DEFAULT_HEALTH_FAILURES;
+    for (key, value) in hash {
+        let key = key.as_str().unwrap_or_default();
+        let property = format!("health.{key}");
+        match key {
+            "interval" => {
+                interval = duration_from_yaml(value)
+                    .ok_or_else(|| invalid(&property, "a duration such as 10s"))?;
+            }
+            "failures" => {
+                failures = value
+                    .as_i64()
+                    .and_then(|failures| usize::try_from(failures).ok())
+                    .filter(|failures| *failures > 0)
+                    .ok_or_else(|| invalid(&property, "a positive number of checks"))?;
+            }
+            "tcp" | "file" | "command" => {
+                let next = probe_from_yaml(key, value)
+                    .ok_or_else(|| invalid(&property, probe_expectation(key)))?;
+                if probe.replace(next).is_some() {
+                    return Err(invalid("health", "exactly one of tcp, file or command"));
+                }
+            }
+            _ => {
+                return Err(errors::FzzError::InvalidConfigError(
+                    format!(
+                        "Invalid property '{property}' in job '{name}'. Only {} are allowed.",
+                        crate::option_catalog::HEALTH_FIELDS.join(", ")
+                    ),
+                    None,
+                    Some("Example:\nhealth:\n  tcp: 3000".to_owned()),
+                ))
+            }
+        }
+    }
+    let probe = probe.ok_or_else(|| invalid("health", "one of tcp, file or command"))?;
+    Ok(Some(HealthCheck {
+        probe,
+        interval,
+        failures,
+    }))
+}
+
+/// Signals a job may ask to be stopped with (`jobs[].stop_signal`).
+pub const STOP_SIGNALS: &[&str] = &[
+    "SIGTERM", "SIGINT", "SIGHUP", "SIGQUIT", "SIGUSR1", "SIGUSR2",
+];
+
+/// Reads `jobs[].stop_signal` and `jobs[].stop_grace`.
+fn stop_from_yaml(yaml: &Yaml, name: &str) -> errors::Result<StopPolicy> {
+    let invalid = |property: &str, expected: String, example: &str| {
+        errors::FzzError::InvalidConfigError(
+            format!("Invalid '{property}' for job '{name}': expected {expected}"),
+            None,
+            Some(format!("Example:\n{example}")),
+        )
+    };
+    let mut stop = StopPolicy::default();
+    match &yaml["stop_signal"] {
+        Yaml::BadValue => {}
+        Yaml::String(raw) => {
+            stop.signal = Some(parse_stop_signal(raw).ok_or_else(|| {
+                invalid(
+                    "stop_signal",
+                    format!("one of {}", STOP_SIGNALS.join(", ")),
+                    "stop_signal: SIGINT",
+                )
+            })?)
+        }
+        _ => {
+            return Err(invalid(
+                "stop_signal",
+                format!("one of {}", STOP_SIGNALS.join(", ")),
+                "stop_signal: SIGINT",
+            ))
+        }
+    }
+    let grace = match &yaml["stop_grace"] {
+        Yaml::BadValue => None,
+        Yaml::Integer(seconds) => Some(seconds.to_string()),
+        Yaml::String(raw) => Some(raw.clone()),
+        _ => Some(String::new()),
+    };
+    if let Some(raw) = grace {
+        stop.grace = Some(parse_debounce(&raw).ok().flatten().ok_or_else(|| {
+            invalid(
+                "stop_grace",
+                "a duration such as 500ms or 20s".to_owned(),
+                "stop_grace: 20s",
+            )
+        })?);
+    }
+    Ok(stop)
+}
+
+/// Reads `jobs[].matchers`: a built-in matcher name, or a list of names and
+/// `{regex, severity}` entries.
+fn matchers_from_yaml(yaml: &Yaml, name: &str) -> errors::Result<Vec<ProblemMatcher>> {
+    let invalid = |reason: String| {
+        errors::FzzError::InvalidConfigError(
+            format!("Invalid 'matchers' for job '{}': {}", name, reason),
+            None,
+            Some(format!(
+                "Example:\nmatchers:\n  - rustc\n  - regex: '^(?P<file>[^:]+):(?P<line>\\d+): (?P<message>.+)$'\n    severity: warning\n\nBuilt-in matchers: {}",
+                crate::matchers::BUILTINS.join(", ")
+            )),
+        )
+    };
+    let entries = match &yaml["matchers"] {
+        Yaml::BadValue => return Ok(vec![]),
+        Yaml::Array(entries) => entries.clone(),
+        entry @ Yaml::String(_) => vec![entry.clone()],
+        _ => {
+            return Err(invalid(
+                "expected a matcher name or a list of matchers".to_owned(),
+            ))
+        }
+    };
+    let mut matchers = Vec::with_capacity(entries.len());
+    for entry in &entries {
+        let matcher = match entry {
+            Yaml::String(builtin) => {
+                let builtin = builtin.trim();
+                if !crate::matchers::BUILTINS.contains(&builtin) {
+                    return Err(invalid(format!("unknown matcher '{}'", builtin)));
+                }
+                ProblemMatcher::Builtin(builtin.to_owned())
+            }
+            Yaml::Hash(fields) => {
+                if let Some(key) = fields
+                    .keys()
+                    .filter_map(Yaml::as_str)
+                    .find(|key| !matches!(*key, "regex" | "severity"))
+                {
+                    return Err(invalid(format!(
+                        "unknown key '{}', expected regex or severity",
+                        key
+                    )));
+                }
+                let Some(regex) = entry["regex"].as_str() else {
+                    return Err(invalid(
+                        "a custom matcher needs a 'regex' string".to_owned(),
+                    ));
+                };
+                crate::matchers::validate_pattern(regex)
+                    .map_err(|reason| invalid(format!("regex '{}': {}", regex, reason)))?;
+                let severity = match &entry["severity"] {
+                    Yaml::BadValue => Severity::default(),
+                    Yaml::String(raw) => Severity::parse(raw).ok_or_else(|| {
+                        invalid(format!(
+                            "severity '{}', expected error, warning, or info",
+                            raw
+                        ))
+                    })?,
+                    _ => return Err(invalid("severity must be a string".to_owned())),
+                };
+                ProblemMatcher::Pattern {
+                    regex: regex.to_owned(),
+                    severity,
+                }
+            }
+            _ => {
+                return Err(invalid(
+                    "each matcher must be a name or a {regex, severity} mapping".to_owned(),
+                ))
+            }
+        };
+        if !matchers.contains(&matcher) {
+            matchers.push(matcher);
+        }
+    }
+    Ok(matchers)
+}
+
+/// Reads `jobs[].on_change`: `restart`, `signal:NAME`, `command:CMD` or
+/// `ignore`. Signal names may omit the `SIG` prefix.
+fn on_change_from_yaml(yaml: &Yaml, name: &str) -> errors::Result<Option<OnChange>> {
+    let invalid = || {
+        errors::FzzError::InvalidConfigError(
+            format!(
+                "Invalid 'on_change' for job '{name}': expected restart, signal:NAME, command:CMD or ignore"
+            ),
+            None,
+            Some(format!(
+                "Example:\non_change: signal:HUP\n\nSignals: {}",
+                STOP_SIGNALS.join(", ")
+            )),
+        )
+    };
+    let raw = match &yaml["on_change"] {
+        Yaml::BadValue => return Ok(None),
+        Yaml::String(raw) => raw.trim(),
+        _ => return Err(invalid()),
+    };
+    let on_change = match raw.split_once(':') {
+        None if raw == "restart" => OnChange::Restart,
+        None if raw == "ignore" => OnChange::Ignore,
+        Some(("signal", signal)) => {
+            let signal = signal.trim();
+            let signal = if signal.starts_with("SIG") {
+                signal.to_owned()
+            } else {
+                format!("SIG{}", signal)
+            };
+            OnChange::Signal(parse_stop_signal(&signal).ok_or_else(invalid)?)
+        }
+        Some(("command", command)) if !command.trim().is_empty() => {
+            OnChange::Command(command.trim().to_owned())
+        }
+        _ => return Err(invalid()),
+    };
+    Ok(Some(on_change))
+}
+
+fn parse_stop_signal(raw: &str) -> Option<Signal> {
+    let raw = raw.trim();
+    STOP_SIGNALS.contains(&raw).then(|| raw.parse().ok())?
+}
+
+/// Renders a duration the way `parse_debounce` reads it back (`20s`, `1500ms`).
+pub fn format_grace(grace: Duration) -> String {
+    let millis = grace.as_millis();
+    if millis.is_multiple_of(1_000) {
+        format!("{}s", millis / 1_000)
+    } else {
+        format!("{}ms", millis)
+    }
+}
+
+fn parse_ionice(raw: &str) -> Option<IoPriority> {
+    match raw.trim() {
+        "idle" => Some(IoPriority::Idle),
+        // The kernel's default best-effort level.
+        "best-effort" => Some(IoPriority::BestEffort(4)),
+        other => {
+            let level: u8 = other.strip_prefix("best-effort:")?.parse().ok()?;
+            (level <= 7).then_some(IoPriority::BestEffort(level))
+        }
+    }
+}
+
+/// Parses a byte size: a bare number of bytes or one with a binary
+/// `K`/`M`/`G`/`T` suffix (`512M`, `4G`).
+pub fn parse_size(raw: &str) -> Option<u64> {
+    let raw = raw.trim();
+    let (digits, shift) = match raw.char_indices().last()? {
+        (index, 'K' | 'k') => (&raw[..index], 10),
+        (index, 'M' | 'm') => (&raw[..index], 20),
+        (index, 'G' | 'g') => (&raw[..index], 30),
+        (index, 'T' | 't') => (&raw[..index], 40),
+        _ => (raw, 0),
+    };
+    digits
+        .trim()
+        .parse::<u64>()
+        .ok()?
+        .checked_mul(1u64 << shift)
+}
+
+/// Renders bytes with the largest exact binary suffix (`4G`, `1536M`).
+pub fn format_size(bytes: u64) -> String {
+    for (suffix, shift) in [("T", 40), ("G", 30), ("M", 20), ("K", 10)] {
+        let unit = 1u64 << shift;
+        if bytes >= unit && bytes.is_multiple_of(unit) {
+            return format!("{}{}", bytes / unit, suffix);
+        }
+    }
+    bytes.to_string()
+}
+
+/// Reads an optional boolean job property; anything but a boolean is an
+/// error so a typo like `yes` never silently disables it.
+fn job_flag(yaml: &Yaml, property: &str, name: &str) -> errors::Result<bool> {
+    match &yaml[property] {
+        Yaml::BadValue => Ok(false),
+        Yaml::Boolean(value) => Ok(*value),
+        _ => Err(errors::FzzError::InvalidConfigError(
+            format!(
+                "Invalid '{}' value for job '{}': must be a boolean",
+                property, name
             ),
             None,
             None,
@@ -559,6 +1592,21 @@ fn recovery_commands_from_yaml(yaml: &Yaml, name: &str) -> errors::Result<Option
     }
 }
 
+/// Reads `jobs[].global_lock`; the name becomes a lock file name.
+fn global_lock_from_yaml(yaml: &Yaml, name: &str) -> errors::Result<Option<String>> {
+    match &yaml["global_lock"] {
+        Yaml::BadValue => Ok(None),
+        Yaml::String(lock) if crate::global_lock::is_valid_name(lock) => Ok(Some(lock.clone())),
+        _ => Err(errors::FzzError::InvalidConfigError(
+            format!(
+                "Invalid 'global_lock' for job '{name}': expected a name of letters, digits, '-', '_' or '.'"
+            ),
+            None,
+            Some("Example:\nglobal_lock: cargo-target".to_owned()),
+        )),
+    }
+}
+
 /// Append task-specific patterns to the common ones, dropping duplicates.
 /// Common patterns keep their position so merged output stays stable.
 fn merge_patterns(common: &[String], task: Vec<String>) -> Vec<String> {
@@ -938,7 +1986,7 @@ pub fn from_default_file_config() -> errors::Result<Vec<Rules>> {
 pub fn rule_as_yaml(rule: &Rules) -> String {
     let mut lines = vec![format!("name: {}", rule.name)];
 
-    lines.push(render_scalar_or_list("run", &rule.commands()));
+    lines.push(render_run(rule));
     if let Some(recovery) = rule.recovery_commands() {
         lines.push(render_scalar_or_list("recovery", &recovery));
     }
@@ -954,10 +2002,165 @@ pub fn rule_as_yaml(rule: &Rules) -> String {
     if rule.run_on_init() {
         lines.push("run_on_init: true".to_owned());
     }
+    if !rule.git_triggers().is_empty() {
+        let git: Vec<String> = rule
+            .git_triggers()
+            .iter()
+            .map(|trigger| trigger.label().to_owned())
+            .collect();
+        lines.push(render_scalar_or_list("git", &git));
+    }
+    if !rule.locks().is_empty() {
+        let locks: Vec<String> = rule.locks().iter().map(|lock| lock.name.clone()).collect();
+        lines.push(render_scalar_or_list("locks", &locks));
+    }
+    if let Some(lock) = rule.global_lock() {
+        lines.push(format!("global_lock: {}", lock));
+    }
+    let limits = rule.limits();
+    let mut limit_lines = vec![];
+    if let Some(memory) = limits.memory {
+        limit_lines.push(format!("  memory: {}", format_size(memory)));
+    }
+    if let Some(cpu) = limits.cpu_seconds {
+        limit_lines.push(format!("  cpu: {}s", cpu));
+    }
+    if let Some(files) = limits.open_files {
+        limit_lines.push(format!("  open_files: {}", files));
+    }
+    if let Some(core) = limits.core {
+        limit_lines.push(format!("  core: {}", format_size(core)));
+    }
+    if !limit_lines.is_empty() {
+        lines.push("limits:".to_owned());
+        lines.extend(limit_lines);
+    }
+    if let Some(nice) = limits.nice {
+        lines.push(format!("nice: {}", nice));
+    }
+    if let Some(ionice) = limits.ionice {
+        lines.push(format!("ionice: {}", ionice.label()));
+    }
+    if rule.tty() {
+        lines.push("tty: true".to_owned());
+    }
+    if rule.interactive() {
+        lines.push("interactive: true".to_owned());
+    }
+    if let Some(ready) = rule.ready() {
+        lines.push("ready:".to_owned());
+        lines.push(probe_as_yaml(&ready.probe));
+        if ready.timeout != DEFAULT_READY_TIMEOUT {
+            lines.push(format!("  timeout: {}", format_grace(ready.timeout)));
+        }
+    }
+    let restart = rule.restart();
+    if restart != RestartPolicy::default() {
+        let defaults = RestartPolicy::default();
+        lines.push("restart:".to_owned());
+        if restart.max != defaults.max {
+            lines.push(format!("  max: {}", restart.max));
+        }
+        if restart.backoff == Backoff::Exponential {
+            lines.push("  backoff: exponential".to_owned());
+        }
+        if restart.delay != defaults.delay {
+            lines.push(format!("  delay: {}", format_grace(restart.delay)));
+        }
+        if let Some(reset_after) = restart.reset_after {
+            lines.push(format!("  reset_after: {}", format_grace(reset_after)));
+        }
+    }
+    if !rule.teardown().is_empty() {
+        lines.push(render_scalar_or_list("stop", rule.teardown()));
+    }
+    if !rule.matchers().is_empty() {
+        lines.push("matchers:".to_owned());
+        for matcher in rule.matchers() {
+            match matcher {
+                ProblemMatcher::Builtin(name) => lines.push(format!("  - {}", name)),
+                ProblemMatcher::Pattern { regex, severity } => {
+                    lines.push(format!("  - regex: {}", regex));
+                    if *severity != Severity::default() {
+                        lines.push(format!("    severity: {}", severity.label()));
+                    }
+                }
+            }
+        }
+    }
+    let reports = rule.reports();
+    if !reports.is_empty() {
+        lines.push("reports:".to_owned());
+        if let Some(junit) = &reports.junit {
+            lines.push(format!("  junit: {}", junit));
+        }
+        if reports.tap {
+            lines.push("  tap: stdout".to_owned());
+        }
+    }
+    if rule.success_codes() != [0] {
+        let codes: Vec<String> = rule.success_codes().iter().map(i32::to_string).collect();
+        lines.push(format!("success_codes: [{}]", codes.join(", ")));
+    }
+    if rule.allow_failure() {
+        lines.push("allow_failure: true".to_owned());
+    }
+    if rule.when() != When::OnSuccess {
+        lines.push(format!("when: {}", rule.when().label()));
+    }
+    if rule.on_change() != &OnChange::Restart {
+        lines.push(format!("on_change: {}", rule.on_change().label()));
+    }
+    if let Some(health) = rule.health() {
+        lines.push("health:".to_owned());
+        lines.push(probe_as_yaml(&health.probe));
+        if health.interval != DEFAULT_HEALTH_INTERVAL {
+            lines.push(format!("  interval: {}", format_grace(health.interval)));
+        }
+        if health.failures != DEFAULT_HEALTH_FAILURES {
+            lines.push(format!("  failures: {}", health.failures));
+        }
+    }
+    let stop = rule.stop();
+    if let Some(signal) = stop.signal {
+        lines.push(format!("stop_signal: {}", signal.as_str()));
+    }
+    if let Some(grace) = stop.grace {
+        lines.push(format!("stop_grace: {}", format_grace(grace)));
+    }
 
     lines.join("\n")
 }
 
+fn probe_as_yaml(probe: &ReadyProbe) -> String {
+    match probe {
+        ReadyProbe::Tcp { host, port } if host == "localhost" => format!("  tcp: {}", port),
+        ReadyProbe::Tcp { host, port } => format!("  tcp: {}:{}", host, port),
+        ReadyProbe::Log(pattern) => format!("  log: {}", pattern),
+        ReadyProbe::File(path) => format!("  file: {}", path),
+        ReadyProbe::Command(command) => format!("  command: {}", command),
+    }
+}
+
+/// Renders `run`, spelling steps that continue on error as mappings.
+fn render_run(rule: &Rules) -> String {
+    let commands = rule.commands();
+    if !(0..commands.len()).any(|index| rule.continues_on_error(index)) {
+        return render_scalar_or_list("run", &commands);
+    }
+
+    let items = commands
+        .iter()
+        .enumerate()
+        .map(|(index, command)| match rule.continues_on_error(index) {
+            true => format!("  - run: {}\n    continue_on_error: true", command),
+            false => format!("  - {}", command),
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+    format!("run:\n{}", items)
+}
+
 fn render_scalar_or_list(prop: &str, values: &[String]) -> String {
     if values.len() == 1 {
         return format!("{}: {}", prop, values[0]);
@@ -2220,6 +3423,189 @@ mod jobs_tests {
             from_yaml("tasks:\n  - name: check\n    run: check\n    recovery: repair\n").is_err()
         );
     }
+
+    #[test]
+    fn matchers_accept_built_in_names_and_custom_patterns() {
+        let rules = from_yaml(
+            "jobs:\n  - name: build\n    run: cargo build\n    matchers: rustc\n  - name: lint\n    run: ./lint\n    matchers:\n      - eslint\n      - regex: '^(?P<file>[^:]+):(?P<line>\\d+) (?P<message>.+)$'\n        severity: warning\n",
+        )
+        .unwrap();
+        assert_eq!(
+            rules[0].matchers(),
+            [ProblemMatcher::Builtin("rustc".to_owned())]
+        );
+        assert_eq!(
+            rules[1].matchers()[1],
+            ProblemMatcher::Pattern {
+                regex: r"^(?P<file>[^:]+):(?P<line>\d+) (?P<message>.+)$".to_owned(),
+                severity: Severity::Warning,
+            }
+        );
+        assert!(rule_as_yaml(&rules[1]).contains("matchers:\n  - eslint\n  - regex: "));
+
+        for (matchers, message) in [
+            ("matchers: gcc", "unknown matcher 'gcc'"),
+            (
+                "matchers:\n      - regex: '(?P<file>.+)'",
+                "no named `message` group",
+            ),
+            (
+                "matchers:\n      - regex: '(?P<message>.+'",
+                "regex '(?P<message>.+'",
+            ),
+            (
+                "matchers:\n      - regex: '(?P<message>.+)'\n        severity: fatal-ish",
+                "severity 'fatal-ish'",
+            ),
+            ("matchers:\n      - pattern: x", "unknown key 'pattern'"),
+        ] {
+            let err = from_yaml(&format!(
+                "jobs:\n  - name: a\n    run: echo a\n    {matchers}\n"
+            ))
+            .unwrap_err();
+            assert!(
+                format!("{err:?}").contains("Invalid 'matchers' for job 'a'")
+                    && format!("{err:?}").contains(message),
+                "{matchers}: {err:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn reports_accept_junit_globs_and_tap_on_stdout() {
+        let rules = from_yaml(
+            "jobs:\n  - name: test\n    run: cargo nextest run\n    reports:\n      junit: 'target/nextest/**/junit.xml'\n      tap: stdout\n",
+        )
+        .unwrap();
+        assert_eq!(
+            rules[0].reports(),
+            &TestReports {
+                junit: Some("target/nextest/**/junit.xml".to_owned()),
+                tap: true,
+            }
+        );
+        assert!(rule_as_yaml(&rules[0])
+            .contains("reports:\n  junit: target/nextest/**/junit.xml\n  tap: stdout"));
+
+        for (reports, message) in [
+            ("reports: junit.xml", "Invalid 'reports' for job 'a'"),
+            (
+                "reports:\n      tap: stderr",
+                "Invalid 'reports.tap' for job 'a'",
+            ),
+            (
+                "reports:\n      junit: '[x'",
+                "Invalid 'reports.junit' for job 'a'",
+            ),
+            (
+                "reports:\n      xunit: out.xml",
+                "Invalid property 'reports.xunit'",
+            ),
+        ] {
+            let err = from_yaml(&format!(
+                "jobs:\n  - name: a\n    run: echo a\n    {reports}\n"
+            ))
+            .unwrap_err();
+            assert!(format!("{err:?}").contains(message), "{reports}: {err:?}");
+        }
+    }
+
+    #[test]
+    fn run_steps_may_continue_on_error() {
+        let rules = from_yaml(
+            "jobs:\n  - name: ci\n    run:\n      - cargo build\n      - run: cargo clippy\n        continue_on_error: true\n      - cargo test\n",
+        )
+        .unwrap();
+        assert_eq!(
+            rules[0].commands(),
+            ["cargo build", "cargo clippy", "cargo test"]
+        );
+        assert!(!rules[0].continues_on_error(0));
+        assert!(rules[0].continues_on_error(1));
+        assert!(rule_as_yaml(&rules[0]).contains(
+            "run:\n  - cargo build\n  - run: cargo clippy\n    continue_on_error: true\n  - cargo test"
+        ));
+
+        for (step, expected) in [
+            (
+                "{ run: cargo test, continue_on_error: maybe }",
+                "'continue_on_error' must be a boolean",
+            ),
+            (
+                "{ continue_on_error: true }",
+                "expected a non-empty 'run' command",
+            ),
+            (
+                "{ run: cargo test, retry: 2 }",
+                "only 'run' and 'continue_on_error' are allowed",
+            ),
+        ] {
+            let err = from_yaml(&format!(
+                "jobs:\n  - name: a\n    run:\n      - cargo build\n      - {step}\n"
+            ))
+            .unwrap_err();
+            let err = format!("{err:?}");
+            assert!(err.contains("Invalid step 2 of 'run' in job 'a'"), "{err}");
+            assert!(err.contains(expected), "{err}");
+        }
+    }
+
+    #[test]
+    fn success_codes_and_allow_failure_are_job_properties() {
+        let rules = from_yaml(
+            "jobs:\n  - name: lint\n    run: ./lint\n    success_codes: [0, 1]\n  - name: audit\n    run: cargo audit\n    success_codes: 0\n    allow_failure: true\n",
+        )
+        .unwrap();
+        assert_eq!(rules[0].success_codes(), [0, 1]);
+        assert!(!rules[0].allow_failure());
+        assert!(rule_as_yaml(&rules[0]).ends_with("success_codes: [0, 1]"));
+        assert_eq!(rules[1].success_codes(), [0]);
+        assert!(rules[1].allow_failure());
+        assert!(rule_as_yaml(&rules[1]).ends_with("allow_failure: true"));
+
+        for (job, message) in [
+            ("success_codes: []", "Invalid 'success_codes' for job 'a'"),
+            (
+                "success_codes: [0, 256]",
+                "Invalid 'success_codes' for job 'a'",
+            ),
+            (
+                "success_codes: warnings",
+                "Invalid 'success_codes' for job 'a'",
+            ),
+            (
+                "allow_failure: sometimes",
+                "Invalid 'allow_failure' value for job 'a'",
+            ),
+        ] {
+            let err = from_yaml(&format!("jobs:\n  - name: a\n    run: echo a\n    {job}\n"))
+                .unwrap_err();
+            assert!(format!("{err:?}").contains(message), "{job}: {err:?}");
+        }
+    }
+
+    #[test]
+    fn when_selects_jobs_that_run_after_failures() {
+        let rules = from_yaml(
+            "jobs:\n  - name: test\n    run: cargo test\n  - name: logs\n    run: docker compose logs\n    when: on_failure\n  - name: down\n    run: docker compose down\n    when: always\n",
+        )
+        .unwrap();
+        assert_eq!(rules[0].when(), When::OnSuccess);
+        assert!(!rule_as_yaml(&rules[0]).contains("when:"));
+        assert_eq!(rules[1].when(), When::OnFailure);
+        assert_eq!(rules[2].when(), When::Always);
+        assert!(rule_as_yaml(&rules[2]).ends_with("when: always"));
+
+        let err =
+            from_yaml("jobs:\n  - name: a\n    run: echo a\n    when: sometimes\n").unwrap_err();
+        assert!(format!("{err:?}")
+            .contains("Invalid 'when' for job 'a': expected on_success, on_failure, always"));
+        let err = from_yaml(
+            "jobs:\n  - name: db\n    run: postgres\n    service: true\n    when: always\n",
+        )
+        .unwrap_err();
+        assert!(format!("{err:?}").contains("cannot declare 'when' when service is true"));
+    }
 }
 
 #[cfg(test)]
@@ -2254,6 +3640,54 @@ mod backend_tests {
         );
     }
 
+    #[test]
+    fn settle_parses_interval_and_bound() {
+        assert_eq!(settle_from_yaml("on:\n  change: '**/*'\n").unwrap(), None);
+        assert_eq!(
+            settle_from_yaml("on:\n  settle: 200ms\n").unwrap(),
+            Some(crate::watcher::Settle {
+                interval: Duration::from_millis(200),
+                max: crate::watcher::Settle::DEFAULT_MAX,
+            })
+        );
+        assert_eq!(
+            settle_from_yaml("on:\n  settle: 500ms\n  settle_max: 2m\n").unwrap(),
+            Some(crate::watcher::Settle {
+                interval: Duration::from_millis(500),
+                max: Duration::from_secs(120),
+            })
+        );
+        assert!(settle_from_yaml("on:\n  settle: soon\n")
+            .unwrap_err()
+            .contains("on.settle"));
+        assert!(settle_from_yaml("on:\n  settle: 5s\n  settle_max: 1s\n").is_err());
+    }
+
+    #[test]
+    fn event_source_accepts_stdin_shorthand_and_fifo() {
+        assert_eq!(
+            event_source_from_yaml("on:\n  change: '**/*'\n").unwrap(),
+            None
+        );
+        assert_eq!(
+            event_source_from_yaml("on:\n  watch_backend: stdin\n").unwrap(),
+            Some(crate::watcher::EventSource::Stdin)
+        );
+        assert_eq!(
+            watch_backend_from_yaml("on:\n  watch_backend: stdin\n").unwrap(),
+            None
+        );
+        assert_eq!(
+            event_source_from_yaml("on:\n  event_source: fifo:/tmp/fzz.events\n").unwrap(),
+            Some(crate::watcher::EventSource::Fifo("/tmp/fzz.events".into()))
+        );
+        assert!(event_source_from_yaml("on:\n  event_source: tcp:9000\n").is_err());
+        assert!(event_source_from_yaml(
+            "on:\n  watch_backend: stdin\n  event_source: fifo:/tmp/x\n"
+        )
+        .is_err());
+    }
+
     #[test]
     fn watch_backend_rejects_invalid_values() {
         assert!(watch_backend_from_yaml("on:\n  watch_backend: bogus\n").is_err());
@@ -2289,6 +3723,11 @@ pub fn watch_backend_from_yaml(
         Yaml::String(value) => value.clone(),
         _ => return Err("Property 'on.watch_backend' must be a string".to_owned()),
     };
+    // `stdin` replaces the filesystem backend with an event source; see
+    // `event_source_from_yaml`.
+    if backend == "stdin" {
+        return Ok(None);
+    }
     let poll_interval = match &on["poll_interval"] {
         Yaml::BadValue => None,
         Yaml::Integer(value) => Some(value.to_string()),
@@ -2302,6 +3741,83 @@ pub fn watch_backend_from_yaml(
     crate::watcher::WatchBackend::parse(Some(&backend), poll_interval).map(Some)
 }
 
+/// Parses the optional `on.settle` write-settle interval and its
+/// `on.settle_max` bound (default 30s). None when `settle` is absent.
+pub fn settle_from_yaml(content: &str) -> Result<Option<crate::watcher::Settle>, String> {
+    let documents = YamlLoader::load_from_str(content).map_err(|err| err.to_string())?;
+    let root = documents
+        .first()
+        .ok_or_else(|| "Configuration file is empty".to_owned())?;
+    let on = &root["on"];
+    if on == &Yaml::BadValue || on["settle"] == Yaml::BadValue {
+        return Ok(None);
+    }
+    let duration = |field: &str| -> Result<Option<Duration>, String> {
+        let raw = match &on[field] {
+            Yaml::BadValue => return Ok(None),
+            Yaml::Integer(value) => value.to_string(),
+            Yaml::String(value) => value.clone(),
+            _ => return Err(format!("Property 'on.{field}' must be a duration")),
+        };
+        parse_debounce(&raw).map_err(|err| err.replace("on.debounce", &format!("on.{field}")))
+    };
+    let interval = duration("settle")?.expect("settle is present");
+    let max = duration("settle_max")?.unwrap_or(crate::watcher::Settle::DEFAULT_MAX);
+    if max < interval {
+        return Err("Property 'on.settle_max' must not be shorter than 'on.settle'".to_owned());
+    }
+    Ok(Some(crate::watcher::Settle { interval, max }))
+}
+
+pub fn settle_from_file(filename: &str) -> Result<Option<crate::watcher::Settle>, String> {
+    let mut file = File::open(filename).map_err(|err| err.to_string())?;
+    let mut content = String::new();
+    file.read_to_string(&mut content)
+        .map_err(|err| err.to_string())?;
+    settle_from_yaml(&content)
+}
+
+/// Parses the optional external event source: `on.event_source: stdin |
+/// fifo:PATH`, or the `on.watch_backend: stdin` shorthand. None keeps the
+/// filesystem backend.
+pub fn event_source_from_yaml(
+    content: &str,
+) -> Result<Option<crate::watcher::EventSource>, String> {
+    let documents = YamlLoader::load_from_str(content).map_err(|err| err.to_string())?;
+    let root = documents
+        .first()
+        .ok_or_else(|| "Configuration file is empty".to_owned())?;
+    let on = &root["on"];
+    if on == &Yaml::BadValue {
+        return Ok(None);
+    }
+    let stdin_backend = on["watch_backend"].as_str() == Some("stdin");
+    let source = match &on["event_source"] {
+        Yaml::BadValue => None,
+        Yaml::String(value) => Some(crate::watcher::EventSource::parse(value)?),
+        _ => return Err("Property 'on.event_source' must be a string".to_owned()),
+    };
+    match source {
+        Some(crate::watcher::EventSource::Fifo(_)) if stdin_backend => Err(
+            "Properties 'on.watch_backend: stdin' and 'on.event_source' disagree; keep one"
+                .to_owned(),
+        ),
+        Some(source) => Ok(Some(source)),
+        None if stdin_backend => Ok(Some(crate::watcher::EventSource::Stdin)),
+        None => Ok(None),
+    }
+}
+
+pub fn event_source_from_file(
+    filename: &str,
+) -> Result<Option<crate::watcher::EventSource>, String> {
+    let mut file = File::open(filename).map_err(|err| err.to_string())?;
+    let mut content = String::new();
+    file.read_to_string(&mut content)
+        .map_err(|err| err.to_string())?;
+    event_source_from_yaml(&content)
+}
+
 pub fn watch_backend_from_file(
     filename: &str,
 ) -> Result<Option<crate::watcher::WatchBackend>, String> {
@@ -2331,6 +3847,13 @@ mod gitignore_config_tests {
     fn respect_gitignore_rejects_non_boolean() {
         assert!(respect_gitignore_from_yaml("on:\n  respect_gitignore: yes-please\n").is_err());
     }
+
+    #[test]
+    fn editor_noise_defaults_to_true_and_parses_boolean() {
+        assert!(editor_noise_from_yaml("on:\n  change: '**/*'\n").unwrap());
+        assert!(!editor_noise_from_yaml("on:\n  editor_noise: false\n").unwrap());
+        assert!(editor_noise_from_yaml("on:\n  editor_noise: off-please\n").is_err());
+    }
 }
 
 /// Parses the optional `on.respect_gitignore` boolean (default false).
@@ -2349,6 +3872,31 @@ pub fn respect_gitignore_from_yaml(content: &str) -> Result<bool, String> {
     }
 }
 
+/// Parses the optional `on.editor_noise` boolean (default true): whether
+/// well-known editor artifacts are filtered from batches.
+pub fn editor_noise_from_yaml(content: &str) -> Result<bool, String> {
+    let documents = YamlLoader::load_from_str(content).map_err(|err| err.to_string())?;
+    let root = documents
+        .first()
+        .ok_or_else(|| "Configuration file is empty".to_owned())?;
+    let on = &root["on"];
+    if on == &Yaml::BadValue || on["editor_noise"] == Yaml::BadValue {
+        return Ok(true);
+    }
+    match &on["editor_noise"] {
+        Yaml::Boolean(value) => Ok(*value),
+        _ => Err("Property 'on.editor_noise' must be a boolean".to_owned()),
+    }
+}
+
+pub fn editor_noise_from_file(filename: &str) -> Result<bool, String> {
+    let mut file = File::open(filename).map_err(|err| err.to_string())?;
+    let mut content = String::new();
+    file.read_to_string(&mut content)
+        .map_err(|err| err.to_string())?;
+    editor_noise_from_yaml(&content)
+}
+
 pub fn respect_gitignore_from_file(filename: &str) -> Result<bool, String> {
     let mut file = File::open(filename).map_err(|err| err.to_string())?;
     let mut content = String::new();
@@ -2560,6 +4108,7 @@ mod output_policy_tests {
             ("quiet", OutputPolicy::Quiet),
             ("capture", OutputPolicy::Capture),
             ("show-on-failure", OutputPolicy::ShowOnFailure),
+            ("grouped", OutputPolicy::Grouped),
         ] {
             let yaml = format!("execution:\n  output: {raw}\n");
             assert_eq!(output_policy_from_yaml(&yaml).unwrap(), expected, "{raw}");
@@ -2613,6 +4162,452 @@ mod service_tests {
         )
         .is_err());
     }
+
+    #[test]
+    fn stop_signal_and_grace_are_read_and_rendered() {
+        let rules = from_yaml(
+            "jobs:\n  - name: server\n    run: ./serve\n    stop_signal: SIGINT\n    stop_grace: 1500ms\n  - name: worker\n    run: ./work\n    stop_grace: 20\n",
+        )
+        .unwrap();
+        assert_eq!(
+            rules[0].stop(),
+            StopPolicy {
+                signal: Some(Signal::SIGINT),
+                grace: Some(Duration::from_millis(1500)),
+            }
+        );
+        assert!(rule_as_yaml(&rules[0]).ends_with("stop_signal: SIGINT\nstop_grace: 1500ms"));
+        assert_eq!(rules[1].stop().grace, Some(Duration::from_secs(20)));
+        assert_eq!(rules[1].stop().signal, None);
+        assert!(rule_as_yaml(&rules[1]).ends_with("stop_grace: 20s"));
+
+        for (job, message) in [
+            ("stop_signal: SIGKILL", "Invalid 'stop_signal' for job 'a'"),
+            ("stop_signal: 2", "Invalid 'stop_signal' for job 'a'"),
+            ("stop_grace: soon", "Invalid 'stop_grace' for job 'a'"),
+            ("stop_grace: 0s", "Invalid 'stop_grace' for job 'a'"),
+        ] {
+            let err = from_yaml(&format!("jobs:\n  - name: a\n    run: echo a\n    {job}\n"))
+                .unwrap_err();
+            assert!(format!("{err:?}").contains(message), "{job}: {err:?}");
+        }
+    }
+
+    #[test]
+    fn ready_probes_are_read_from_services() {
+        let rules = from_yaml(
+            "jobs:\n  - name: api\n    run: ./api\n    service: true\n    ready:\n      tcp: 3000\n  - name: web\n    run: ./web\n    service: true\n    ready:\n      log: 'listening on :\\d+'\n      timeout: 2m\n  - name: db\n    run: ./db\n    service: true\n    ready: { tcp: '127.0.0.1:5432' }\n",
+        )
+        .unwrap();
+        assert_eq!(
+            rules[0].ready(),
+            Some(&Readiness {
+                probe: ReadyProbe::Tcp {
+                    host: "localhost".to_owned(),
+                    port: 3000,
+                },
+                timeout: DEFAULT_READY_TIMEOUT,
+            })
+        );
+        assert!(rule_as_yaml(&rules[0]).ends_with("ready:\n  tcp: 3000"));
+        assert_eq!(
+            rules[1].ready(),
+            Some(&Readiness {
+                probe: ReadyProbe::Log("listening on :\\d+".to_owned()),
+                timeout: Duration::from_secs(120),
+            })
+        );
+        assert!(rule_as_yaml(&rules[1]).ends_with("  timeout: 120s"));
+        assert_eq!(
+            rules[2].ready().map(|ready| ready.probe.label()),
+            Some("tcp 127.0.0.1:5432".to_owned())
+        );
+
+        for (job, message) in [
+            (
+                "ready:\n      tcp: 3000",
+                "Job 'a' declares 'ready' but is not a service",
+            ),
+            (
+                "service: true\n    ready:\n      tcp: 3000\n      file: up",
+                "Invalid 'ready' for job 'a': expected exactly one",
+            ),
+            (
+                "service: true\n    ready:\n      log: '(unclosed'",
+                "Invalid 'ready.log' for job 'a'",
+            ),
+            (
+                "service: true\n    ready:\n      tcp: 70000",
+                "Invalid 'ready.tcp' for job 'a'",
+            ),
+            (
+                "service: true\n    ready:\n      http: /health",
+                "Invalid property 'ready.http' in job 'a'",
+            ),
+            (
+                "service: true\n    ready:\n      timeout: 5s",
+                "Invalid 'ready' for job 'a': expected one of",
+            ),
+        ] {
+            let err = from_yaml(&format!("jobs:\n  - name: a\n    run: echo a\n    {job}\n"))
+                .unwrap_err();
+            assert!(format!("{err:?}").contains(message), "{job}: {err:?}");
+        }
+    }
+
+    #[test]
+    fn restart_and_health_are_read_from_services() {
+        let rules = from_yaml(
+            "jobs:\n  - name: api\n    run: ./api\n    service: true\n    restart:\n      max: 10\n      backoff: exponential\n      reset_after: 1m\n    health:\n      command: curl -sf localhost:3000/health\n      interval: 5s\n  - name: db\n    run: ./db\n    service: true\n    health: { tcp: 5432 }\n",
+        )
+        .unwrap();
+        assert_eq!(
+            rules[0].restart(),
+            RestartPolicy {
+                max: 10,
+                backoff: Backoff::Exponential,
+                delay: Duration::from_millis(500),
+                reset_after: Some(Duration::from_secs(60)),
+            }
+        );
+        assert_eq!(
+            rules[0].health(),
+            Some(&HealthCheck {
+                probe: ReadyProbe::Command("curl -sf localhost:3000/health".to_owned()),
+                interval: Duration::from_secs(5),
+                failures: DEFAULT_HEALTH_FAILURES,
+            })
+        );
+        assert!(rule_as_yaml(&rules[0]).ends_with(
+            "restart:\n  max: 10\n  backoff: exponential\n  reset_after: 60s\nhealth:\n  command: curl -sf localhost:3000/health\n  interval: 5s"
+        ));
+        assert_eq!(rules[1].restart(), RestartPolicy::default());
+        assert!(rule_as_yaml(&rules[1]).ends_with("health:\n  tcp: 5432"));
+
+        for (job, message) in [
+            (
+                "restart:\n      max: 5",
+                "Job 'a' declares 'restart' but is not a service",
+            ),
+            (
+                "service: true\n    restart:\n      backoff: linear",
+                "Invalid 'restart.backoff' for job 'a'",
+            ),
+            (
+                "service: true\n    restart:\n      max: -1",
+                "Invalid 'restart.max' for job 'a'",
+            ),
+            (
+                "service: true\n    restart:\n      jitter: 1s",
+                "Invalid property 'restart.jitter' in job 'a'",
+            ),
+            (
+                "service: true\n    health:\n      log: up",
+                "Invalid property 'health.log' in job 'a'",
+            ),
+            (
+                "service: true\n    health:\n      tcp: 3000\n      failures: 0",
+                "Invalid 'health.failures' for job 'a'",
+            ),
+            (
+                "service: true\n    health:\n      interval: 5s",
+                "Invalid 'health' for job 'a': expected one of",
+            ),
+        ] {
+            let err = from_yaml(&format!("jobs:\n  - name: a\n    run: echo a\n    {job}\n"))
+                .unwrap_err();
+            assert!(format!("{err:?}").contains(message), "{job}: {err:?}");
+        }
+    }
+
+    #[test]
+    fn on_change_actions_are_read_from_services() {
+        let rules = from_yaml(
+            "jobs:\n  - name: nginx\n    run: nginx\n    service: true\n    on_change: signal:HUP\n  - name: gunicorn\n    run: gunicorn app\n    service: true\n    on_change: 'command: kill -HUP $(cat app.pid)'\n  - name: db\n    run: ./db\n    service: true\n    on_change: ignore\n  - name: api\n    run: ./api\n    service: true\n    on_change: signal:SIGUSR2\n",
+        )
+        .unwrap();
+        assert_eq!(rules[0].on_change(), &OnChange::Signal(Signal::SIGHUP));
+        assert!(rule_as_yaml(&rules[0]).ends_with("on_change: signal:HUP"));
+        assert_eq!(
+            rules[1].on_change(),
+            &OnChange::Command("kill -HUP $(cat app.pid)".to_owned())
+        );
+        assert_eq!(rules[2].on_change(), &OnChange::Ignore);
+        assert_eq!(rules[3].on_change(), &OnChange::Signal(Signal::SIGUSR2));
+
+        for (job, message) in [
+            (
+                "on_change: ignore",
+                "Job 'a' declares 'on_change' but is not a service",
+            ),
+            (
+                "service: true\n    on_change: reload",
+                "Invalid 'on_change' for job 'a'",
+            ),
+            (
+                "service: true\n    on_change: signal:KILL",
+                "Invalid 'on_change' for job 'a'",
+            ),
+            (
+                "service: true\n    on_change: 'command:'",
+                "Invalid 'on_change' for job 'a'",
+            ),
+        ] {
+            let err = from_yaml(&format!("jobs:\n  - name: a\n    run: echo a\n    {job}\n"))
+                .unwrap_err();
+            assert!(format!("{err:?}").contains(message), "{job}: {err:?}");
+        }
+    }
+
+    #[test]
+    fn stop_commands_are_read_from_services() {
+        let rules = from_yaml(
+            "jobs:\n  - name: stack\n    run: docker compose up\n    service: true\n    stop: docker compose down\n  - name: db\n    run: ./db\n    service: true\n    stop:\n      - ./db flush\n      - ./db close\n",
+        )
+        .unwrap();
+        assert_eq!(rules[0].teardown(), ["docker compose down"]);
+        assert!(rule_as_yaml(&rules[0]).contains("stop: docker compose down"));
+        assert_eq!(rules[1].teardown(), ["./db flush", "./db close"]);
+        assert!(rules[0].stop().is_empty());
+
+        let err =
+            from_yaml("jobs:\n  - name: a\n    run: echo a\n    stop: echo bye\n").unwrap_err();
+        assert!(
+            format!("{err:?}").contains("Job 'a' declares 'stop' but is not a service"),
+            "{err:?}"
+        );
+    }
+
+    #[test]
+    fn services_cannot_declare_locks() {
+        let err = from_yaml(
+            "jobs:\n  - name: db\n    run: postgres\n    service: true\n    locks: db\n  - name: migrate\n    run: migrate\n    locks: db\n",
+        )
+        .unwrap_err();
+        assert!(format!("{err:?}").contains("Job 'db' cannot declare 'locks' when service is true"));
+    }
+
+    #[test]
+    fn services_cannot_declare_a_global_lock() {
+        let err = from_yaml(
+            "jobs:\n  - name: db\n    run: postgres\n    service: true\n    global_lock: cargo-target\n",
+        )
+        .unwrap_err();
+        assert!(format!("{err:?}")
+            .contains("Job 'db' cannot declare 'global_lock' when service is true"));
+    }
+}
+
+#[cfg(test)]
+mod git_trigger_tests {
+    use super::*;
+
+    #[test]
+    fn git_triggers_merge_on_before_job_and_dedupe() {
+        let rules = from_yaml(
+            "on:\n  change: '**/*'\n  git: head_change\njobs:\n  - name: deps\n    run: npm ci\n    git: [commit, head_change]\n",
+        )
+        .unwrap();
+        assert_eq!(
+            rules[0].git_triggers(),
+            &[GitTrigger::HeadChange, GitTrigger::Commit]
+        );
+    }
+
+    #[test]
+    fn git_only_job_is_valid_without_change() {
+        let rules =
+            from_yaml("jobs:\n  - name: deps\n    run: npm ci\n    git: index_change\n").unwrap();
+        assert!(rules[0].watch_patterns().is_empty());
+        assert_eq!(rules[0].git_triggers(), &[GitTrigger::IndexChange]);
+    }
+
+    #[test]
+    fn git_rejects_unknown_events() {
+        let err =
+            from_yaml("jobs:\n  - name: deps\n    run: npm ci\n    git: checkout\n").unwrap_err();
+        assert!(format!("{err:?}").contains("head_change, commit, index_change"));
+        assert!(from_yaml("on:\n  git: 3\njobs:\n  - name: a\n    run: echo a\n").is_err());
+    }
+}
+
+#[cfg(test)]
+mod execution_tests {
+    use super::*;
+
+    #[test]
+    fn execution_timestamps_apply_to_every_job() {
+        let rules = from_yaml(
+            "execution:\n  timestamps: elapsed\njobs:\n  - name: a\n    run: echo a\n  - name: b\n    run: echo b\n",
+        )
+        .unwrap();
+        assert!(rules
+            .iter()
+            .all(|rule| rule.timestamps() == Timestamps::Elapsed));
+        let rules = from_yaml("jobs:\n  - name: a\n    run: echo a\n").unwrap();
+        assert_eq!(rules[0].timestamps(), Timestamps::None);
+
+        let err =
+            from_yaml("execution:\n  timestamps: local\njobs:\n  - name: a\n    run: echo a\n")
+                .unwrap_err();
+        assert!(format!("{err:?}").contains("expected wall, elapsed, or none"));
+        assert!(
+            from_yaml("execution:\n  timestamps: 1\njobs:\n  - name: a\n    run: echo a\n")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn locks_take_their_capacity_from_execution_resources() {
+        let rules = from_yaml(
+            "execution:\n  resources:\n    db: 2\njobs:\n  - name: migrate\n    run: echo m\n    locks: db\n  - name: build\n    run: echo b\n    locks: [target, db, target]\n",
+        )
+        .unwrap();
+        assert_eq!(
+            rules[0].locks(),
+            [ResourceLock {
+                name: "db".to_owned(),
+                capacity: 2
+            }]
+        );
+        // Undeclared locks are exclusive; duplicates collapse.
+        assert_eq!(
+            rules[1].locks(),
+            [
+                ResourceLock {
+                    name: "target".to_owned(),
+                    capacity: 1
+                },
+                ResourceLock {
+                    name: "db".to_owned(),
+                    capacity: 2
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn global_lock_is_a_file_safe_name() {
+        let rules =
+            from_yaml("jobs:\n  - name: a\n    run: echo a\n    global_lock: cargo-target\n")
+                .unwrap();
+        assert_eq!(rules[0].global_lock(), Some("cargo-target"));
+        let err =
+            from_yaml("jobs:\n  - name: a\n    run: echo a\n    global_lock: ../x\n").unwrap_err();
+        assert!(format!("{err:?}").contains("Invalid 'global_lock' for job 'a'"));
+    }
+
+    #[test]
+    fn limits_parse_sizes_durations_and_priority() {
+        let rules = from_yaml(
+            "jobs:\n  - name: a\n    run: cargo test\n    limits:\n      memory: 4G\n      cpu: 90\n      open_files: 1024\n      core: 0\n    nice: 10\n    ionice: best-effort:6\n",
+        )
+        .unwrap();
+        assert_eq!(
+            rules[0].limits(),
+            ProcessLimits {
+                memory: Some(4 << 30),
+                cpu_seconds: Some(90),
+                open_files: Some(1024),
+                core: Some(0),
+                nice: Some(10),
+                ionice: Some(IoPriority::BestEffort(6)),
+            }
+        );
+        let rendered = rule_as_yaml(&rules[0]);
+        assert!(
+            rendered.contains("limits:\n  memory: 4G\n  cpu: 90s"),
+            "{rendered}"
+        );
+        assert!(rendered.contains("ionice: best-effort:6"), "{rendered}");
+    }
+
+    #[test]
+    fn rejects_invalid_limits() {
+        for (job, message) in [
+            (
+                "limits:\n      memory: lots",
+                "Invalid 'limits.memory' for job 'a'",
+            ),
+            ("limits:\n      cpu: 0", "Invalid 'limits.cpu' for job 'a'"),
+            (
+                "limits:\n      stack: 8M",
+                "Invalid property 'limits.stack' in job 'a'",
+            ),
+            ("nice: 40", "Invalid 'nice' for job 'a'"),
+            ("ionice: realtime", "Invalid 'ionice' for job 'a'"),
+        ] {
+            let err = from_yaml(&format!("jobs:\n  - name: a\n    run: echo a\n    {job}\n"))
+                .unwrap_err();
+            assert!(format!("{err:?}").contains(message), "{job}: {err:?}");
+        }
+        assert_eq!(parse_size("512m"), Some(512 << 20));
+        assert_eq!(format_size(1536 << 20), "1536M");
+    }
+
+    #[test]
+    fn rejects_invalid_resources_and_locks() {
+        let err =
+            from_yaml("execution:\n  resources:\n    db: 0\njobs:\n  - name: a\n    run: echo a\n")
+                .unwrap_err();
+        assert!(format!("{err:?}").contains("'execution.resources.db' must be a positive integer"));
+        let err =
+            from_yaml("jobs:\n  - name: a\n    run: echo a\n    locks: {db: 1}\n").unwrap_err();
+        assert!(format!("{err:?}").contains("Invalid 'locks' for job 'a'"));
+    }
+}
+
+#[cfg(test)]
+mod terminal_tests {
+    use super::*;
+
+    #[test]
+    fn tty_is_a_job_boolean() {
+        let rules = from_yaml("jobs:\n  - name: a\n    run: cargo test\n    tty: true\n").unwrap();
+        assert!(rules[0].tty());
+        assert!(rule_as_yaml(&rules[0]).ends_with("tty: true"));
+        let err =
+            from_yaml("jobs:\n  - name: a\n    run: echo a\n    tty: yes please\n").unwrap_err();
+        assert!(format!("{err:?}").contains("Invalid 'tty' value for job 'a'"));
+    }
+
+    #[test]
+    fn interactive_jobs_must_run_alone_in_the_foreground() {
+        let rules =
+            from_yaml("jobs:\n  - name: repl\n    run: node\n    interactive: true\n").unwrap();
+        assert!(rules[0].interactive());
+        for (extra, conflict) in [
+            ("parallel: dev", "it runs in parallel group 'dev'"),
+            ("tty: true", "tty is true"),
+        ] {
+            let err = from_yaml(&format!(
+                "jobs:\n  - name: repl\n    run: node\n    interactive: true\n    {extra}\n"
+            ))
+            .unwrap_err();
+            assert!(
+                format!("{err:?}").contains(&format!(
+                    "Job 'repl' cannot be interactive because {conflict}"
+                )),
+                "{err:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn only_one_interactive_service_is_allowed() {
+        let one =
+            "jobs:\n  - name: repl\n    run: node\n    interactive: true\n    service: true\n";
+        assert!(from_yaml(one).is_ok());
+        let err = from_yaml(&format!(
+            "{one}  - name: shell\n    run: sh\n    interactive: true\n    service: true\n"
+        ))
+        .unwrap_err();
+        assert!(
+            format!("{err:?}")
+                .contains("Only one interactive service is allowed, found: repl, shell"),
+            "{err:?}"
+        );
+    }
 }
 
 #[cfg(test)]
@@ -46,6 +46,12 @@ pub struct RuntimeConfig {
     pub debounce: Duration,
     pub backend: WatchBackend,
     pub respect_gitignore: bool,
+    /// `on.editor_noise` (default true); see [`RuntimeConfig::with_editor_noise`].
+    pub editor_noise: bool,
+    /// `on.settle` / `on.settle_max`; see [`RuntimeConfig::with_settle`].
+    pub settle: Option<crate::watcher::Settle>,
+    /// `on.event_source`; see [`RuntimeConfig::with_event_source`].
+    pub event_source: Option<crate::watcher::EventSource>,
     pub recovery_policy: crate::config::RecoveryPolicy,
     pub hooks: GenerationHooks,
     pub session_hooks: SessionHooks,
@@ -79,6 +85,9 @@ impl RuntimeConfig {
             debounce,
             backend,
             respect_gitignore,
+            editor_noise: true,
+            settle: None,
+            event_source: None,
             recovery_policy,
             hooks,
             session_hooks,
@@ -86,6 +95,26 @@ impl RuntimeConfig {
         }
     }
 
+    /// Records the editor artifact filter setting; captured configs default
+    /// to enabled.
+    pub fn with_editor_noise(mut self, enabled: bool) -> Self {
+        self.editor_noise = enabled;
+        self
+    }
+
+    /// Records the external event source; captured configs default to the
+    /// filesystem backend.
+    pub fn with_event_source(mut self, source: Option<crate::watcher::EventSource>) -> Self {
+        self.event_source = source;
+        self
+    }
+
+    /// Records the write-settle policy; captured configs default to none.
+    pub fn with_settle(mut self, settle: Option<crate::watcher::Settle>) -> Self {
+        self.settle = settle;
+        self
+    }
+
     /// The execution plan for these frozen rules (unfiltered topology), so a
     /// generation's plan derives from the same revision as its policy.
     pub fn plan(&self) -> RunPlan {
@@ -118,6 +147,19 @@ pub fn semantic_hash(config: &RuntimeConfig) -> String {
     canonical.u64(config.debounce.as_millis() as u64);
     canonical.string(&backend_tag(config.backend));
     canonical.bool(config.respect_gitignore);
+    // Only the non-default value is encoded, so existing revisions keep
+    // their hashes.
+    if !config.editor_noise {
+        canonical.string("editor_noise:off");
+    }
+    if let Some(source) = &config.event_source {
+        canonical.string(&format!("event_source:{}", source.label()));
+    }
+    if let Some(settle) = config.settle {
+        canonical.string("settle");
+        canonical.u64(settle.interval.as_millis() as u64);
+        canonical.u64(settle.max.as_millis() as u64);
+    }
     canonical.string(match config.recovery_policy {
         crate::config::RecoveryPolicy::Prompt => "prompt",
         crate::config::RecoveryPolicy::Skip => "skip",
@@ -150,6 +192,10 @@ fn encode_rule(canonical: &mut CanonicalEncoder, rule: &Rules) {
     canonical.optional_string(rule.parallel().map(str::to_owned));
     canonical.bool(rule.service());
     canonical.string(&output_policy_tag(&rule.output()));
+    if rule.timestamps() != crate::rules::Timestamps::None {
+        canonical.string("timestamps");
+        canonical.string(rule.timestamps().label());
+    }
     canonical.optional_string(rule.cwd().map(str::to_owned));
 
     let mut change = rule.watch_patterns();
@@ -191,6 +237,159 @@ fn encode_rule(canonical: &mut CanonicalEncoder, rule: &Rules) {
     for key in env_keys {
         canonical.string(key);
     }
+
+    // Git triggers are appended only when configured, so revisions of
+    // configs without `git` stay byte-identical to earlier releases.
+    let mut git: Vec<&str> = rule.git_triggers().iter().map(|t| t.label()).collect();
+    if !git.is_empty() {
+        git.sort_unstable();
+        canonical.string("git");
+        canonical.u64(git.len() as u64);
+        for trigger in git {
+            canonical.string(trigger);
+        }
+    }
+
+    let mut locks = rule.locks().to_vec();
+    if !locks.is_empty() {
+        locks.sort();
+        canonical.string("locks");
+        canonical.u64(locks.len() as u64);
+        for lock in locks {
+            canonical.string(&lock.name);
+            canonical.u64(lock.capacity as u64);
+        }
+    }
+
+    if let Some(lock) = rule.global_lock() {
+        canonical.string("global_lock");
+        canonical.string(lock);
+    }
+
+    let limits = rule.limits();
+    if !limits.is_empty() {
+        canonical.string("limits");
+        for value in [
+            limits.memory,
+            limits.cpu_seconds,
+            limits.open_files,
+            limits.core,
+        ] {
+            canonical.string(&value.map(|value| value.to_string()).unwrap_or_default());
+        }
+        canonical.string(&limits.nice.map(|nice| nice.to_string()).unwrap_or_default());
+        canonical.string(&limits.ionice.map(|class| class.label()).unwrap_or_default());
+    }
+
+    if rule.tty() {
+        canonical.string("tty");
+    }
+    if rule.interactive() {
+        canonical.string("interactive");
+    }
+
+    if let Some(ready) = rule.ready() {
+        canonical.string("ready");
+        canonical.string(&ready.probe.label());
+        canonical.u64(ready.timeout.as_millis() as u64);
+    }
+
+    let restart = rule.restart();
+    if restart != crate::rules::RestartPolicy::default() {
+        canonical.string("restart");
+        canonical.u64(restart.max as u64);
+        canonical.string(match restart.backoff {
+            crate::rules::Backoff::Fixed => "fixed",
+            crate::rules::Backoff::Exponential => "exponential",
+        });
+        canonical.u64(restart.delay.as_millis() as u64);
+        canonical.u64(
+            restart
+                .reset_after
+                .map(|reset_after| reset_after.as_millis() as u64)
+                .unwrap_or_default(),
+        );
+    }
+    if rule.on_change() != &crate::rules::OnChange::Restart {
+        canonical.string("on_change");
+        canonical.string(&rule.on_change().label());
+    }
+    if !rule.teardown().is_empty() {
+        canonical.string("teardown");
+        canonical.u64(rule.teardown().len() as u64);
+        for command in rule.teardown() {
+            canonical.string(command);
+        }
+    }
+    if !rule.matchers().is_empty() {
+        canonical.string("matchers");
+        canonical.u64(rule.matchers().len() as u64);
+        for matcher in rule.matchers() {
+            match matcher {
+                crate::rules::ProblemMatcher::Builtin(name) => {
+                    canonical.string("builtin");
+                    canonical.string(name);
+                }
+                crate::rules::ProblemMatcher::Pattern { regex, severity } => {
+                    canonical.string("regex");
+                    canonical.string(regex);
+                    canonical.string(severity.label());
+                }
+            }
+        }
+    }
+    let reports = rule.reports();
+    if !reports.is_empty() {
+        canonical.string("reports");
+        canonical.string(reports.junit.as_deref().unwrap_or_default());
+        canonical.u64(reports.tap as u64);
+    }
+    if rule.success_codes() != [0] {
+        canonical.string("success_codes");
+        canonical.u64(rule.success_codes().len() as u64);
+        for code in rule.success_codes() {
+            canonical.u64(*code as u64);
+        }
+    }
+    if rule.allow_failure() {
+        canonical.string("allow_failure");
+    }
+    if rule.when() != crate::rules::When::OnSuccess {
+        canonical.string("when");
+        canonical.string(rule.when().label());
+    }
+    let tolerated: Vec<usize> = (0..rule.commands().len())
+        .filter(|index| rule.continues_on_error(*index))
+        .collect();
+    if !tolerated.is_empty() {
+        canonical.string("continue_on_error");
+        canonical.u64(tolerated.len() as u64);
+        for index in tolerated {
+            canonical.u64(index as u64);
+        }
+    }
+    if let Some(health) = rule.health() {
+        canonical.string("health");
+        canonical.string(&health.probe.label());
+        canonical.u64(health.interval.as_millis() as u64);
+        canonical.u64(health.failures as u64);
+    }
+
+    let stop = rule.stop();
+    if !stop.is_empty() {
+        canonical.string("stop");
+        canonical.string(
+            stop.signal
+                .map(|signal| signal.as_str())
+                .unwrap_or_default(),
+        );
+        canonical.string(
+            &stop
+                .grace
+                .map(|grace| grace.as_millis().to_string())
+                .unwrap_or_default(),
+        );
+    }
 }
 
 /// Stable backend tag for hashing.
@@ -241,6 +440,7 @@ fn output_policy_tag(policy: &crate::rules::OutputPolicy) -> String {
         crate::rules::OutputPolicy::Quiet => "quiet",
         crate::rules::OutputPolicy::Capture => "capture",
         crate::rules::OutputPolicy::ShowOnFailure => "show_on_failure",
+        crate::rules::OutputPolicy::Grouped => "grouped",
     }
     .to_owned()
 }
@@ -510,6 +710,15 @@ mod tests {
         };
         assert_ne!(semantic_hash(&base), semantic_hash(&with_gitignore));
 
+        let without_editor_noise = base.clone().with_editor_noise(false);
+        assert_ne!(semantic_hash(&base), semantic_hash(&without_editor_noise));
+
+        let with_settle = base.clone().with_settle(Some(crate::watcher::Settle {
+            interval: Duration::from_millis(500),
+            max: crate::watcher::Settle::DEFAULT_MAX,
+        }));
+        assert_ne!(semantic_hash(&base), semantic_hash(&with_settle));
+
         let with_close_hook = RuntimeConfig {
             session_hooks: SessionHooks {
                 close: Some("echo closed".to_owned()),
@@ -712,6 +712,7 @@ fn process_request(request: serde_json::Value, api: &ControlApi) -> Option<serde
         "emit" => emit_requested_path(&request, emit_path),
         "cancel" => cancel_requested_generation(&request, cancel_generation, instance),
         "output" => output_retrieval(&request, outputs, instance),
+        "diagnostics" => diagnostics_result(&request, state, outputs, instance),
         // Honest negotiated profile (contract §8): methods list only what this
         // server implements; features stay false until the additive contract
         // (subscribe, cancel, output, correlated snapshots, estimates) lands.
@@ -779,6 +780,7 @@ fn capabilities_result(
         "emit",
         "await",
         "output",
+        "diagnostics",
         "cancel",
         "capabilities",
     ];
@@ -792,6 +794,7 @@ fn capabilities_result(
         "outputRetentionBytes": OUTPUT_RETENTION_BYTES as u64,
         "maxResponseBytes": MAX_RESPONSE_BYTES,
         "maxEvidenceLines": DEFAULT_FAILURE_EVIDENCE_LINES,
+        "maxDiagnostics": crate::matchers::MAX_DIAGNOSTICS,
         // Contract §4: paging and envelope facts so advanced clients negotiate
         // before requesting, instead of discovering a > transport response.
         "outputSchemaVersion": 2,
@@ -821,7 +824,10 @@ fn capabilities_result(
             "predecessor",
             "supersededBy",
             "failureEvidence",
-            "estimate"
+            "estimate",
+            "diagnostics",
+            "tests",
+            "steps"
         ],
         "outputFormats": ["toon", "json"],
         "limits": limits,
@@ -961,26 +967,7 @@ fn output_retrieval(
         ));
     };
 
-    // Instance identity (contract §3 `-32012`): a request carrying a stale
-    // token was formed against another watcher process and must never read
-    // the same-number generation from this one. Missing token (legacy) keeps
-    // working but never claims exact freshness.
-    if let Some(token) = params
-        .and_then(|params| params.get("instanceToken"))
-        .and_then(serde_json::Value::as_str)
-    {
-        if token != instance.token {
-            return Err((
-                -32012,
-                "instance_mismatch",
-                Some(serde_json::json!({
-                    "instance": token,
-                    "activeInstance": instance.token,
-                    "action": "restart-or-reobserve",
-                })),
-            ));
-        }
-    }
+    check_instance(params, instance)?;
 
     let task = params
         .and_then(|params| params.get("task"))
@@ -1089,6 +1076,74 @@ fn output_retrieval(
     }
 }
 
+/// Instance identity (contract §3 `-32012`): a request carrying a stale
+/// token was formed against another watcher process and must never read
+/// the same-number generation from this one. Missing token (legacy) keeps
+/// working but never claims exact freshness.
+fn check_instance(
+    params: Option<&serde_json::Map<String, serde_json::Value>>,
+    instance: &WatcherInstance,
+) -> Result<(), (i64, &'static str, Option<serde_json::Value>)> {
+    match params
+        .and_then(|params| params.get("instanceToken"))
+        .and_then(serde_json::Value::as_str)
+    {
+        Some(token) if token != instance.token => Err((
+            -32012,
+            "instance_mismatch",
+            Some(serde_json::json!({
+                "instance": token,
+                "activeInstance": instance.token,
+                "action": "restart-or-reobserve",
+            })),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// `diagnostics` result (`jobs[].matchers`): the problem-matcher records of
+/// one retained generation, the latest when `params.generation` is absent,
+/// optionally for one exact task. Errors share the `output` codes.
+fn diagnostics_result(
+    request: &serde_json::Value,
+    state: &Arc<Mutex<WatcherState>>,
+    outputs: Option<&OutputRegistry>,
+    instance: &WatcherInstance,
+) -> Result<serde_json::Value, (i64, &'static str, Option<serde_json::Value>)> {
+    let Some(outputs) = outputs else {
+        return Err((
+            -32014,
+            "output_unavailable",
+            Some(serde_json::json!({ "feature": "outputRetrieval" })),
+        ));
+    };
+    let params = request.get("params").and_then(serde_json::Value::as_object);
+    check_instance(params, instance)?;
+    let generation = match params.and_then(|params| params.get("generation")) {
+        None | Some(serde_json::Value::Null) => state.lock().unwrap().generation(),
+        Some(value) => value.as_u64().ok_or((
+            -32602,
+            "Invalid params",
+            Some(serde_json::json!(
+                "diagnostics params.generation must be a number"
+            )),
+        ))?,
+    };
+    let task = params
+        .and_then(|params| params.get("task"))
+        .and_then(serde_json::Value::as_str);
+    let found = outputs
+        .diagnostics(generation, task)
+        .map_err(|error| typed_output_error(error, generation))?;
+    serde_json::to_value(found).map_err(|_| {
+        (
+            -32000,
+            "Server error",
+            Some(serde_json::json!("diagnostics serialization failed")),
+        )
+    })
+}
+
 fn typed_output_error(
     error: crate::output::RetrievalError,
     generation: u64,
@@ -1700,6 +1755,71 @@ mod tests {
         assert_eq!(data["action"], "reobserve-or-copy-exact");
     }
 
+    #[test]
+    fn diagnostics_method_returns_matched_records_of_a_generation() {
+        let outputs = output_registry_with(&[(
+            7,
+            "build",
+            &[
+                "error[E0425]: cannot find value `x`\n",
+                "  --> src/lib.rs:3:5\n",
+            ],
+        )]);
+        let matchers = [
+            crate::matchers::Matcher::new(&crate::rules::ProblemMatcher::Builtin(
+                "rustc".to_owned(),
+            ))
+            .expect("built-in"),
+        ];
+        let capture = crate::cmd::CaptureHandle::new();
+        capture.append(
+            b"error[E0425]: cannot find value `x`\n  --> src/lib.rs:3:5\n",
+            false,
+        );
+        let (found, truncated) = crate::matchers::extract(&matchers, "build", &capture.finish());
+        outputs.record_diagnostics(7, "build", found, truncated);
+        let state = Arc::new(Mutex::new(WatcherState::default()));
+
+        let request = serde_json::json!({ "params": { "generation": 7, "task": "build" } });
+        let result = diagnostics_result(&request, &state, Some(&outputs), &instance("fz-7f3a"))
+            .expect("diagnostics");
+        assert_eq!(result["generation"], 7);
+        assert_eq!(result["truncated"], false);
+        assert_eq!(
+            result["diagnostics"][0],
+            serde_json::json!({
+                "task": "build",
+                "matcher": "rustc",
+                "file": "src/lib.rs",
+                "line": 3,
+                "column": 5,
+                "severity": "error",
+                "message": "cannot find value `x`",
+                "code": "E0425",
+            })
+        );
+
+        let request = serde_json::json!({ "params": { "generation": 7, "task": "nope" } });
+        let (code, message, data) =
+            diagnostics_result(&request, &state, Some(&outputs), &instance("fz-7f3a"))
+                .expect_err("unknown task");
+        assert_eq!((code, message), (-32011, "task_not_found"));
+        assert_eq!(
+            data.expect("data")["candidates"],
+            serde_json::json!(["build"])
+        );
+
+        // Without a generation the latest watcher generation is read.
+        let (code, _, _) = diagnostics_result(
+            &serde_json::json!({}),
+            &state,
+            Some(&outputs),
+            &instance("fz-7f3a"),
+        )
+        .expect_err("generation 0 retained nothing");
+        assert_eq!(code, -32010);
+    }
+
     #[test]
     fn output_tail_and_full_together_is_typed_invalid_options() {
         let outputs = output_registry_with(&[(7, "t", &["x\n"])]);
@@ -1753,6 +1873,8 @@ mod tests {
                     truncated: false,
                 }),
                 stderr: None,
+                tests: None,
+                steps: vec![],
             }],
             next_cursor: Some("cursor".to_owned()),
             returned_bytes: Some(8),
@@ -108,6 +108,32 @@ pub struct TerminalTaskSnapshot {
     pub name: String,
     pub state: String,
     pub duration_ms: Option<u64>,
+    /// Lock wait reason for `waiting` tasks (additive; absent on older
+    /// servers and for tasks that never waited).
+    pub waiting: Option<String>,
+    /// Failure cause beyond the exit status, e.g. an exceeded resource
+    /// limit (additive; absent otherwise).
+    pub failure_reason: Option<String>,
+    /// Restarts of a running service (additive; absent otherwise).
+    pub restarts: Option<u32>,
+    /// Last health check result of a service (additive; absent otherwise).
+    pub health: Option<String>,
+    /// Per-command results of a multi-command job (additive; empty
+    /// otherwise).
+    pub steps: Vec<TerminalStepSnapshot>,
+    /// Generation whose change cancelled this job under `--on-busy
+    /// restart-affected` (additive; absent otherwise).
+    pub superseded_by: Option<u64>,
+}
+
+/// One command of a multi-command job in a terminal task snapshot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TerminalStepSnapshot {
+    pub index: u64,
+    pub command: String,
+    pub state: String,
+    pub exit_code: Option<i64>,
+    pub duration_ms: Option<u64>,
 }
 
 /// Validated `status` result (additive contract §7 legacy shape, preserved).
@@ -723,12 +749,63 @@ impl StreamSnapshot {
     }
 }
 
+/// Per-test counts and failing tests of one retrieved task (`reports`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestsSnapshot {
+    pub passed: u64,
+    pub failed: u64,
+    pub skipped: u64,
+    /// Failing test names with their first message line, when reported.
+    pub failures: Vec<(String, Option<String>)>,
+}
+
+impl TestsSnapshot {
+    fn from_value(value: &Value) -> Result<Self, String> {
+        let object = value
+            .as_object()
+            .ok_or_else(|| "task tests must be an object".to_string())?;
+        let count = |key: &str| {
+            object
+                .get(key)
+                .and_then(Value::as_u64)
+                .ok_or_else(|| format!("task tests must carry a numeric \"{key}\""))
+        };
+        let failures = object
+            .get("failures")
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+            .iter()
+            .map(|failure| {
+                let name = failure
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .map(str::to_owned)
+                    .ok_or_else(|| "failing test must carry \"name\"".to_string())?;
+                let message = failure
+                    .get("message")
+                    .and_then(Value::as_str)
+                    .map(str::to_owned);
+                Ok((name, message))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(Self {
+            passed: count("passed")?,
+            failed: count("failed")?,
+            skipped: count("skipped")?,
+            failures,
+        })
+    }
+}
+
 /// One retrieved task's streams.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RetrievedTaskSnapshot {
     pub id: String,
     pub stdout: Option<StreamSnapshot>,
     pub stderr: Option<StreamSnapshot>,
+    /// Additive — omitted when the job declares no `reports`.
+    pub tests: Option<TestsSnapshot>,
 }
 
 /// Retrieval domain result (contract §6): consumed by the renderers.
@@ -790,7 +867,16 @@ impl OutputSnapshot {
                     None | Some(Value::Null) => None,
                     Some(value) => Some(StreamSnapshot::from_value(value.clone())?),
                 };
-                Ok(RetrievedTaskSnapshot { id, stdout, stderr })
+                let tests = match task.get("tests") {
+                    None | Some(Value::Null) => None,
+                    Some(value) => Some(TestsSnapshot::from_value(value)?),
+                };
+                Ok(RetrievedTaskSnapshot {
+                    id,
+                    stdout,
+                    stderr,
+                    tests,
+                })
             })
             .collect::<Result<Vec<_>, String>>()?;
         Ok(Self {
@@ -1042,7 +1128,10 @@ fn read_optional_terminal_tasks(
             let state = task
                 .get("state")
                 .and_then(Value::as_str)
-                .filter(|state| matches!(*state, "passed" | "failed" | "cancelled"))
+                .filter(|state| matches!(
+                        *state,
+                        "passed" | "failed" | "warning" | "skipped" | "cancelled" | "waiting" | "starting" | "ready" | "unready"
+                    ))
                 .map(str::to_owned)
                 .ok_or_else(|| format!("status result task at index {index} field \"state\" is invalid"))?;
             let duration_ms = match task.get("durationMs") {
@@ -1056,17 +1145,83 @@ fn read_optional_terminal_tasks(
                     ))
                 }
             };
+            let optional_string = |field: &str| match task.get(field) {
+                None | Some(Value::Null) => Ok(None),
+                Some(Value::String(reason)) => Ok(Some(reason.clone())),
+                Some(_) => Err(format!(
+                    "status result task at index {index} field \"{field}\" must be a string"
+                )),
+            };
             Ok(TerminalTaskSnapshot {
                 id,
                 name,
                 state,
                 duration_ms,
+                waiting: optional_string("waiting")?,
+                failure_reason: optional_string("failureReason")?,
+                restarts: match task.get("restarts") {
+                    None | Some(Value::Null) => None,
+                    Some(value) => Some(
+                        value
+                            .as_u64()
+                            .and_then(|restarts| u32::try_from(restarts).ok())
+                            .ok_or_else(|| {
+                                format!("status result task at index {index} field \"restarts\" must be a number")
+                            })?,
+                    ),
+                },
+                health: optional_string("health")?,
+                steps: read_task_steps(task.get("steps"), index)?,
+                superseded_by: match task.get("supersededBy") {
+                    None | Some(Value::Null) => None,
+                    Some(value) => Some(value.as_u64().ok_or_else(|| {
+                        format!("status result task at index {index} field \"supersededBy\" must be a number")
+                    })?),
+                },
             })
         })
         .collect::<Result<Vec<_>, _>>()
         .map(Some)
 }
 
+fn read_task_steps(
+    steps: Option<&Value>,
+    task_index: usize,
+) -> Result<Vec<TerminalStepSnapshot>, String> {
+    let steps = match steps {
+        None | Some(Value::Null) => return Ok(vec![]),
+        Some(Value::Array(steps)) => steps,
+        Some(_) => {
+            return Err(format!(
+                "status result task at index {task_index} field \"steps\" must be an array"
+            ))
+        }
+    };
+    steps
+        .iter()
+        .map(|step| {
+            let invalid =
+                || format!("status result task at index {task_index} has an invalid step");
+            let text = |field: &str| {
+                step.get(field)
+                    .and_then(Value::as_str)
+                    .map(str::to_owned)
+                    .ok_or_else(invalid)
+            };
+            Ok(TerminalStepSnapshot {
+                index: step
+                    .get("index")
+                    .and_then(Value::as_u64)
+                    .ok_or_else(invalid)?,
+                command: text("command")?,
+                state: text("state")?,
+                exit_code: step.get("exitCode").and_then(Value::as_i64),
+                duration_ms: step.get("durationMs").and_then(Value::as_u64),
+            })
+        })
+        .collect()
+}
+
 fn read_string_array(
     object: &serde_json::Map<String, Value>,
     field: &str,
@@ -1232,9 +1387,10 @@ impl ControlClient {
             params["instanceToken"] = serde_json::json!(token);
         }
         // Graceful shutdown can run up to the cancel grace period (default
-        // 5s), so a normal 3s read bound would misclassify a legitimate
-        // escalated cleanup as a client timeout. Bounded generously.
-        let read_bound = Duration::from_secs(30);
+        // 5s) and `when: always` jobs up to their own budget, so a normal 3s
+        // read bound would misclassify a legitimate cleanup as a client
+        // timeout. Bounded generously.
+        let read_bound = crate::executor::ALWAYS_TIMEOUT + Duration::from_secs(30);
         self.reader
             .get_mut()
             .set_read_timeout(Some(read_bound))
@@ -1437,12 +1593,24 @@ mod tests {
                     name: "lint".to_owned(),
                     state: "failed".to_owned(),
                     duration_ms: Some(120),
+                    waiting: None,
+                    failure_reason: None,
+                    restarts: None,
+                    health: None,
+                    steps: vec![],
+                    superseded_by: None,
                 },
                 TerminalTaskSnapshot {
                     id: "test".to_owned(),
                     name: "test".to_owned(),
                     state: "cancelled".to_owned(),
                     duration_ms: None,
+                    waiting: None,
+                    failure_reason: None,
+                    restarts: None,
+                    health: None,
+                    steps: vec![],
+                    superseded_by: None,
                 },
             ])
         );
@@ -1465,12 +1633,24 @@ mod tests {
                         name: "lint".to_owned(),
                         state: "failed".to_owned(),
                         duration_ms: Some(120),
+                        waiting: None,
+                        failure_reason: None,
+                        restarts: None,
+                        health: None,
+                        steps: vec![],
+                        superseded_by: None,
                     },
                     TerminalTaskSnapshot {
                         id: "test".to_owned(),
                         name: "test".to_owned(),
                         state: "cancelled".to_owned(),
                         duration_ms: None,
+                        waiting: None,
+                        failure_reason: None,
+                        restarts: None,
+                        health: None,
+                        steps: vec![],
+                        superseded_by: None,
                     },
                 ]),
             }
@@ -423,6 +423,13 @@ mod tests {
                     name: task.to_owned(),
                     state: crate::executor::TaskState::Passed,
                     duration_ms: Some(10_000),
+                    waiting: None,
+                    failure_reason: None,
+                    restarts: None,
+                    health: None,
+                    tests: None,
+                    steps: vec![],
+                    superseded_by: None,
                 },
             });
         }
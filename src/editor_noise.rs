@@ -0,0 +1,143 @@
+//! Editor artifact suppression (`on.editor_noise`, default on).
+//!
+//! Saving a file in most editors touches more than the file: swap files,
+//! backups, lock files and atomic-save temporaries all land in the watched
+//! tree. Each well-known artifact is either dropped from the batch or mapped
+//! back to the real file it stands for, before the batch is normalized, so
+//! one save routes exactly one path.
+
+use std::path::Path;
+
+/// What the filter does with one artifact path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Noise {
+    /// Never a real change (swap files, lock files, write probes).
+    Drop { editor: &'static str },
+    /// An atomic-save temporary or backup that stands for `target`.
+    Target {
+        editor: &'static str,
+        target: String,
+    },
+}
+
+impl Noise {
+    pub fn editor(&self) -> &'static str {
+        match self {
+            Noise::Drop { editor } | Noise::Target { editor, .. } => editor,
+        }
+    }
+}
+
+/// Suffixes an editor appends to the real file name during atomic saves.
+const TARGET_SUFFIXES: &[(&str, &str)] = &[
+    ("___jb_tmp___", "jetbrains"),
+    ("___jb_old___", "jetbrains"),
+    (".vsctmp", "vscode"),
+    ("~", "vim"),
+];
+
+/// Vim swap file extensions (`.name.swp`, then `.swo`, `.swn`, ...).
+const VIM_SWAP_EXTENSIONS: &[&str] = &[".swp", ".swo", ".swn", ".swx"];
+
+/// Classifies a changed path by its file name; None for ordinary files.
+pub fn classify(path: &str) -> Option<Noise> {
+    let name = Path::new(path).file_name()?.to_str()?;
+    // Vim probes directory writability with a file literally named 4913.
+    if name == "4913" {
+        return Some(Noise::Drop { editor: "vim" });
+    }
+    if name.starts_with('.')
+        && VIM_SWAP_EXTENSIONS
+            .iter()
+            .any(|extension| name.ends_with(extension))
+    {
+        return Some(Noise::Drop { editor: "vim" });
+    }
+    // Emacs: `#name#` auto-save files and `.#name` lock symlinks.
+    if name.starts_with(".#") || (name.len() > 2 && name.starts_with('#') && name.ends_with('#')) {
+        return Some(Noise::Drop { editor: "emacs" });
+    }
+    for (suffix, editor) in TARGET_SUFFIXES {
+        let Some(stem) = name.strip_suffix(suffix) else {
+            continue;
+        };
+        if stem.is_empty() {
+            continue;
+        }
+        let target = &path[..path.len() - suffix.len()];
+        return Some(Noise::Target {
+            editor,
+            target: target.to_owned(),
+        });
+    }
+    None
+}
+
+/// Applies the filter to a batch: drops artifacts and rewrites atomic-save
+/// paths to their target. Order is preserved; the caller normalizes.
+pub fn filter(paths: Vec<String>) -> Vec<String> {
+    paths
+        .into_iter()
+        .filter_map(|path| match classify(&path) {
+            None => Some(path),
+            Some(Noise::Drop { .. }) => None,
+            Some(Noise::Target { target, .. }) => Some(target),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drops_swap_lock_and_probe_files() {
+        for path in [
+            "/w/src/.main.rs.swp",
+            "/w/src/.main.rs.swo",
+            "/w/src/4913",
+            "/w/src/#main.rs#",
+            "/w/src/.#main.rs",
+        ] {
+            assert!(
+                matches!(classify(path), Some(Noise::Drop { .. })),
+                "{path} should be dropped"
+            );
+        }
+    }
+
+    #[test]
+    fn maps_atomic_save_temporaries_to_the_real_file() {
+        assert_eq!(
+            classify("/w/src/main.rs___jb_tmp___"),
+            Some(Noise::Target {
+                editor: "jetbrains",
+                target: "/w/src/main.rs".to_owned()
+            })
+        );
+        assert_eq!(
+            classify("src/main.rs~").map(|noise| noise.editor()),
+            Some("vim")
+        );
+        assert_eq!(
+            filter(vec![
+                "/w/src/main.rs".to_owned(),
+                "/w/src/main.rs~".to_owned(),
+                "/w/src/lib.rs.vsctmp".to_owned(),
+                "/w/src/.lib.rs.swp".to_owned(),
+            ]),
+            vec![
+                "/w/src/main.rs".to_owned(),
+                "/w/src/main.rs".to_owned(),
+                "/w/src/lib.rs".to_owned(),
+            ]
+        );
+    }
+
+    #[test]
+    fn ordinary_files_pass_through() {
+        for path in ["/w/src/main.rs", "/w/src/swp", "/w/~", "/w/#", "/w/a.swp"] {
+            assert_eq!(classify(path), None, "{path} is not editor noise");
+        }
+    }
+}
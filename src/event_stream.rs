@@ -98,12 +98,46 @@ impl EventStream {
                 "tsMs": ts_ms,
                 "task": task.name,
                 "group": task.id,
-                "state": match task.state {
-                    crate::executor::TaskState::Passed => "passed",
-                    crate::executor::TaskState::Failed => "failed",
-                    crate::executor::TaskState::Cancelled => "cancelled",
-                },
+                "state": task.state.label(),
                 "durationMs": task.duration_ms,
+                "failureReason": task.failure_reason,
+                "tests": task.tests,
+                "steps": task.steps,
+                "supersededBy": task.superseded_by,
+            }),
+            Event::TaskWaiting { run_id, task } => json!({
+                "schemaVersion": EVENT_SCHEMA_VERSION,
+                "event": "task_waiting",
+                "runId": run_id,
+                "tsMs": ts_ms,
+                "task": task.name,
+                "group": task.id,
+                "waiting": task.waiting,
+            }),
+            Event::ServiceState { run_id, task } => json!({
+                "schemaVersion": EVENT_SCHEMA_VERSION,
+                "event": "service_state",
+                "runId": run_id,
+                "tsMs": ts_ms,
+                "task": task.name,
+                "group": task.id,
+                "state": task.state.label(),
+                "failureReason": task.failure_reason,
+                "restarts": task.restarts,
+                "health": task.health,
+            }),
+            Event::ServiceAction {
+                run_id,
+                task,
+                action,
+            } => json!({
+                "schemaVersion": EVENT_SCHEMA_VERSION,
+                "event": "service_action",
+                "runId": run_id,
+                "tsMs": ts_ms,
+                "task": task.name,
+                "group": task.id,
+                "action": action,
             }),
             Event::Finished {
                 run_id,
@@ -129,6 +163,20 @@ impl EventStream {
                 "tsMs": ts_ms,
                 "supersededBy": superseded_by,
             }),
+            Event::Diagnostics {
+                run_id,
+                task,
+                diagnostics,
+                truncated,
+            } => json!({
+                "schemaVersion": EVENT_SCHEMA_VERSION,
+                "event": "diagnostics",
+                "runId": run_id,
+                "tsMs": ts_ms,
+                "task": task,
+                "diagnostics": diagnostics,
+                "truncated": truncated,
+            }),
             Event::RecoveryPhase {
                 run_id,
                 job,
@@ -258,6 +306,13 @@ mod tests {
                 name: "test @quick".to_owned(),
                 state: crate::executor::TaskState::Passed,
                 duration_ms: Some(120),
+                waiting: None,
+                failure_reason: None,
+                restarts: None,
+                health: None,
+                tests: None,
+                steps: vec![],
+                superseded_by: None,
             },
         });
         drop(stream);
@@ -271,6 +326,34 @@ mod tests {
         std::fs::remove_file(&path).ok();
     }
 
+    #[test]
+    fn diagnostics_record_carries_structured_problems() {
+        let (path, stream) = stream_in_temp("diagnostics");
+        stream.emit(Event::Diagnostics {
+            run_id: 7,
+            task: "lint".to_owned(),
+            diagnostics: vec![crate::matchers::Diagnostic {
+                task: "lint".to_owned(),
+                matcher: "eslint".to_owned(),
+                file: Some("src/a.js".to_owned()),
+                line: Some(3),
+                column: Some(5),
+                severity: crate::rules::Severity::Warning,
+                message: "Unexpected console statement".to_owned(),
+                code: Some("no-console".to_owned()),
+            }],
+            truncated: false,
+        });
+        drop(stream);
+        let records = read_lines(&path);
+        assert_eq!(records[0]["event"], "diagnostics");
+        assert_eq!(records[0]["task"], "lint");
+        assert_eq!(records[0]["diagnostics"][0]["file"], "src/a.js");
+        assert_eq!(records[0]["diagnostics"][0]["severity"], "warning");
+        assert_eq!(records[0]["diagnostics"][0]["code"], "no-console");
+        std::fs::remove_file(&path).ok();
+    }
+
     #[test]
     fn recovery_phase_carries_structured_phase_and_outcome() {
         let (path, stream) = stream_in_temp("recovery-phase");
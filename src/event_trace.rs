@@ -0,0 +1,382 @@
+//! Watcher event traces (`--record-events FILE`, `fzz replay FILE`).
+//!
+//! A trace is NDJSON: one header line naming the watch root, then one line
+//! per debounce batch with its offset from the start of recording, the
+//! events the backend delivered, and the normalized changed-path set, and
+//! one line per finished generation with its start offset and duration.
+//! Replay feeds the recorded events back through the real watch loop on the
+//! recorded clock, so a timing-dependent bug report can be reproduced on
+//! another machine without the original filesystem.
+
+use crate::executor::{Clock, Event};
+use crate::watcher::FileEvent;
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+/// Trace format version; replay rejects anything else.
+pub const TRACE_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TraceHeader {
+    pub version: u32,
+    pub root: String,
+}
+
+/// One backend event: the path as delivered and `any` or `continuous`.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TraceEvent {
+    pub path: String,
+    pub kind: String,
+}
+
+/// One recorded debounce batch.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TraceBatch {
+    /// Milliseconds since recording started.
+    pub t_ms: u64,
+    pub batch: u64,
+    pub events: Vec<TraceEvent>,
+    /// Changed paths after editor-noise filtering and normalization.
+    pub normalized: Vec<String>,
+}
+
+/// One recorded generation: when it started and how long it kept the
+/// watcher busy until it finished or was cancelled.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TraceRun {
+    /// Milliseconds since recording started.
+    pub t_ms: u64,
+    pub generation: u64,
+    /// Debounce batch that scheduled it; None for init and git runs.
+    pub batch: Option<u64>,
+    pub duration_ms: u64,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum TraceLine {
+    Batch(TraceBatch),
+    Run(TraceRun),
+}
+
+/// A loaded trace, ready for replay.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trace {
+    pub root: PathBuf,
+    pub batches: Vec<TraceBatch>,
+    pub runs: Vec<TraceRun>,
+}
+
+impl Trace {
+    /// Recorded duration of the generation scheduled by `batch`.
+    pub fn batch_run(&self, batch: u64) -> Option<Duration> {
+        self.runs
+            .iter()
+            .find(|run| run.batch == Some(batch))
+            .map(|run| Duration::from_millis(run.duration_ms))
+    }
+
+    /// Recorded duration of the `index`-th generation without a batch
+    /// (init first, then git runs).
+    pub fn unbatched_run(&self, index: usize) -> Option<Duration> {
+        self.runs
+            .iter()
+            .filter(|run| run.batch.is_none())
+            .nth(index)
+            .map(|run| Duration::from_millis(run.duration_ms))
+    }
+}
+
+/// Replay's injected clock: time only moves when the replay advances it to
+/// a recorded offset, so busy windows follow the trace instead of the wall
+/// clock.
+pub struct ReplayClock {
+    origin: Instant,
+    offset: Mutex<Duration>,
+}
+
+impl Default for ReplayClock {
+    fn default() -> Self {
+        ReplayClock {
+            origin: Instant::now(),
+            offset: Mutex::new(Duration::ZERO),
+        }
+    }
+}
+
+impl ReplayClock {
+    /// Time since the trace started.
+    pub fn offset(&self) -> Duration {
+        *self.offset.lock().unwrap()
+    }
+
+    /// Moves the clock forward to `offset`; it never runs backwards.
+    pub fn advance_to(&self, offset: Duration) {
+        let mut current = self.offset.lock().unwrap();
+        *current = (*current).max(offset);
+    }
+}
+
+impl Clock for ReplayClock {
+    fn now(&self) -> Instant {
+        self.origin + self.offset()
+    }
+
+    fn elapsed(&self, started: Instant) -> Duration {
+        self.now().saturating_duration_since(started)
+    }
+
+    fn sleep(&self, duration: Duration) {
+        *self.offset.lock().unwrap() += duration;
+    }
+}
+
+/// A trace being replayed and the clock it drives.
+pub struct Replay {
+    pub trace: Trace,
+    pub clock: ReplayClock,
+}
+
+impl Replay {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        Ok(Replay {
+            trace: load(path)?,
+            clock: ReplayClock::default(),
+        })
+    }
+}
+
+impl TraceBatch {
+    /// Rebuilds the backend events, re-anchoring paths recorded under
+    /// `recorded_root` onto `root` so a trace replays in any checkout.
+    pub fn file_events(&self, recorded_root: &Path, root: &Path) -> Vec<FileEvent> {
+        self.events
+            .iter()
+            .map(|event| {
+                let path = Path::new(&event.path);
+                let path = match path.strip_prefix(recorded_root) {
+                    Ok(relative) => root.join(relative),
+                    Err(_) => root.join(path),
+                };
+                FileEvent {
+                    path: path.to_string_lossy().into_owned(),
+                    continuous: event.kind == "continuous",
+                }
+            })
+            .collect()
+    }
+}
+
+struct Recorder {
+    file: std::fs::File,
+    started: Instant,
+    /// Generations in flight: their batch and start offset.
+    running: HashMap<u64, (Option<u64>, u64)>,
+}
+
+static RECORDER: LazyLock<Mutex<Option<Recorder>>> = LazyLock::new(|| Mutex::new(None));
+
+/// Starts recording to `path` (truncated), writing the header for `root`.
+pub fn init(path: &Path, root: &Path) -> Result<(), String> {
+    let mut file = std::fs::File::create(path)
+        .map_err(|err| format!("cannot create {}: {}", path.display(), err))?;
+    let header = TraceHeader {
+        version: TRACE_VERSION,
+        root: root.display().to_string(),
+    };
+    let line = serde_json::to_string(&header).map_err(|err| err.to_string())?;
+    writeln!(file, "{}", line)
+        .map_err(|err| format!("cannot write {}: {}", path.display(), err))?;
+    *RECORDER.lock().unwrap() = Some(Recorder {
+        file,
+        started: Instant::now(),
+        running: HashMap::new(),
+    });
+    Ok(())
+}
+
+/// Appends one batch; a no-op unless `init` succeeded. Write errors are
+/// reported once and stop the recording rather than the watcher.
+pub fn record(batch_id: u64, events: &[FileEvent], normalized: &[String]) {
+    let mut recorder = RECORDER.lock().unwrap();
+    let Some(active) = recorder.as_mut() else {
+        return;
+    };
+    let batch = TraceBatch {
+        t_ms: active.started.elapsed().as_millis() as u64,
+        batch: batch_id,
+        events: events
+            .iter()
+            .map(|event| TraceEvent {
+                path: event.path.clone(),
+                kind: if event.continuous {
+                    "continuous"
+                } else {
+                    "any"
+                }
+                .to_owned(),
+            })
+            .collect(),
+        normalized: normalized.to_vec(),
+    };
+    let written = serde_json::to_string(&batch);
+    write_line(&mut recorder, written);
+}
+
+/// Records generation lifetimes from run events, so replay knows how long
+/// each generation kept the watcher busy. A no-op unless `init` succeeded.
+pub fn observe(event: &Event) {
+    let mut recorder = RECORDER.lock().unwrap();
+    let Some(active) = recorder.as_mut() else {
+        return;
+    };
+    let now_ms = active.started.elapsed().as_millis() as u64;
+    let run_id = match event {
+        Event::Started { run_id, batch, .. } => {
+            active.running.insert(*run_id, (*batch, now_ms));
+            return;
+        }
+        Event::Finished { run_id, .. } | Event::Cancelled { run_id, .. } => *run_id,
+        _ => return,
+    };
+    let Some((batch, t_ms)) = active.running.remove(&run_id) else {
+        return;
+    };
+    let run = TraceRun {
+        t_ms,
+        generation: run_id,
+        batch,
+        duration_ms: now_ms.saturating_sub(t_ms),
+    };
+    let written = serde_json::to_string(&run);
+    write_line(&mut recorder, written);
+}
+
+/// Write errors are reported once and stop the recording rather than the
+/// watcher.
+fn write_line(recorder: &mut Option<Recorder>, line: serde_json::Result<String>) {
+    let Some(active) = recorder.as_mut() else {
+        return;
+    };
+    let written = line
+        .map_err(|err| err.to_string())
+        .and_then(|line| writeln!(active.file, "{}", line).map_err(|err| err.to_string()));
+    if let Err(err) = written {
+        crate::stdout::warn(&format!("Stopped recording events: {}", err));
+        *recorder = None;
+    }
+}
+
+/// Parses trace text: the header line first, then one batch or generation
+/// per line.
+pub fn parse(content: &str) -> Result<Trace, String> {
+    let mut lines = content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty());
+    let Some((_, first)) = lines.next() else {
+        return Err("trace is empty".to_owned());
+    };
+    let header: TraceHeader =
+        serde_json::from_str(first).map_err(|err| format!("invalid trace header: {}", err))?;
+    if header.version != TRACE_VERSION {
+        return Err(format!(
+            "unsupported trace version {} (expected {})",
+            header.version, TRACE_VERSION
+        ));
+    }
+    let mut batches = vec![];
+    let mut runs = vec![];
+    for (index, line) in lines {
+        match serde_json::from_str(line)
+            .map_err(|err| format!("invalid trace line {}: {}", index + 1, err))?
+        {
+            TraceLine::Batch(batch) => batches.push(batch),
+            TraceLine::Run(run) => runs.push(run),
+        }
+    }
+    Ok(Trace {
+        root: PathBuf::from(header.root),
+        batches,
+        runs,
+    })
+}
+
+pub fn load(path: &Path) -> Result<Trace, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|err| format!("cannot read {}: {}", path.display(), err))?;
+    parse(&content).map_err(|err| format!("{}: {}", path.display(), err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_header_and_batches() {
+        let trace = parse(concat!(
+            "{\"version\":1,\"root\":\"/w\"}\n",
+            "{\"t_ms\":0,\"batch\":1,\"events\":[{\"path\":\"/w/a.rs\",\"kind\":\"any\"}],\"normalized\":[\"/w/a.rs\"]}\n",
+            "\n",
+            "{\"t_ms\":250,\"batch\":2,\"events\":[],\"normalized\":[]}\n",
+            "{\"t_ms\":0,\"generation\":1,\"batch\":null,\"duration_ms\":900}\n",
+            "{\"t_ms\":260,\"generation\":2,\"batch\":2,\"duration_ms\":40}\n",
+        ))
+        .expect("trace parses");
+        assert_eq!(trace.root, PathBuf::from("/w"));
+        assert_eq!(trace.batches.len(), 2);
+        assert_eq!(trace.batches[1].t_ms, 250);
+        assert_eq!(trace.unbatched_run(0), Some(Duration::from_millis(900)));
+        assert_eq!(trace.batch_run(2), Some(Duration::from_millis(40)));
+        assert_eq!(trace.batch_run(1), None);
+    }
+
+    #[test]
+    fn replay_clock_moves_only_forward_to_recorded_offsets() {
+        let clock = ReplayClock::default();
+        let started = clock.now();
+        clock.advance_to(Duration::from_millis(300));
+        clock.advance_to(Duration::from_millis(100));
+        assert_eq!(clock.offset(), Duration::from_millis(300));
+        clock.sleep(Duration::from_millis(50));
+        assert_eq!(clock.elapsed(started), Duration::from_millis(350));
+    }
+
+    #[test]
+    fn rejects_missing_header_and_unknown_versions() {
+        assert!(parse("").is_err());
+        assert!(parse("{\"version\":9,\"root\":\"/w\"}\n")
+            .unwrap_err()
+            .contains("unsupported trace version 9"));
+        assert!(parse("{\"version\":1,\"root\":\"/w\"}\nnot json\n")
+            .unwrap_err()
+            .contains("line 2"));
+    }
+
+    #[test]
+    fn replayed_events_are_reanchored_onto_the_current_root() {
+        let batch = TraceBatch {
+            t_ms: 0,
+            batch: 1,
+            events: vec![
+                TraceEvent {
+                    path: "/old/src/a.rs".to_owned(),
+                    kind: "continuous".to_owned(),
+                },
+                TraceEvent {
+                    path: "b.rs".to_owned(),
+                    kind: "any".to_owned(),
+                },
+            ],
+            normalized: vec![],
+        };
+        let events = batch.file_events(Path::new("/old"), Path::new("/new"));
+        assert_eq!(events[0].path, "/new/src/a.rs");
+        assert!(events[0].continuous);
+        assert_eq!(events[1].path, "/new/b.rs");
+        assert!(!events[1].continuous);
+    }
+}
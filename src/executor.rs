@@ -4,30 +4,30 @@
 //! collection, timing, lifecycle events, and stage barriers. Wait and restart
 //! policies only decide how plans are submitted or replaced.
 
-use crate::cmd::{self, CaptureHandle, LoggedChild, ShutdownOutcome};
+use crate::cmd::{self, CaptureHandle, ShutdownOutcome};
+pub use crate::cmd::{ChildProcess, ProcessRunner, SystemProcessRunner};
 use crate::diagnostics;
 use crate::logging;
 use crate::output::OutputRegistry;
 use crate::plan::{
     ExecutionSignature, RunOutcome, RunPlan, Stage, TaskContext, TaskOutcome, TaskPlan,
 };
-use crate::rules::CommandLine;
+use crate::readiness::{self, HealthMonitor, ReadinessCheck};
+use crate::rules::{CommandLine, When};
 use crate::stdout;
-
-/// Bounded service restart attempts on unexpected exit (TASK-0035).
-pub const SERVICE_MAX_RESTARTS: usize = 3;
-/// Backoff between service restarts (TASK-0035).
-pub const SERVICE_RESTART_BACKOFF_MS: u64 = 500;
 use serde::Serialize;
-use std::collections::VecDeque;
-use std::io;
+use std::collections::{BTreeMap, VecDeque};
 use std::process::ExitStatus;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime};
 
 const POLL_INTERVAL: Duration = Duration::from_millis(10);
 
+/// Budget shared by the `when: always` jobs that run after a cancellation;
+/// jobs still running then are stopped and later ones never start.
+pub const ALWAYS_TIMEOUT: Duration = Duration::from_secs(60);
+
 /// Cancellation signal shared by the scheduler, executor, and approval
 /// adapter. It lets a replacement or exact cancel interrupt a blocking
 /// recovery boundary before the worker can process the queued command.
@@ -48,15 +48,46 @@ impl CancellationToken {
     }
 }
 
-/// Wire-level task state for the correlated snapshot (contract §7). `Skipped`
-/// (fail-fast skipped work) collapses to `Cancelled` — never-started work is
-/// reported as cancelled, matching the pi-watcher decoder vocabulary.
+/// Wire-level task state for the correlated snapshot (contract §7). Work a
+/// fail-fast stop or a cancellation left unstarted is reported `Cancelled`;
+/// `Skipped` is only for a job whose `when` condition did not hold.
+/// `Passed`, `Failed`, `Cancelled`, `Warning` and `Skipped` are terminal.
+/// The rest describe live work: `Waiting` is a queued task blocked on a
+/// resource lock (`jobs[].locks`), and `Starting`, `Ready` and `Unready`
+/// follow a running service's readiness probe.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum TaskState {
     Passed,
     Failed,
     Cancelled,
+    /// Failed, but the job allows failure (`jobs[].allow_failure`).
+    Warning,
+    /// Not run because its `when` condition did not hold.
+    Skipped,
+    Waiting,
+    /// A service whose readiness probe has not passed yet.
+    Starting,
+    /// A running service that is ready (or declares no probe).
+    Ready,
+    /// A running service whose probe did not pass within its timeout.
+    Unready,
+}
+
+impl TaskState {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TaskState::Passed => "passed",
+            TaskState::Failed => "failed",
+            TaskState::Cancelled => "cancelled",
+            TaskState::Warning => "warning",
+            TaskState::Skipped => "skipped",
+            TaskState::Waiting => "waiting",
+            TaskState::Starting => "starting",
+            TaskState::Ready => "ready",
+            TaskState::Unready => "unready",
+        }
+    }
 }
 
 /// One task's terminal outcome for the correlated snapshot (TASK-0050).
@@ -71,6 +102,57 @@ pub struct TaskSnapshot {
     pub name: String,
     pub state: TaskState,
     pub duration_ms: Option<u64>,
+    /// Why the task is (or was, when cancelled before starting) still
+    /// queued, e.g. `lock 'db' held by migrate`. Omitted otherwise.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub waiting: Option<String>,
+    /// Why a failed task failed when that is more than its exit status,
+    /// e.g. `cpu limit of 10s exceeded`. Omitted otherwise.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failure_reason: Option<String>,
+    /// Times a running service was restarted. Omitted for other jobs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restarts: Option<u32>,
+    /// Last health check result of a service, e.g. `passing` or
+    /// `failing 1/3: tcp localhost:3000`. Omitted until one completes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub health: Option<String>,
+    /// Per-test counts and failing tests from the job's `reports`, read
+    /// when it finished. Omitted when the job declares none or they held
+    /// no test.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tests: Option<crate::test_reports::TestSummary>,
+    /// Per-command results of a multi-command job, in `run` order. Omitted
+    /// for single-command jobs and services.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub steps: Vec<StepSnapshot>,
+    /// Generation whose change cancelled this job under `--on-busy
+    /// restart-affected`. Omitted otherwise.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub superseded_by: Option<u64>,
+}
+
+/// One command of a multi-command job. A step that never ran is `skipped`;
+/// one interrupted by a stop is `cancelled`; a failure tolerated by
+/// `continue_on_error` is a `warning`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StepSnapshot {
+    /// 1-based position in the job's `run` list.
+    pub index: usize,
+    pub command: String,
+    pub state: TaskState,
+    /// Omitted when the step never ran, failed to start or was signalled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    /// `[start, end)` byte offsets of the step's output within the job's
+    /// observed stdout and stderr. Omitted when the output was not captured.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stdout_offsets: Option<[u64; 2]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stderr_offsets: Option<[u64; 2]>,
 }
 
 /// One exact generation/job recovery approval request. Command text is
@@ -171,6 +253,30 @@ pub enum Event {
     /// generation. Emitted per task so the correlated snapshot can show task
     /// outcomes and durations (TASK-0050).
     TaskTerminal { run_id: u64, task: TaskSnapshot },
+    /// A queued task is blocked on a resource lock (`task.waiting` names
+    /// it), or acquired its locks after waiting (`task.waiting` is None).
+    TaskWaiting { run_id: u64, task: TaskSnapshot },
+    /// A service is `starting` (probing), `ready`, or `unready` after its
+    /// readiness timeout (`task.failure_reason` says why), or its restart
+    /// count or health result changed.
+    ServiceState { run_id: u64, task: TaskSnapshot },
+    /// A change matched a running service that stays up
+    /// (`jobs[].on_change`); `action` is what was done, e.g. `signal
+    /// SIGHUP`, `command nginx -s reload` or `ignore`.
+    ServiceAction {
+        run_id: u64,
+        task: TaskSnapshot,
+        action: String,
+    },
+    /// Problem-matcher diagnostics (`jobs[].matchers`) extracted from a
+    /// finished task's output; emitted before its `TaskTerminal`, and only
+    /// when something matched.
+    Diagnostics {
+        run_id: u64,
+        task: String,
+        diagnostics: Vec<crate::matchers::Diagnostic>,
+        truncated: bool,
+    },
     /// Non-terminal recovery lifecycle evidence for one generation/job.
     RecoveryPhase {
         run_id: u64,
@@ -193,65 +299,6 @@ where
     }
 }
 
-pub trait ChildProcess: Send {
-    fn try_wait(&mut self) -> io::Result<Option<ExitStatus>>;
-    fn shutdown(
-        &mut self,
-        signal: nix::sys::signal::Signal,
-        grace: Duration,
-        verbose: bool,
-    ) -> ShutdownOutcome;
-}
-
-impl ChildProcess for LoggedChild {
-    fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
-        LoggedChild::try_wait(self)
-    }
-
-    fn shutdown(
-        &mut self,
-        signal: nix::sys::signal::Signal,
-        grace: Duration,
-        verbose: bool,
-    ) -> ShutdownOutcome {
-        LoggedChild::shutdown(self, signal, grace, verbose)
-    }
-}
-
-pub trait ProcessRunner: Send + Sync {
-    fn spawn(
-        &self,
-        task: &str,
-        command: &CommandLine,
-        context: &TaskContext,
-        capture: Option<Arc<CaptureHandle>>,
-        label: Option<String>,
-        quiet: bool,
-    ) -> Result<Box<dyn ChildProcess>, String>;
-}
-
-pub struct SystemProcessRunner;
-
-impl ProcessRunner for SystemProcessRunner {
-    fn spawn(
-        &self,
-        _task: &str,
-        command: &CommandLine,
-        context: &TaskContext,
-        capture: Option<Arc<CaptureHandle>>,
-        label: Option<String>,
-        quiet: bool,
-    ) -> Result<Box<dyn ChildProcess>, String> {
-        let child = match command {
-            CommandLine::Shell(command) => {
-                cmd::spawn_in_with_capture_quiet(command, context, capture, label, quiet)
-            }
-            CommandLine::Argv(argv) => cmd::spawn_argv_in(argv, context),
-        }?;
-        Ok(Box::new(child))
-    }
-}
-
 pub trait Clock: Send + Sync {
     fn now(&self) -> Instant;
     fn elapsed(&self, started: Instant) -> Duration;
@@ -429,6 +476,17 @@ pub enum Step {
     Finished,
 }
 
+/// Whether a task's live output is held back: always for quiet, capture and
+/// show-on-failure (TASK-0041), and for grouped jobs only while they run in
+/// a parallel group. Grouped services keep streaming; they never finish.
+fn holds_output(task: &ActiveTask) -> bool {
+    match task.output {
+        crate::rules::OutputPolicy::Inherit => false,
+        crate::rules::OutputPolicy::Grouped => task.group_occurrence.is_some() && !task.service,
+        _ => true,
+    }
+}
+
 struct ActiveTask {
     name: String,
     position: usize,
@@ -455,10 +513,54 @@ struct ActiveTask {
     output: crate::rules::OutputPolicy,
     /// Managed long-running service (TASK-0035).
     service: bool,
-    /// Unexpected-exit restart attempts remaining for a service (TASK-0035).
-    service_restarts_left: usize,
+    /// How a service restarts after an unexpected exit or failed health
+    /// checks (TASK-0035, `jobs[].restart`).
+    restart: crate::rules::RestartPolicy,
+    /// Restarts counted against `restart.max` since the budget last reset.
+    restart_streak: usize,
+    /// Restarts over the service's lifetime, for snapshots.
+    restarts: u32,
+    /// When a restarting service respawns; the backoff never blocks the
+    /// executor.
+    restart_at: Option<Instant>,
+    /// When the service's current process spawned.
+    spawned_at: Option<Instant>,
+    /// Periodic health check of a running service.
+    health: Option<HealthMonitor>,
+    /// Last reported health check result.
+    last_health: Option<String>,
+    /// What a matching change does to the running service.
+    on_change: crate::rules::OnChange,
+    /// A running `on_change` reload command.
+    reload: Option<Box<dyn ChildProcess>>,
     /// Defer original command errors while recovery may change the outcome.
     defer_failure: bool,
+    /// Resource locks held from spawn until the task leaves the run.
+    locks: Vec<crate::rules::ResourceLock>,
+    /// Cross-process lock (`jobs[].global_lock`), released on drop.
+    global_lock: Option<crate::global_lock::GlobalLock>,
+    /// Short cause for the snapshot's `failureReason`: an exceeded
+    /// resource limit or a readiness timeout.
+    failure_reason: Option<String>,
+    /// Readiness probe of a starting service; dropped once it resolves.
+    readiness: Option<ReadinessCheck>,
+    /// Problem matchers run over the output once the task finishes.
+    matchers: Vec<crate::matchers::Matcher>,
+    /// Test reports read once the task finishes.
+    reports: crate::rules::TestReports,
+    /// Wall-clock time of the first spawn; older report files are stale.
+    started_wall: Option<SystemTime>,
+    /// Exit codes counted as success.
+    success_codes: Vec<i32>,
+    /// Failures are warnings that never fail the run or stop it early.
+    allow_failure: bool,
+    when: When,
+    /// Results of the `run` steps that ended, in order.
+    steps: Vec<StepSnapshot>,
+    /// When the running step spawned, and the output bytes observed then.
+    step_started: Option<(Instant, Option<(u64, u64)>)>,
+    /// Indexes of the steps whose failure does not fail the job.
+    continue_on_error: Vec<usize>,
 }
 
 impl From<TaskPlan> for ActiveTask {
@@ -481,12 +583,144 @@ impl From<TaskPlan> for ActiveTask {
             command_total: task.commands.len(),
             output: task.output,
             service: task.service,
-            service_restarts_left: crate::executor::SERVICE_MAX_RESTARTS,
+            restart: task.rule.restart(),
+            restart_streak: 0,
+            restarts: 0,
+            restart_at: None,
+            spawned_at: None,
+            health: task
+                .rule
+                .health()
+                .filter(|_| task.service)
+                .map(HealthMonitor::new),
+            last_health: None,
+            on_change: task.rule.on_change().clone(),
+            reload: None,
             defer_failure: task.recovery_commands.is_some(),
+            locks: task.rule.locks().to_vec(),
+            global_lock: None,
+            failure_reason: None,
+            readiness: task
+                .rule
+                .ready()
+                .filter(|_| task.service)
+                .map(ReadinessCheck::new),
+            matchers: task
+                .rule
+                .matchers()
+                .iter()
+                .filter_map(crate::matchers::Matcher::new)
+                .collect(),
+            reports: task.rule.reports().clone(),
+            started_wall: None,
+            success_codes: task.rule.success_codes().to_vec(),
+            allow_failure: task.rule.allow_failure(),
+            when: task.rule.when(),
+            steps: vec![],
+            step_started: None,
+            continue_on_error: (0..task.commands.len())
+                .filter(|index| task.rule.continues_on_error(*index))
+                .collect(),
+        }
+    }
+}
+
+impl ActiveTask {
+    /// Whether a command exit counts as success under `success_codes`. A
+    /// process killed by a signal never does.
+    fn exited_successfully(&self, status: &ExitStatus) -> bool {
+        status
+            .code()
+            .is_some_and(|code| self.success_codes.contains(&code))
+    }
+
+    /// Marks the spawn of the next `run` step.
+    fn begin_step(&mut self, now: Instant) {
+        let observed = self.capture.as_ref().map(|capture| capture.observed());
+        self.step_started = Some((now, observed));
+    }
+
+    /// Whether a failure of the running step lets the job go on.
+    fn tolerates_step_failure(&self) -> bool {
+        self.continue_on_error.contains(&self.steps.len())
+    }
+
+    /// Records the result of the step that just ended. Service restarts are
+    /// not steps.
+    fn end_step(
+        &mut self,
+        command: String,
+        state: TaskState,
+        exit_code: Option<i32>,
+        clock: &dyn Clock,
+    ) {
+        let started = self.step_started.take();
+        if self.service {
+            return;
+        }
+        let observed = self.capture.as_ref().map(|capture| capture.observed());
+        let offsets = started.and_then(|(_, start)| start.zip(observed));
+        self.steps.push(StepSnapshot {
+            index: self.steps.len() + 1,
+            command,
+            state,
+            exit_code,
+            duration_ms: started.map(|(at, _)| clock.elapsed(at).as_millis() as u64),
+            stdout_offsets: offsets.map(|(start, end)| [start.0, end.0]),
+            stderr_offsets: offsets.map(|(start, end)| [start.1, end.1]),
+        });
+    }
+
+    /// Every declared step of a multi-command job: the one a stop
+    /// interrupted is cancelled and those never started are skipped. Empty
+    /// for single-command jobs and services.
+    fn step_snapshots(&self) -> Vec<StepSnapshot> {
+        if self.service || self.original_commands.len() < 2 {
+            return vec![];
+        }
+        let mut steps = self.steps.clone();
+        let mut interrupted = self.step_started.is_some();
+        for command in self.original_commands.iter().skip(steps.len()) {
+            steps.push(StepSnapshot {
+                index: steps.len() + 1,
+                command: command.display(),
+                state: match std::mem::take(&mut interrupted) {
+                    true => TaskState::Cancelled,
+                    false => TaskState::Skipped,
+                },
+                exit_code: None,
+                duration_ms: None,
+                stdout_offsets: None,
+                stderr_offsets: None,
+            });
+        }
+        steps
+    }
+
+    /// Terminal state and outcome of a task that recorded failures.
+    fn failed_outcome(&self) -> (TaskState, TaskOutcome) {
+        let failures = self.failures.clone();
+        match self.allow_failure {
+            true => (TaskState::Warning, TaskOutcome::Warning { failures }),
+            false => (TaskState::Failed, TaskOutcome::Failed { failures }),
         }
     }
 }
 
+/// Running services handed from a superseded generation to the one
+/// replacing it (`jobs[].on_change`).
+#[derive(Default)]
+pub struct KeptServices(Vec<ActiveTask>);
+
+/// Jobs handed from a generation to the one superseding it under
+/// `--on-busy restart-affected`, because the newer change does not affect
+/// them: those running, and the stages of those not started yet.
+#[derive(Default)]
+pub struct CarriedTasks {
+    running: Vec<ActiveTask>,
+    stages: Vec<Stage>,
+}
+
 pub struct Run {
     stages: VecDeque<Stage>,
     queued: VecDeque<TaskPlan>,
@@ -496,12 +730,30 @@ pub struct Run {
     /// Running managed services (TASK-0035): spawned, alive, and NOT blocking
     /// later stages. Reaped on cancellation/supersession/shutdown.
     services: Vec<ActiveTask>,
+    /// Running jobs carried over from the generation this run superseded.
+    /// They hold the stages that come after them in plan order and take
+    /// concurrency slots until they finish here.
+    carried: Vec<ActiveTask>,
+    /// Unstarted stages carried over likewise. They start once this run's
+    /// own stages and the carried jobs are done, as they would have there.
+    carried_stages: VecDeque<Stage>,
+    /// Generation the affected jobs of this run were cancelled for; their
+    /// snapshots name it.
+    handed_over_to: Option<u64>,
     stage_limit: usize,
     results: Vec<Result<(), String>>,
     outcomes: Vec<(usize, String, Option<String>, TaskOutcome)>,
     /// Terminal snapshots retain their configured position so every projection
     /// is deterministic when parallel jobs complete out of order.
     task_snapshots: Vec<TaskSnapshot>,
+    /// Queued task name -> the lock it is blocked on, as last reported.
+    waiting: BTreeMap<String, String>,
+    /// Finished task name -> its failure reason, until its snapshot is taken.
+    failure_reasons: BTreeMap<String, String>,
+    /// Finished task name -> its test report summary, likewise.
+    test_summaries: BTreeMap<String, crate::test_reports::TestSummary>,
+    /// Finished task name -> its step results, likewise.
+    step_results: BTreeMap<String, Vec<StepSnapshot>>,
     metadata: RunMetadata,
     superseded_by: Option<u64>,
     cancellation: CancellationToken,
@@ -537,6 +789,22 @@ impl Run {
     fn cancellation_requested(&self) -> bool {
         self.cancellation.is_cancelled()
     }
+
+    /// Whether no job is running or left to start.
+    fn is_drained(&self) -> bool {
+        self.active.is_empty()
+            && self.carried.is_empty()
+            && self.queued.is_empty()
+            && self.stages.is_empty()
+            && self.carried_stages.is_empty()
+    }
+
+    /// Whether a finished job failed; allowed failures do not count.
+    fn has_failed(&self) -> bool {
+        self.outcomes
+            .iter()
+            .any(|(_, _, _, outcome)| matches!(outcome, TaskOutcome::Failed { .. }))
+    }
 }
 
 enum TaskStep {
@@ -561,6 +829,8 @@ pub struct Executor {
     outputs: Option<Arc<OutputRegistry>>,
     /// Injected approval boundary; domain code never reads global stdin.
     approval: Arc<dyn RecoveryApproval>,
+    /// Where `jobs[].global_lock` files live (shared by every process).
+    global_lock_dir: std::path::PathBuf,
 }
 
 impl Executor {
@@ -585,6 +855,7 @@ impl Executor {
             verbose,
             outputs: None,
             approval: Arc::new(DenyRecoveryApproval),
+            global_lock_dir: crate::global_lock::lock_dir(),
         })
     }
 
@@ -613,6 +884,7 @@ impl Executor {
             verbose,
             outputs,
             approval: Arc::new(DenyRecoveryApproval),
+            global_lock_dir: crate::global_lock::lock_dir(),
         })
     }
 
@@ -622,6 +894,12 @@ impl Executor {
         self
     }
 
+    /// Moves the cross-process lock files; tests isolate them per fixture.
+    pub fn with_global_lock_dir(mut self, dir: std::path::PathBuf) -> Self {
+        self.global_lock_dir = dir;
+        self
+    }
+
     pub fn concurrency_limit(&self) -> usize {
         self.concurrency_limit
             .load(std::sync::atomic::Ordering::SeqCst)
@@ -676,10 +954,17 @@ impl Executor {
             active: vec![],
             pending_recoveries: vec![],
             services: vec![],
+            carried: vec![],
+            carried_stages: VecDeque::new(),
+            handed_over_to: None,
             stage_limit: 0,
             results: vec![],
             outcomes: vec![],
             task_snapshots: vec![],
+            waiting: BTreeMap::new(),
+            failure_reasons: BTreeMap::new(),
+            test_summaries: BTreeMap::new(),
+            step_results: BTreeMap::new(),
             metadata,
             superseded_by: None,
             cancellation: CancellationToken::new(),
@@ -694,7 +979,32 @@ impl Executor {
 
         loop {
             if run.active.is_empty() && run.queued.is_empty() {
+                // A stage waits for carried jobs that came before it, as it
+                // would have in the generation that started them.
+                if holds_next_stage(run) {
+                    self.advance_carried(run);
+                    if holds_next_stage(run) {
+                        self.advance_services(run);
+                        return Step::Running;
+                    }
+                    continue;
+                }
                 let Some(stage) = run.stages.pop_front() else {
+                    // Carried jobs finish into this generation before it
+                    // does.
+                    if !run.carried.is_empty() {
+                        self.advance_carried(run);
+                        if !run.carried.is_empty() || !run.services.is_empty() {
+                            self.advance_services(run);
+                            return Step::Running;
+                        }
+                        continue;
+                    }
+                    if !run.carried_stages.is_empty() {
+                        let mut carried = std::mem::take(&mut run.carried_stages);
+                        run.stages.append(&mut carried);
+                        continue;
+                    }
                     // TASK-0035: background services keep the generation
                     // alive (polled for restart/failure) until reaped.
                     if !run.services.is_empty() {
@@ -705,10 +1015,16 @@ impl Executor {
                 };
                 match stage {
                     Stage::Serial(task) => {
-                        run.stage_limit = 1;
-                        run.queued.push_back(task);
+                        if let Some(task) = self.admit(run, task) {
+                            run.stage_limit = 1;
+                            run.queued.push_back(task);
+                        }
                     }
                     Stage::Parallel { tasks, .. } => {
+                        let tasks: Vec<TaskPlan> = tasks
+                            .into_iter()
+                            .filter_map(|task| self.admit(run, task))
+                            .collect();
                         // TASK-0073: a per-generation sequential override
                         // caps this stage at one; otherwise the configured
                         // executor limit applies.
@@ -735,8 +1051,36 @@ impl Executor {
                     TaskStep::Running => {
                         // TASK-0035: a spawned-and-running service is moved
                         // to the background set so it never blocks later
-                        // stages; it is reaped at cancellation/shutdown.
+                        // stages; it is reaped at cancellation/shutdown. A
+                        // service with a readiness probe holds its stage
+                        // until the probe passes or times out.
                         if run.active[index].service {
+                            match self.poll_readiness(run.metadata.run_id, &mut run.active[index]) {
+                                readiness::Poll::Starting => {
+                                    index += 1;
+                                    continue;
+                                }
+                                readiness::Poll::Ready => {}
+                                readiness::Poll::Unready(reason) => {
+                                    let task = &mut run.active[index];
+                                    let failure = format!("Service {} was {}", task.name, reason);
+                                    task.failures.push(failure.clone());
+                                    task.failure_reason = Some(reason);
+                                    if !task.allow_failure {
+                                        run.results.push(Err(failure));
+                                    }
+                                    if self.fail_fast && !task.allow_failure {
+                                        let mut task = run.active.remove(index);
+                                        self.shutdown_task(&mut task);
+                                        self.record_task_outcome(run, task);
+                                        self.stop_after_failure(run);
+                                        if run.is_drained() {
+                                            return Step::Finished;
+                                        }
+                                        continue;
+                                    }
+                                }
+                            }
                             let service = run.active.remove(index);
                             run.services.push(service);
                             continue;
@@ -755,7 +1099,11 @@ impl Executor {
                         if !run.pending_recoveries.is_empty() && self.resolve_recoveries(run) {
                             return Step::Running;
                         }
-                        return Step::Finished;
+                        // `when: on_failure` and `when: always` jobs still run.
+                        if run.is_drained() {
+                            return Step::Finished;
+                        }
+                        task_finished = true;
                     }
                 }
             }
@@ -765,6 +1113,9 @@ impl Executor {
             if !run.services.is_empty() {
                 self.advance_services(run);
             }
+            if !run.carried.is_empty() {
+                self.advance_carried(run);
+            }
 
             if run.active.is_empty() && run.queued.is_empty() {
                 if !run.pending_recoveries.is_empty() {
@@ -791,13 +1142,280 @@ impl Executor {
         }
     }
 
+    /// Passes a task of a newly started stage through its `when` condition;
+    /// an `on_failure` job of a run without failures is recorded as skipped.
+    fn admit(&self, run: &mut Run, task: TaskPlan) -> Option<TaskPlan> {
+        if task.rule.when() != When::OnFailure || run.has_failed() {
+            return Some(task);
+        }
+        self.record_task_snapshot(
+            run,
+            task.position,
+            &task.name,
+            task.group_occurrence.as_deref(),
+            TaskState::Skipped,
+            None,
+        );
+        run.outcomes.push((
+            task.position,
+            task.name,
+            task.group_occurrence,
+            TaskOutcome::Skipped,
+        ));
+        None
+    }
+
+    /// Starts queued tasks in order while the stage has free slots. A task
+    /// whose resource lock is at capacity stays queued (reported once as
+    /// waiting) and later tasks may pass it. Carried jobs take slots of the
+    /// concurrency limit too.
     fn fill_available_slots(&self, run: &mut Run) {
-        while run.active.len() < run.stage_limit {
-            let Some(task) = run.queued.pop_front() else {
-                return;
-            };
-            run.active.push(task.into());
+        let limit = run
+            .metadata
+            .effective_concurrency
+            .unwrap_or_else(|| self.concurrency_limit());
+        let mut index = 0;
+        while run.active.len() < run.stage_limit
+            && run.active.len() + run.carried.len() < limit
+            && index < run.queued.len()
+        {
+            let mut global_lock = None;
+            let blocked = lock_conflict(run, run.queued[index].rule.locks()).or_else(|| match self
+                .acquire_global_lock(&run.queued[index])
+            {
+                Ok(lock) => {
+                    global_lock = lock;
+                    None
+                }
+                Err(reason) => Some(reason),
+            });
+            match blocked {
+                Some(reason) => {
+                    let task = &run.queued[index];
+                    if run.waiting.get(&task.name) != Some(&reason) {
+                        run.waiting.insert(task.name.clone(), reason.clone());
+                        self.emit_waiting(run, index, Some(reason));
+                    }
+                    index += 1;
+                }
+                None => {
+                    if run.waiting.remove(&run.queued[index].name).is_some() {
+                        self.emit_waiting(run, index, None);
+                    }
+                    let task = run.queued.remove(index).expect("index is in bounds");
+                    let mut task: ActiveTask = task.into();
+                    task.global_lock = global_lock;
+                    run.active.push(task);
+                }
+            }
+        }
+    }
+
+    /// Takes the task's cross-process lock without waiting; `Err` carries
+    /// the waiting reason, naming the holder when it is known.
+    fn acquire_global_lock(
+        &self,
+        task: &TaskPlan,
+    ) -> Result<Option<crate::global_lock::GlobalLock>, String> {
+        use crate::global_lock::{try_acquire, Acquire};
+        let Some(name) = task.rule.global_lock() else {
+            return Ok(None);
+        };
+        match try_acquire(&self.global_lock_dir, name, &task.name) {
+            Ok(Acquire::Held(lock)) => Ok(Some(lock)),
+            Ok(Acquire::Busy(Some(holder))) => {
+                Err(format!("global lock '{}' held by {}", name, holder))
+            }
+            Ok(Acquire::Busy(None)) => {
+                Err(format!("global lock '{}' held by another process", name))
+            }
+            Err(err) => Err(format!("global lock '{}' unavailable: {}", name, err)),
+        }
+    }
+
+    /// Polls a running service's readiness probe and reports its state:
+    /// `starting` once when probing begins, then `ready` or `unready`. A
+    /// service without a probe is ready as soon as it runs.
+    fn poll_readiness(&self, run_id: u64, task: &mut ActiveTask) -> readiness::Poll {
+        let poll = match task.readiness.as_mut() {
+            None => readiness::Poll::Ready,
+            Some(check) => {
+                let first = !check.started();
+                let poll = check.poll(
+                    self.clock.now(),
+                    readiness::Target {
+                        task: &task.name,
+                        context: &task.context,
+                        capture: task.capture.as_deref(),
+                        runner: self.runner.as_ref(),
+                    },
+                );
+                if poll == readiness::Poll::Starting {
+                    if first {
+                        self.emit_service_state(run_id, task, TaskState::Starting, None);
+                    }
+                    return poll;
+                }
+                poll
+            }
+        };
+        task.readiness = None;
+        let (state, reason) = match &poll {
+            readiness::Poll::Unready(reason) => (TaskState::Unready, Some(reason.clone())),
+            _ => (TaskState::Ready, None),
+        };
+        self.emit_service_state(run_id, task, state, reason);
+        poll
+    }
+
+    fn emit_service_state(
+        &self,
+        run_id: u64,
+        task: &ActiveTask,
+        state: TaskState,
+        reason: Option<String>,
+    ) {
+        self.events.emit(Event::ServiceState {
+            run_id,
+            task: Self::service_snapshot(task, state, reason),
+        });
+    }
+
+    fn service_snapshot(
+        task: &ActiveTask,
+        state: TaskState,
+        reason: Option<String>,
+    ) -> TaskSnapshot {
+        TaskSnapshot {
+            position: task.position,
+            id: task
+                .group_occurrence
+                .clone()
+                .unwrap_or_else(|| task.name.clone()),
+            name: task.name.clone(),
+            state,
+            duration_ms: None,
+            waiting: None,
+            failure_reason: reason,
+            restarts: Some(task.restarts),
+            health: task.last_health.clone(),
+            tests: None,
+            steps: vec![],
+            superseded_by: None,
+        }
+    }
+
+    /// The state a running service reports alongside restarts and health.
+    fn service_state(task: &ActiveTask) -> TaskState {
+        if task.readiness.as_ref().is_some_and(ReadinessCheck::started) {
+            TaskState::Starting
+        } else if task.failure_reason.is_some() {
+            TaskState::Unready
+        } else {
+            TaskState::Ready
+        }
+    }
+
+    /// Queues a service's respawn after an unexpected exit or failed health
+    /// checks, once its backoff elapses. Returns false when the restart
+    /// budget is spent. A service that ran for `reset_after` gets its budget
+    /// back first.
+    fn schedule_restart(
+        &self,
+        run_id: u64,
+        task: &mut ActiveTask,
+        cause: &str,
+        command: Option<String>,
+    ) -> bool {
+        if let (Some(reset_after), Some(spawned)) = (task.restart.reset_after, task.spawned_at) {
+            if self.clock.elapsed(spawned) >= reset_after {
+                task.restart_streak = 0;
+            }
+        }
+        if task.restart_streak >= task.restart.max {
+            return false;
+        }
+        task.restart_streak += 1;
+        task.restarts += 1;
+        let delay = task.restart.delay_for(task.restart_streak);
+        stdout::warn(&format!(
+            "service '{}' {}; restarting in {} ({} left)",
+            task.name,
+            cause,
+            crate::config::format_grace(delay),
+            task.restart.max - task.restart_streak
+        ));
+        // The service command was consumed at spawn; put it back so the
+        // next spawn respawns it.
+        if let Some(command) = command {
+            task.commands.push_front(CommandLine::Shell(command));
+        }
+        task.restart_at = Some(self.clock.now() + delay);
+        if let Some(health) = task.health.as_mut() {
+            health.reset();
+        }
+        self.emit_service_state(
+            run_id,
+            task,
+            Self::service_state(task),
+            task.failure_reason.clone(),
+        );
+        true
+    }
+
+    /// Runs a background service's due health check and reports a changed
+    /// result. Returns true once enough checks failed in a row that the
+    /// service should be restarted.
+    fn poll_health(&self, run_id: u64, task: &mut ActiveTask) -> bool {
+        let Some(health) = task.health.as_mut() else {
+            return false;
+        };
+        let Some(result) = health.poll(
+            self.clock.now(),
+            readiness::Target {
+                task: &task.name,
+                context: &task.context,
+                capture: task.capture.as_deref(),
+                runner: self.runner.as_ref(),
+            },
+        ) else {
+            return false;
+        };
+        let exhausted = health.exhausted();
+        if task.last_health.as_ref() != Some(&result) {
+            task.last_health = Some(result);
+            self.emit_service_state(
+                run_id,
+                task,
+                Self::service_state(task),
+                task.failure_reason.clone(),
+            );
         }
+        exhausted
+    }
+
+    fn emit_waiting(&self, run: &Run, index: usize, reason: Option<String>) {
+        let task = &run.queued[index];
+        self.events.emit(Event::TaskWaiting {
+            run_id: run.metadata.run_id,
+            task: TaskSnapshot {
+                position: task.position,
+                id: task
+                    .group_occurrence
+                    .clone()
+                    .unwrap_or_else(|| task.name.clone()),
+                name: task.name.clone(),
+                state: TaskState::Waiting,
+                duration_ms: None,
+                waiting: reason,
+                failure_reason: None,
+                restarts: None,
+                health: None,
+                tests: None,
+                steps: vec![],
+                superseded_by: None,
+            },
+        });
     }
 
     fn advance_task(
@@ -807,6 +1425,9 @@ impl Executor {
         run_id: u64,
         fail_fast: bool,
     ) -> TaskStep {
+        // An allowed failure is reported as a warning: it neither counts
+        // against the run nor stops it.
+        let fail_fast = fail_fast && !task.allow_failure;
         if !task.context_validated {
             task.context_validated = true;
             if let Some(cwd) = &task.context.cwd {
@@ -817,7 +1438,7 @@ impl Executor {
                         cwd.display()
                     );
                     task.failures.push(failure.clone());
-                    if !task.defer_failure {
+                    if !task.defer_failure && !task.allow_failure {
                         results.push(Err(failure));
                     }
                     task.commands.clear();
@@ -832,6 +1453,10 @@ impl Executor {
 
         loop {
             if task.child.is_none() {
+                if task.restart_at.is_some_and(|at| self.clock.now() < at) {
+                    return TaskStep::Running;
+                }
+                task.restart_at = None;
                 let Some(command) = task.commands.pop_front() else {
                     return TaskStep::Finished;
                 };
@@ -840,12 +1465,27 @@ impl Executor {
                 // Capture whenever a retention registry exists OR the task
                 // needs buffered output for its policy (TASK-0041):
                 // quiet/capture/show-on-failure hold output for retrieval or
-                // reveal even when no control surface is wired.
+                // reveal even when no control surface is wired. Timestamped
+                // jobs capture so elapsed time counts from the first command.
                 if task.capture.is_none()
                     && (self.outputs.is_some()
-                        || task.output != crate::rules::OutputPolicy::Inherit)
+                        || holds_output(task)
+                        || cmd::timestamps_for(&task.context) != crate::rules::Timestamps::None
+                        || !task.matchers.is_empty()
+                        || task.reports.tap
+                        || task
+                            .readiness
+                            .as_ref()
+                            .is_some_and(ReadinessCheck::reads_output))
                 {
-                    task.capture = Some(Arc::new(CaptureHandle::new()));
+                    task.capture = Some(Arc::new(
+                        if task.output == crate::rules::OutputPolicy::Grouped && holds_output(task)
+                        {
+                            CaptureHandle::grouped()
+                        } else {
+                            CaptureHandle::new()
+                        },
+                    ));
                 }
                 match self.runner.spawn(
                     &task.name,
@@ -858,13 +1498,16 @@ impl Executor {
                     task.group_occurrence.is_some().then(|| task.name.clone()),
                     // TASK-0041: quiet/capture/show-on-failure suppress live
                     // output; inherit streams it.
-                    !matches!(task.output, crate::rules::OutputPolicy::Inherit),
+                    holds_output(task),
                 ) {
                     Ok(child) => {
                         task.child = Some(child);
                         task.command_index += 1;
+                        task.spawned_at = Some(self.clock.now());
+                        task.begin_step(self.clock.now());
                         if task.started.is_none() {
-                            task.started = Some(self.clock.now());
+                            task.started = task.spawned_at;
+                            task.started_wall = Some(SystemTime::now());
                         }
                         if self.verbose {
                             diagnostics::debug(&diagnostics::Record {
@@ -884,11 +1527,16 @@ impl Executor {
                     Err(err) => {
                         let failure = format!("Command {} failed to start: {}", display, err);
                         stdout::error(&failure);
+                        task.current_command = None;
+                        if task.tolerates_step_failure() {
+                            task.end_step(display, TaskState::Warning, None, self.clock.as_ref());
+                            continue;
+                        }
+                        task.end_step(display, TaskState::Failed, None, self.clock.as_ref());
                         task.failures.push(failure.clone());
-                        if !task.defer_failure {
+                        if !task.defer_failure && !task.allow_failure {
                             results.push(Err(failure));
                         }
-                        task.current_command = None;
                         if fail_fast {
                             return TaskStep::FailedFast;
                         }
@@ -916,44 +1564,67 @@ impl Executor {
                     // finishes the generation — it returns Running until
                     // superseded or shut down.
                     if task.service {
-                        if status.success() {
+                        if task.exited_successfully(&status) {
                             // Deliberate stop: the service is done for this
                             // generation (e.g. it exited on its own request).
                             results.push(Ok(()));
                             return TaskStep::Finished;
                         }
-                        if task.service_restarts_left > 0 {
-                            task.service_restarts_left -= 1;
-                            stdout::warn(&format!(
-                                "service '{}' exited with {}; restarting ({} left)",
-                                task.name, status, task.service_restarts_left
-                            ));
-                            self.clock
-                                .sleep(Duration::from_millis(SERVICE_RESTART_BACKOFF_MS));
-                            // The service command was consumed at spawn; put it
-                            // back so the next loop iteration respawns it.
-                            if let Some(cmd) = &service_command {
-                                task.commands.push_front(CommandLine::Shell(cmd.clone()));
-                            }
+                        if self.schedule_restart(
+                            run_id,
+                            task,
+                            &format!("exited with {}", status),
+                            service_command,
+                        ) {
                             continue;
                         }
                         let failure = format!(
                             "Service {} has failed after {} restarts",
-                            task.name, SERVICE_MAX_RESTARTS
+                            task.name, task.restart_streak
                         );
                         task.failures.push(failure.clone());
-                        results.push(Err(failure));
+                        if !task.allow_failure {
+                            results.push(Err(failure));
+                        }
                         return TaskStep::Finished;
                     }
 
-                    if status.success() {
+                    if task.exited_successfully(&status) {
+                        task.end_step(
+                            display,
+                            TaskState::Passed,
+                            status.code(),
+                            self.clock.as_ref(),
+                        );
+                        results.push(Ok(()));
+                        continue;
+                    }
+                    if task.tolerates_step_failure() {
+                        task.end_step(
+                            display,
+                            TaskState::Warning,
+                            status.code(),
+                            self.clock.as_ref(),
+                        );
                         results.push(Ok(()));
                         continue;
                     }
+                    task.end_step(
+                        display.clone(),
+                        TaskState::Failed,
+                        status.code(),
+                        self.clock.as_ref(),
+                    );
 
-                    let failure = format!("Command {} has failed with {}", display, status);
+                    let mut failure = format!("Command {} has failed with {}", display, status);
+                    if let Some(reason) = crate::cmd::limit_violation(&task.context.limits, &status)
+                        .filter(|_| !crate::process_owner::stopping())
+                    {
+                        failure = format!("{} ({})", failure, reason);
+                        task.failure_reason = Some(reason);
+                    }
                     task.failures.push(failure.clone());
-                    if !task.defer_failure {
+                    if !task.defer_failure && !task.allow_failure {
                         results.push(Err(failure));
                     }
                     if fail_fast {
@@ -963,9 +1634,15 @@ impl Executor {
                 Err(err) => {
                     task.child = None;
                     task.current_command = None;
+                    task.end_step(
+                        display.clone(),
+                        TaskState::Failed,
+                        None,
+                        self.clock.as_ref(),
+                    );
                     let failure = format!("Command {} has errored with {}", display, err);
                     task.failures.push(failure.clone());
-                    if !task.defer_failure {
+                    if !task.defer_failure && !task.allow_failure {
                         results.push(Err(failure));
                     }
                     if fail_fast {
@@ -996,6 +1673,13 @@ impl Executor {
             name: name.to_owned(),
             state,
             duration_ms,
+            waiting: run.waiting.remove(name),
+            failure_reason: run.failure_reasons.remove(name),
+            restarts: None,
+            health: None,
+            tests: run.test_summaries.remove(name),
+            steps: run.step_results.remove(name).unwrap_or_default(),
+            superseded_by: run.handed_over_to.filter(|_| state == TaskState::Cancelled),
         };
         run.task_snapshots.push(task.clone());
         self.events.emit(Event::TaskTerminal {
@@ -1032,6 +1716,53 @@ impl Executor {
         }
     }
 
+    /// Prints a grouped job's held output as one block under a header when
+    /// the job leaves the run, so parallel jobs never interleave. The
+    /// retained capture is untouched.
+    fn print_grouped(task: &ActiveTask, state: TaskState, duration_ms: Option<u64>) {
+        let Some(capture) = task
+            .capture
+            .as_ref()
+            .filter(|_| task.output == crate::rules::OutputPolicy::Grouped && holds_output(task))
+        else {
+            return;
+        };
+        let header = format!(
+            "--- {} ({}, {}) ---",
+            task.name,
+            state.label(),
+            duration_ms
+                .map(stdout::format_job_duration)
+                .unwrap_or_else(|| "-".to_owned())
+        );
+        println!("{}", header);
+        logging::log_line(&header);
+        let mut open_line = false;
+        let dropped = capture.replay(|line, is_stderr| {
+            let rendered = String::from_utf8_lossy(line);
+            if is_stderr {
+                eprint!("{}", rendered);
+            } else {
+                print!("{}", rendered);
+            }
+            logging::log_plain(&rendered);
+            open_line = !line.ends_with(b"\n");
+        });
+        if open_line {
+            println!();
+        }
+        let _ = std::io::Write::flush(&mut std::io::stdout());
+        let _ = std::io::Write::flush(&mut std::io::stderr());
+        if dropped > 0 {
+            stdout::warn(&format!(
+                "job '{}' printed more than {} bytes; the last {} bytes were dropped",
+                task.name,
+                crate::cmd::GROUPED_MEMORY_BYTES as u64 + crate::cmd::GROUPED_SPILL_BYTES,
+                dropped
+            ));
+        }
+    }
+
     fn defer_or_record(&self, run: &mut Run, task: ActiveTask) {
         if !task.failures.is_empty() && task.recovery_commands.is_some() {
             self.events.emit(Event::RecoveryPhase {
@@ -1142,11 +1873,13 @@ impl Executor {
                     phase: "approval_decided".to_owned(),
                     outcome: Some(reason.to_owned()),
                 });
-                run.results.push(Err(task
-                    .failures
-                    .first()
-                    .cloned()
-                    .unwrap_or_else(|| format!("Job '{}' failed", task.name))));
+                if !task.allow_failure {
+                    run.results.push(Err(task
+                        .failures
+                        .first()
+                        .cloned()
+                        .unwrap_or_else(|| format!("Job '{}' failed", task.name))));
+                }
                 self.record_task_outcome(run, task);
             }
             return false;
@@ -1171,6 +1904,10 @@ impl Executor {
                 phase: "recovery_started".to_owned(),
                 outcome: None,
             });
+            // Steps describe the job's own commands: recovery commands
+            // neither replace them nor continue on error.
+            let original_steps = std::mem::take(&mut task.steps);
+            let continue_on_error = std::mem::take(&mut task.continue_on_error);
             task.commands = recovery_commands;
             task.failures.clear();
             task.current_command = None;
@@ -1202,18 +1939,23 @@ impl Executor {
                 let mut failures = original_failures;
                 failures.extend(task.failures.clone());
                 task.failures = failures;
-                run.results.push(Err(task
-                    .failures
-                    .last()
-                    .cloned()
-                    .unwrap_or_else(|| format!("Recovery failed for job '{}'", task.name))));
-                self.record_task_outcome(run, task);
-                for remaining in remaining {
-                    run.results.push(Err(remaining
+                task.steps = original_steps;
+                if !task.allow_failure {
+                    run.results.push(Err(task
                         .failures
-                        .first()
+                        .last()
                         .cloned()
-                        .unwrap_or_else(|| format!("Job '{}' failed", remaining.name))));
+                        .unwrap_or_else(|| format!("Recovery failed for job '{}'", task.name))));
+                }
+                self.record_task_outcome(run, task);
+                for remaining in remaining {
+                    if !remaining.allow_failure {
+                        run.results.push(Err(remaining
+                            .failures
+                            .first()
+                            .cloned()
+                            .unwrap_or_else(|| format!("Job '{}' failed", remaining.name))));
+                    }
                     self.record_task_outcome(run, remaining);
                 }
                 return false;
@@ -1226,6 +1968,8 @@ impl Executor {
                 outcome: None,
             });
             task.commands = task.original_commands.clone().into();
+            task.steps.clear();
+            task.continue_on_error = continue_on_error;
             task.failures.clear();
             task.current_command = None;
             task.child = None;
@@ -1264,12 +2008,12 @@ impl Executor {
                     .to_owned(),
                 ),
             });
-            if verification_failed {
+            if verification_failed && !task.allow_failure {
                 run.results
                     .push(Err(task.failures.last().cloned().unwrap_or_else(|| {
                         format!("Verification failed for job '{}'", task.name)
                     })));
-            } else {
+            } else if !verification_failed {
                 run.results.push(Ok(()));
             }
             self.record_task_outcome(run, task);
@@ -1279,23 +2023,77 @@ impl Executor {
 
     fn record_task_outcome(&self, run: &mut Run, task: ActiveTask) {
         self.reveal_on_failure(&task, &task.failures);
-        if let (Some(outputs), Some(capture)) = (&self.outputs, &task.capture) {
+        let duration_ms = task
+            .started
+            .map(|started| self.clock.elapsed(started).as_millis() as u64);
+        let state = if task.failures.is_empty() {
+            TaskState::Passed
+        } else {
+            task.failed_outcome().0
+        };
+        Self::print_grouped(&task, state, duration_ms);
+        let data = task.capture.as_ref().map(|capture| capture.finish());
+        let (diagnostics, truncated) = data
+            .as_ref()
+            .map(|data| crate::matchers::extract(&task.matchers, &task.name, data))
+            .unwrap_or_default();
+        let tests = task
+            .started_wall
+            .filter(|_| !task.reports.is_empty())
+            .and_then(|since| {
+                let stdout = data
+                    .as_ref()
+                    .map(|data| data.stdout.bytes())
+                    .unwrap_or_default();
+                crate::test_reports::collect(
+                    &task.reports,
+                    task.context.cwd.as_deref(),
+                    since,
+                    stdout,
+                )
+            });
+        let steps = task.step_snapshots();
+        if let (Some(outputs), Some(data)) = (&self.outputs, data) {
             outputs.record(
                 run.metadata.run_id,
                 task.name.clone(),
-                capture.finish(),
+                data,
                 run.metadata.revision,
                 run.metadata.revision_hash.clone(),
             );
+            outputs.record_steps(run.metadata.run_id, &task.name, steps.clone());
+            outputs.record_diagnostics(
+                run.metadata.run_id,
+                &task.name,
+                diagnostics.clone(),
+                truncated,
+            );
+            if let Some(tests) = &tests {
+                outputs.record_tests(run.metadata.run_id, &task.name, tests.clone());
+            }
+        }
+        if let Some(tests) = tests {
+            run.test_summaries.insert(task.name.clone(), tests);
+        }
+        if !steps.is_empty() {
+            run.step_results.insert(task.name.clone(), steps);
+        }
+        if !diagnostics.is_empty() {
+            self.events.emit(Event::Diagnostics {
+                run_id: run.metadata.run_id,
+                task: task.name.clone(),
+                diagnostics,
+                truncated,
+            });
         }
-        let duration_ms = task
-            .started
-            .map(|started| self.clock.elapsed(started).as_millis() as u64);
         let (state, outcome) = if task.failures.is_empty() {
             (TaskState::Passed, TaskOutcome::Passed)
         } else {
-            let failures = task.failures.clone();
-            (TaskState::Failed, TaskOutcome::Failed { failures })
+            if let Some(reason) = &task.failure_reason {
+                run.failure_reasons
+                    .insert(task.name.clone(), reason.clone());
+            }
+            task.failed_outcome()
         };
         self.record_task_snapshot(
             run,
@@ -1313,29 +2111,19 @@ impl Executor {
         ));
     }
 
+    /// Stops a run after a fail-fast failure: running jobs are cancelled and
+    /// unstarted ones skipped, except `when: on_failure` and `when: always`
+    /// jobs, which keep running or stay planned in their stages.
     fn stop_after_failure(&self, run: &mut Run) {
-        for mut task in std::mem::take(&mut run.active) {
+        let carried = std::mem::take(&mut run.carried);
+        let (kept, cancelled): (Vec<ActiveTask>, Vec<ActiveTask>) = std::mem::take(&mut run.active)
+            .into_iter()
+            .chain(carried)
+            .partition(|task| task.when != When::OnSuccess);
+        run.active = kept;
+        for mut task in cancelled {
             self.shutdown_task(&mut task);
-            if let (Some(outputs), Some(capture)) = (&self.outputs, &task.capture) {
-                outputs.record(
-                    run.metadata.run_id,
-                    task.name.clone(),
-                    capture.finish(),
-                    run.metadata.revision,
-                    run.metadata.revision_hash.clone(),
-                );
-            }
-            let duration_ms = task
-                .started
-                .map(|started| self.clock.elapsed(started).as_millis() as u64);
-            self.record_task_snapshot(
-                run,
-                task.position,
-                &task.name,
-                task.group_occurrence.as_deref(),
-                TaskState::Cancelled,
-                duration_ms,
-            );
+            self.record_cancelled(run, &task);
             run.outcomes.push((
                 task.position,
                 task.name,
@@ -1343,24 +2131,20 @@ impl Executor {
                 TaskOutcome::Cancelled,
             ));
         }
-        for task in std::mem::take(&mut run.queued) {
-            self.record_task_snapshot(
-                run,
-                task.position,
-                &task.name,
-                task.group_occurrence.as_deref(),
-                TaskState::Cancelled,
-                None,
-            );
-            run.outcomes.push((
-                task.position,
-                task.name,
-                task.group_occurrence.clone(),
-                TaskOutcome::Skipped,
-            ));
-        }
-        for stage in std::mem::take(&mut run.stages) {
-            for task in stage_tasks(stage) {
+        // The current stage's queue first, then the stages not started yet.
+        let carried_stages = std::mem::take(&mut run.carried_stages);
+        let mut planned = vec![Vec::from(std::mem::take(&mut run.queued))];
+        planned.extend(
+            std::mem::take(&mut run.stages)
+                .into_iter()
+                .chain(carried_stages)
+                .map(stage_tasks),
+        );
+        for (index, tasks) in planned.into_iter().enumerate() {
+            let (tasks, skipped): (Vec<TaskPlan>, Vec<TaskPlan>) = tasks
+                .into_iter()
+                .partition(|task| task.rule.when() != When::OnSuccess);
+            for task in skipped {
                 self.record_task_snapshot(
                     run,
                     task.position,
@@ -1376,20 +2160,33 @@ impl Executor {
                     TaskOutcome::Skipped,
                 ));
             }
+            if index == 0 {
+                run.queued = tasks.into();
+            } else if let Some(stage) = stage_from_tasks(tasks) {
+                run.stages.push_back(stage);
+            }
         }
     }
 
     /// Polls background services (TASK-0035): a service that exited
-    /// unexpectedly restarts with a bounded attempt count; exceeding the
-    /// bound records a failure. Deliberate zero-exit stops remove the
-    /// service from the background set.
+    /// unexpectedly or failed its health checks restarts under its restart
+    /// policy; exceeding the budget records a failure. Deliberate zero-exit
+    /// stops remove the service from the background set.
     fn advance_services(&self, run: &mut Run) {
+        let run_id = run.metadata.run_id;
         let mut index = 0;
         while index < run.services.len() {
             let service = &mut run.services[index];
+            self.poll_reload(service);
             // Respawn a restarted service whose child was reaped and whose
-            // command was queued for the next attempt.
+            // command was queued for the next attempt, once its backoff
+            // elapsed.
             if service.child.is_none() {
+                if service.restart_at.is_some_and(|at| self.clock.now() < at) {
+                    index += 1;
+                    continue;
+                }
+                service.restart_at = None;
                 let Some(command) = service.commands.pop_front() else {
                     index += 1;
                     continue;
@@ -1405,16 +2202,17 @@ impl Executor {
                         .group_occurrence
                         .is_some()
                         .then(|| service.name.clone()),
-                    !matches!(service.output, crate::rules::OutputPolicy::Inherit),
+                    holds_output(service),
                 ) {
                     Ok(child) => {
                         service.child = Some(child);
+                        service.spawned_at = Some(self.clock.now());
                         if service.started.is_none() {
-                            service.started = Some(self.clock.now());
+                            service.started = service.spawned_at;
                         }
                         if self.verbose {
                             diagnostics::debug(&diagnostics::Record {
-                                generation: Some(run.metadata.run_id),
+                                generation: Some(run_id),
                                 command_position: Some((1, 1)),
                                 state: Some("started"),
                                 command: Some(display.clone()),
@@ -1424,28 +2222,7 @@ impl Executor {
                     }
                     Err(err) => {
                         let failure = format!("Service {} respawn failed: {}", service.name, err);
-                        service.failures.push(failure.clone());
-                        run.results.push(Err(failure));
-                        let done = run.services.remove(index);
-                        let duration_ms = done
-                            .started
-                            .map(|started| self.clock.elapsed(started).as_millis() as u64);
-                        self.record_task_snapshot(
-                            run,
-                            done.position,
-                            &done.name,
-                            done.group_occurrence.as_deref(),
-                            TaskState::Failed,
-                            duration_ms,
-                        );
-                        run.outcomes.push((
-                            done.position,
-                            done.name.clone(),
-                            done.group_occurrence.clone(),
-                            TaskOutcome::Failed {
-                                failures: done.failures.clone(),
-                            },
-                        ));
+                        self.fail_service(run, index, failure);
                         continue;
                     }
                 }
@@ -1454,12 +2231,34 @@ impl Executor {
             }
             let child = service.child.as_mut().expect("child present");
             match child.try_wait() {
-                Ok(None) => index += 1,
+                Ok(None) => {
+                    if !self.poll_health(run_id, service) {
+                        index += 1;
+                        continue;
+                    }
+                    // A hung-but-alive service: stop it and restart it like
+                    // one that exited.
+                    let command = service.current_command.take();
+                    let cause = format!(
+                        "is unhealthy ({})",
+                        service.last_health.as_deref().unwrap_or("failing")
+                    );
+                    self.shutdown_task(service);
+                    if self.schedule_restart(run_id, service, &cause, command) {
+                        index += 1;
+                        continue;
+                    }
+                    let failure = format!(
+                        "Service {} has failed after {} restarts",
+                        service.name, service.restart_streak
+                    );
+                    self.fail_service(run, index, failure);
+                }
                 Ok(Some(status)) => {
                     service.child = None;
                     let command = service.current_command.clone();
                     service.current_command = None;
-                    if status.success() {
+                    if service.exited_successfully(&status) {
                         // Deliberate stop: remove from background.
                         let done = run.services.remove(index);
                         let duration_ms = done
@@ -1481,51 +2280,311 @@ impl Executor {
                         ));
                         continue;
                     }
-                    if service.service_restarts_left > 0 {
-                        service.service_restarts_left -= 1;
-                        stdout::warn(&format!(
-                            "service '{}' exited with {}; restarting ({} left)",
-                            service.name, status, service.service_restarts_left
-                        ));
-                        if let Some(cmd) = command {
-                            service.commands.push_front(CommandLine::Shell(cmd));
-                        }
+                    if self.schedule_restart(
+                        run_id,
+                        service,
+                        &format!("exited with {}", status),
+                        command,
+                    ) {
                         index += 1;
                         continue;
                     }
                     let failure = format!(
                         "Service {} has failed after {} restarts",
-                        service.name, SERVICE_MAX_RESTARTS
-                    );
-                    service.failures.push(failure.clone());
-                    run.results.push(Err(failure));
-                    let done = run.services.remove(index);
-                    let duration_ms = done
-                        .started
-                        .map(|started| self.clock.elapsed(started).as_millis() as u64);
-                    self.record_task_snapshot(
-                        run,
-                        done.position,
-                        &done.name,
-                        done.group_occurrence.as_deref(),
-                        TaskState::Failed,
-                        duration_ms,
+                        service.name, service.restart_streak
                     );
-                    run.outcomes.push((
-                        done.position,
-                        done.name.clone(),
-                        done.group_occurrence.clone(),
-                        TaskOutcome::Failed {
-                            failures: done.failures.clone(),
-                        },
-                    ));
-                    continue;
+                    self.fail_service(run, index, failure);
                 }
                 Err(_) => index += 1,
             }
         }
     }
 
+    /// Reaps a finished `on_change` reload command; a failed one is only
+    /// reported; the service keeps running.
+    fn poll_reload(&self, service: &mut ActiveTask) {
+        let Some(reload) = service.reload.as_mut() else {
+            return;
+        };
+        match reload.try_wait() {
+            Ok(None) => return,
+            Ok(Some(status)) if !status.success() => stdout::warn(&format!(
+                "service '{}' reload command failed with {}",
+                service.name, status
+            )),
+            Ok(Some(_)) => {}
+            Err(err) => stdout::warn(&format!(
+                "service '{}' reload command errored with {}",
+                service.name, err
+            )),
+        }
+        service.reload = None;
+    }
+
+    /// Takes the running services of a generation being superseded by
+    /// `plan` whose `on_change` keeps them up, and applies that action. The
+    /// caller hands them to the superseding generation with
+    /// [`Executor::start_with_services`]; every other service is stopped by
+    /// the following [`Executor::cancel`].
+    pub fn keep_services(&self, run: &mut Run, plan: &RunPlan, run_id: u64) -> KeptServices {
+        let planned = plan_services(plan);
+        let mut kept = KeptServices::default();
+        let mut index = 0;
+        while index < run.services.len() {
+            let service = &run.services[index];
+            if service.on_change == crate::rules::OnChange::Restart
+                || !planned.contains(&service.name.as_str())
+            {
+                index += 1;
+                continue;
+            }
+            let mut service = run.services.remove(index);
+            let action = self.apply_on_change(&mut service);
+            self.events.emit(Event::ServiceAction {
+                run_id,
+                task: Self::service_snapshot(
+                    &service,
+                    Self::service_state(&service),
+                    service.failure_reason.clone(),
+                ),
+                action,
+            });
+            kept.0.push(service);
+        }
+        kept
+    }
+
+    /// Delivers a kept service's `on_change` action; returns its label.
+    fn apply_on_change(&self, service: &mut ActiveTask) -> String {
+        match service.on_change.clone() {
+            crate::rules::OnChange::Restart => "restart".to_owned(),
+            crate::rules::OnChange::Ignore => "ignore".to_owned(),
+            crate::rules::OnChange::Signal(signal) => {
+                // A service waiting out its restart backoff picks the change
+                // up when it respawns.
+                if let Some(child) = service.child.as_mut() {
+                    if let Err(err) = child.signal(signal) {
+                        stdout::warn(&format!(
+                            "service '{}' could not be sent {}: {}",
+                            service.name,
+                            signal.as_str(),
+                            err
+                        ));
+                    }
+                }
+                format!("signal {}", signal.as_str())
+            }
+            crate::rules::OnChange::Command(command) => {
+                if service.reload.is_some() {
+                    stdout::warn(&format!(
+                        "service '{}' reload command is still running; skipping this change",
+                        service.name
+                    ));
+                } else {
+                    // The reload command runs with the service's cwd and
+                    // environment, and its output follows the service's
+                    // output policy.
+                    let context = TaskContext {
+                        cwd: service.context.cwd.clone(),
+                        environment: service.context.environment.clone(),
+                        ..TaskContext::default()
+                    };
+                    match self.runner.spawn(
+                        &service.name,
+                        &CommandLine::Shell(command.clone()),
+                        &context,
+                        service.capture.clone(),
+                        service
+                            .group_occurrence
+                            .is_some()
+                            .then(|| service.name.clone()),
+                        holds_output(service),
+                    ) {
+                        Ok(child) => service.reload = Some(child),
+                        Err(err) => stdout::warn(&format!(
+                            "service '{}' reload command failed to start: {}",
+                            service.name, err
+                        )),
+                    }
+                }
+                format!("command {}", command)
+            }
+        }
+    }
+
+    /// Starts a generation that takes over services kept by
+    /// [`Executor::keep_services`]: their tasks are dropped from `plan` and
+    /// they keep running in the new generation. A kept service the final
+    /// plan no longer includes (a later change in the same burst superseded
+    /// the one that matched it) is stopped.
+    pub fn start_with_services(
+        &self,
+        metadata: RunMetadata,
+        mut plan: RunPlan,
+        kept: KeptServices,
+    ) -> Run {
+        let planned = plan_services(&plan);
+        let (adopted, stopped): (Vec<ActiveTask>, Vec<ActiveTask>) = kept
+            .0
+            .into_iter()
+            .partition(|service| planned.contains(&service.name.as_str()));
+        self.stop_services(KeptServices(stopped));
+        for stage in &mut plan.stages {
+            if let Stage::Parallel { tasks, .. } = stage {
+                tasks.retain(|task| !adopted.iter().any(|service| service.name == task.name));
+            }
+        }
+        plan.stages.retain(|stage| match stage {
+            Stage::Serial(task) => !adopted.iter().any(|service| service.name == task.name),
+            Stage::Parallel { tasks, .. } => !tasks.is_empty(),
+        });
+        let mut run = self.start(metadata, plan);
+        for service in adopted {
+            self.emit_service_state(
+                run.metadata.run_id,
+                &service,
+                Self::service_state(&service),
+                service.failure_reason.clone(),
+            );
+            run.services.push(service);
+        }
+        run
+    }
+
+    /// Stops kept services no generation took over.
+    pub fn stop_services(&self, kept: KeptServices) {
+        for mut service in kept.0 {
+            self.shutdown_task(&mut service);
+        }
+    }
+
+    /// `--on-busy restart-affected`: takes the jobs of `run` that the
+    /// superseding `plan` does not schedule, running or not started yet, so
+    /// they finish in generation `run_id`. Cancelling `run` afterwards stops
+    /// only the affected jobs, each naming `run_id` as its `supersededBy`.
+    pub fn carry_unaffected(&self, run: &mut Run, plan: &RunPlan, run_id: u64) -> CarriedTasks {
+        let planned: Vec<&str> = planned_tasks(&plan.stages)
+            .map(|task| task.name.as_str())
+            .collect();
+        let affects = |name: &str| planned.contains(&name);
+        let carried = std::mem::take(&mut run.carried);
+        let (affected, running): (Vec<ActiveTask>, Vec<ActiveTask>) =
+            std::mem::take(&mut run.active)
+                .into_iter()
+                .chain(carried)
+                .partition(|task| affects(&task.name));
+        run.active = affected;
+        // The current stage's queue first, then the stages not started yet.
+        let carried_stages = std::mem::take(&mut run.carried_stages);
+        let mut unstarted = vec![Vec::from(std::mem::take(&mut run.queued))];
+        unstarted.extend(
+            std::mem::take(&mut run.stages)
+                .into_iter()
+                .chain(carried_stages)
+                .map(stage_tasks),
+        );
+        let mut stages = vec![];
+        for (index, tasks) in unstarted.into_iter().enumerate() {
+            let (affected, unaffected): (Vec<TaskPlan>, Vec<TaskPlan>) =
+                tasks.into_iter().partition(|task| affects(&task.name));
+            if index == 0 {
+                run.queued = affected.into();
+            } else if let Some(stage) = stage_from_tasks(affected) {
+                run.stages.push_back(stage);
+            }
+            stages.extend(stage_from_tasks(unaffected));
+        }
+        run.handed_over_to = Some(run_id);
+        CarriedTasks { running, stages }
+    }
+
+    /// Hands jobs carried over from a superseded generation to `run`. One
+    /// that `run` schedules again is stopped, or dropped when not started:
+    /// its new run replaces it.
+    pub fn adopt(&self, run: &mut Run, carried: CarriedTasks) {
+        let planned: Vec<String> = planned_tasks(&run.stages)
+            .map(|task| task.name.clone())
+            .collect();
+        for mut task in carried.running {
+            if planned.contains(&task.name) {
+                self.shutdown_task(&mut task);
+                continue;
+            }
+            run.carried.push(task);
+        }
+        for stage in carried.stages {
+            let tasks = stage_tasks(stage)
+                .into_iter()
+                .filter(|task| !planned.contains(&task.name))
+                .collect();
+            run.carried_stages.extend(stage_from_tasks(tasks));
+        }
+    }
+
+    /// Stops carried jobs no generation took over.
+    pub fn stop_carried(&self, carried: CarriedTasks) {
+        for mut task in carried.running {
+            self.shutdown_task(&mut task);
+        }
+    }
+
+    /// Polls the jobs carried over from a superseded generation. They record
+    /// their outcome in this run, and a failure is final: recovery belongs to
+    /// the generation that started them.
+    fn advance_carried(&self, run: &mut Run) {
+        let mut index = 0;
+        while index < run.carried.len() {
+            let step = self.advance_task(
+                &mut run.carried[index],
+                &mut run.results,
+                run.metadata.run_id,
+                self.fail_fast,
+            );
+            if matches!(step, TaskStep::Running) {
+                index += 1;
+                continue;
+            }
+            let task = run.carried.remove(index);
+            if task.defer_failure && !task.allow_failure {
+                if let Some(failure) = task.failures.first() {
+                    run.results.push(Err(failure.clone()));
+                }
+            }
+            self.record_task_outcome(run, task);
+            if matches!(step, TaskStep::FailedFast) {
+                self.stop_after_failure(run);
+            }
+        }
+    }
+
+    /// Removes a background service that cannot be kept running and records
+    /// it as failed.
+    fn fail_service(&self, run: &mut Run, index: usize, failure: String) {
+        let mut done = run.services.remove(index);
+        done.failures.push(failure.clone());
+        if !done.allow_failure {
+            run.results.push(Err(failure));
+        }
+        let duration_ms = done
+            .started
+            .map(|started| self.clock.elapsed(started).as_millis() as u64);
+        let (state, outcome) = done.failed_outcome();
+        self.record_task_snapshot(
+            run,
+            done.position,
+            &done.name,
+            done.group_occurrence.as_deref(),
+            state,
+            duration_ms,
+        );
+        run.outcomes.push((
+            done.position,
+            done.name.clone(),
+            done.group_occurrence.clone(),
+            outcome,
+        ));
+    }
+
     /// Runs the applicable terminal hook once (TASK-0040): success hook on
     /// pass, failure hook on fail. Hook failure never changes the run
     /// outcome; it is surfaced via a warning for loop diagnosis. The hook is
@@ -1631,36 +2690,42 @@ impl Executor {
     /// Cancels the run and records which generation superseded it, when any.
     /// The replacement relation (contract §1) is carried on the Cancelled
     /// event so superseded generations are never reported as passed/failed.
-    /// Cancels the run and records which generation superseded it, when any.
-    /// Returns the disposition: graceful when every active child terminated
-    /// after the initial signal, escalated when any child was force-killed.
+    /// Its `when: always` jobs run before this returns, within
+    /// [`ALWAYS_TIMEOUT`]. Returns the disposition: graceful when every
+    /// active child terminated after the initial signal, escalated when any
+    /// child was force-killed.
     pub fn cancel(&self, run: &mut Run, superseded_by: Option<u64>) -> CancelDisposition {
+        self.cancel_run(run, superseded_by, true)
+    }
+
+    /// Cancels a run that a newly scheduled one replaces. Unlike [`cancel`],
+    /// its `when: always` jobs are reported cancelled rather than run: the
+    /// replacing run reaches its own, and a save never waits on cleanup.
+    ///
+    /// [`cancel`]: Executor::cancel
+    pub fn replace(&self, run: &mut Run, superseded_by: Option<u64>) -> CancelDisposition {
+        self.cancel_run(run, superseded_by, false)
+    }
+
+    fn cancel_run(
+        &self,
+        run: &mut Run,
+        superseded_by: Option<u64>,
+        run_always: bool,
+    ) -> CancelDisposition {
         run.superseded_by = superseded_by;
         let mut escalated = false;
-        for mut task in std::mem::take(&mut run.active) {
+        let carried = std::mem::take(&mut run.carried);
+        let (mut always, cancelled): (Vec<ActiveTask>, Vec<ActiveTask>) =
+            std::mem::take(&mut run.active)
+                .into_iter()
+                .chain(carried)
+                .partition(|task| run_always && task.when == When::Always);
+        for mut task in cancelled {
             if self.shutdown_task(&mut task) {
                 escalated = true;
             }
-            if let (Some(outputs), Some(capture)) = (&self.outputs, &task.capture) {
-                outputs.record(
-                    run.metadata.run_id,
-                    task.name.clone(),
-                    capture.finish(),
-                    run.metadata.revision,
-                    run.metadata.revision_hash.clone(),
-                );
-            }
-            let duration_ms = task
-                .started
-                .map(|started| self.clock.elapsed(started).as_millis() as u64);
-            self.record_task_snapshot(
-                run,
-                task.position,
-                &task.name,
-                task.group_occurrence.as_deref(),
-                TaskState::Cancelled,
-                duration_ms,
-            );
+            self.record_cancelled(run, &task);
         }
         for mut task in std::mem::take(&mut run.services) {
             if self.shutdown_task(&mut task) {
@@ -1701,7 +2766,17 @@ impl Executor {
                     .map(|started| self.clock.elapsed(started).as_millis() as u64),
             );
         }
-        for task in std::mem::take(&mut run.queued) {
+        let queued: Vec<TaskPlan> = std::mem::take(&mut run.queued).into();
+        let carried_stages = std::mem::take(&mut run.carried_stages);
+        let stages = std::mem::take(&mut run.stages)
+            .into_iter()
+            .chain(carried_stages)
+            .map(stage_tasks);
+        for task in std::iter::once(queued).chain(stages).flatten() {
+            if run_always && task.rule.when() == When::Always {
+                always.push(task.into());
+                continue;
+            }
             self.record_task_snapshot(
                 run,
                 task.position,
@@ -1711,18 +2786,7 @@ impl Executor {
                 None,
             );
         }
-        for stage in std::mem::take(&mut run.stages) {
-            for task in stage_tasks(stage) {
-                self.record_task_snapshot(
-                    run,
-                    task.position,
-                    &task.name,
-                    task.group_occurrence.as_deref(),
-                    TaskState::Cancelled,
-                    None,
-                );
-            }
-        }
+        self.run_always(run, always);
         if self.verbose {
             diagnostics::debug(&diagnostics::Record {
                 generation: Some(run.metadata.run_id),
@@ -1746,12 +2810,101 @@ impl Executor {
         }
     }
 
+    /// Retains a stopped task's output and reports it cancelled.
+    fn record_cancelled(&self, run: &mut Run, task: &ActiveTask) {
+        let duration_ms = task
+            .started
+            .map(|started| self.clock.elapsed(started).as_millis() as u64);
+        Self::print_grouped(task, TaskState::Cancelled, duration_ms);
+        let steps = task.step_snapshots();
+        if let (Some(outputs), Some(capture)) = (&self.outputs, &task.capture) {
+            outputs.record(
+                run.metadata.run_id,
+                task.name.clone(),
+                capture.finish(),
+                run.metadata.revision,
+                run.metadata.revision_hash.clone(),
+            );
+            outputs.record_steps(run.metadata.run_id, &task.name, steps.clone());
+        }
+        if !steps.is_empty() {
+            run.step_results.insert(task.name.clone(), steps);
+        }
+        self.record_task_snapshot(
+            run,
+            task.position,
+            &task.name,
+            task.group_occurrence.as_deref(),
+            TaskState::Cancelled,
+            duration_ms,
+        );
+    }
+
+    /// Runs the `when: always` jobs of a cancelled run one at a time, those
+    /// already running first, within [`ALWAYS_TIMEOUT`] overall. A job still
+    /// running at the deadline is stopped; later ones are never started.
+    fn run_always(&self, run: &mut Run, tasks: Vec<ActiveTask>) {
+        let deadline = self.clock.now() + ALWAYS_TIMEOUT;
+        for mut task in tasks {
+            let mut results = vec![];
+            let finished = loop {
+                if self.clock.now() >= deadline {
+                    break false;
+                }
+                let step = self.advance_task(&mut task, &mut results, run.metadata.run_id, false);
+                if !matches!(step, TaskStep::Running) {
+                    break true;
+                }
+                self.clock.sleep(POLL_INTERVAL);
+            };
+            if finished {
+                self.record_task_outcome(run, task);
+                continue;
+            }
+            if task.child.is_some() {
+                stdout::warn(&format!(
+                    "Job '{}' did not finish within {:?} after cancellation; stopping it",
+                    task.name, ALWAYS_TIMEOUT
+                ));
+            }
+            self.shutdown_task(&mut task);
+            self.record_cancelled(run, &task);
+        }
+    }
+
     fn shutdown_task(&self, task: &mut ActiveTask) -> bool {
+        if let Some(check) = task.readiness.as_mut() {
+            check.stop();
+        }
+        if let Some(health) = task.health.as_mut() {
+            health.reset();
+        }
+        if let Some(mut reload) = task.reload.take() {
+            let (signal, grace) = crate::process_owner::stop_policy(task.context.stop);
+            reload.shutdown(signal, grace, self.verbose);
+        }
         let Some(child) = task.child.as_mut() else {
             task.commands.clear();
             return false;
         };
-        let (signal, grace) = crate::process_owner::shutdown_policy();
+        // `stop` commands run while the service is still up (e.g.
+        // `docker compose down`), never for one that already exited, and
+        // never twice: the process-wide shutdown path may have claimed them.
+        if !task.context.teardown.is_empty()
+            && matches!(child.try_wait(), Ok(None))
+            && child
+                .group()
+                .is_none_or(crate::process_owner::take_teardown)
+        {
+            crate::process_owner::run_teardown(
+                self.runner.as_ref(),
+                &task.name,
+                &task.context,
+                crate::process_owner::TEARDOWN_TIMEOUT,
+                self.verbose,
+            );
+        }
+        let (signal, grace) = crate::process_owner::stop_policy(task.context.stop);
         let outcome = child.shutdown(signal, grace, self.verbose);
         let escalated = matches!(outcome, ShutdownOutcome::Escalated { .. });
         task.child = None;
@@ -1825,6 +2978,64 @@ impl Executor {
     }
 }
 
+/// The first lock in `locks` already held by as many running tasks as its
+/// capacity, rendered as a waiting reason. Background services never count:
+/// they run for the generation's whole life, so a job waiting on one would
+/// never start.
+fn lock_conflict(run: &Run, locks: &[crate::rules::ResourceLock]) -> Option<String> {
+    locks.iter().find_map(|lock| {
+        let holders: Vec<&str> = run
+            .active
+            .iter()
+            .chain(&run.carried)
+            .filter(|task| task.locks.iter().any(|held| held.name == lock.name))
+            .map(|task| task.name.as_str())
+            .collect();
+        (holders.len() >= lock.capacity)
+            .then(|| format!("lock '{}' held by {}", lock.name, holders.join(", ")))
+    })
+}
+
+/// Whether a carried job comes before the next stage in plan order; one of
+/// the same parallel group occurrence runs alongside it instead.
+fn holds_next_stage(run: &Run) -> bool {
+    let Some(stage) = run.stages.front() else {
+        return false;
+    };
+    let tasks = planned_tasks(std::iter::once(stage));
+    let Some(first) = tasks.map(|task| task.position).min() else {
+        return false;
+    };
+    let occurrence = match stage {
+        Stage::Serial(_) => None,
+        Stage::Parallel { tasks, .. } => tasks
+            .first()
+            .and_then(|task| task.group_occurrence.as_deref()),
+    };
+    run.carried.iter().any(|task| {
+        task.position < first
+            && (occurrence.is_none() || task.group_occurrence.as_deref() != occurrence)
+    })
+}
+
+/// Every task of the given stages, in order.
+fn planned_tasks<'a>(
+    stages: impl IntoIterator<Item = &'a Stage>,
+) -> impl Iterator<Item = &'a TaskPlan> {
+    stages.into_iter().flat_map(|stage| match stage {
+        Stage::Serial(task) => std::slice::from_ref(task),
+        Stage::Parallel { tasks, .. } => tasks.as_slice(),
+    })
+}
+
+/// Names of the services a plan starts.
+fn plan_services(plan: &RunPlan) -> Vec<&str> {
+    planned_tasks(&plan.stages)
+        .filter(|task| task.service)
+        .map(|task| task.name.as_str())
+        .collect()
+}
+
 fn stage_tasks(stage: Stage) -> Vec<TaskPlan> {
     match stage {
         Stage::Serial(task) => vec![task],
@@ -1832,11 +3043,20 @@ fn stage_tasks(stage: Stage) -> Vec<TaskPlan> {
     }
 }
 
+/// Rebuilds a stage from the remaining tasks of one, None when none remain.
+fn stage_from_tasks(mut tasks: Vec<TaskPlan>) -> Option<Stage> {
+    match tasks.first()?.parallel.clone() {
+        Some(group) => Some(Stage::Parallel { group, tasks }),
+        None => tasks.pop().map(Stage::Serial),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::rules::Rules;
     use std::collections::{HashMap, HashSet};
+    use std::io;
     use std::os::unix::process::ExitStatusExt;
     use std::sync::{Arc, Mutex};
 
@@ -2251,6 +3471,166 @@ mod tests {
         assert_eq!(completed.tasks[1].duration_ms, None);
     }
 
+    #[test]
+    fn when_jobs_run_after_a_fail_fast_stop_and_on_failure_skips_passing_runs() {
+        let executor = Executor::new(
+            Arc::new(SystemProcessRunner),
+            Arc::new(FixedClock),
+            1,
+            Arc::new(|_| {}),
+            true,
+            false,
+        )
+        .unwrap();
+        let rules = |first: &str| {
+            RunPlan::from_rules(vec![
+                task("test", None, &[first]),
+                task("deploy", None, &["true"]),
+                task("logs", None, &["true"]).with_when(When::OnFailure),
+                task("cleanup", None, &["true"]).with_when(When::Always),
+            ])
+        };
+        let states = |completed: &CompletedRun| {
+            completed
+                .tasks
+                .iter()
+                .map(|task| (task.name.clone(), task.state))
+                .collect::<Vec<_>>()
+        };
+
+        let failed = executor.run_to_completion(RunMetadata::new(1, "test"), rules("false"));
+        assert!(failed.outcome.has_failures());
+        assert_eq!(
+            states(&failed),
+            [
+                ("test".to_owned(), TaskState::Failed),
+                ("deploy".to_owned(), TaskState::Cancelled),
+                ("logs".to_owned(), TaskState::Passed),
+                ("cleanup".to_owned(), TaskState::Passed),
+            ]
+        );
+
+        let passed = executor.run_to_completion(RunMetadata::new(2, "test"), rules("true"));
+        assert!(passed.outcome.is_success());
+        assert_eq!(
+            states(&passed),
+            [
+                ("test".to_owned(), TaskState::Passed),
+                ("deploy".to_owned(), TaskState::Passed),
+                ("logs".to_owned(), TaskState::Skipped),
+                ("cleanup".to_owned(), TaskState::Passed),
+            ]
+        );
+    }
+
+    #[test]
+    fn always_jobs_run_when_the_generation_is_cancelled() {
+        let executor = Executor::new(
+            Arc::new(SystemProcessRunner),
+            Arc::new(FixedClock),
+            1,
+            Arc::new(|_| {}),
+            false,
+            false,
+        )
+        .unwrap();
+        let marker =
+            std::env::temp_dir().join(format!("funzzy-exec-always-{}", std::process::id()));
+        let _ = std::fs::remove_file(&marker);
+        let cleanup = format!("touch {}", marker.display());
+        let plan = RunPlan::from_rules(vec![
+            task("serve", None, &["sleep 30"]),
+            task("report", None, &["true"]),
+            task("cleanup", None, &[cleanup.as_str()]).with_when(When::Always),
+        ]);
+        let mut run = executor.start(RunMetadata::new(1, "test"), plan);
+        assert!(matches!(executor.advance(&mut run), Step::Running));
+
+        executor.cancel(&mut run, None);
+        assert!(marker.exists(), "always job ran after cancellation");
+        std::fs::remove_file(marker).unwrap();
+    }
+
+    #[test]
+    fn exceeded_cpu_limit_is_the_snapshot_failure_reason() {
+        let executor = Executor::new(
+            Arc::new(SystemProcessRunner),
+            Arc::new(FixedClock),
+            1,
+            Arc::new(|_| {}),
+            false,
+            false,
+        )
+        .unwrap();
+        let limits = crate::rules::ProcessLimits {
+            cpu_seconds: Some(1),
+            ..Default::default()
+        };
+        let completed = executor.run_to_completion(
+            RunMetadata::new(9, "test"),
+            RunPlan::from_rules(vec![
+                task("spin", None, &["while :; do :; done"]).with_limits(limits),
+                task("plain", None, &["false"]),
+            ]),
+        );
+
+        assert_eq!(completed.tasks[0].state, TaskState::Failed);
+        assert_eq!(
+            completed.tasks[0].failure_reason.as_deref(),
+            Some("cpu limit of 1s exceeded")
+        );
+        assert_eq!(completed.tasks[1].state, TaskState::Failed);
+        assert_eq!(completed.tasks[1].failure_reason, None);
+    }
+
+    #[test]
+    fn multi_command_jobs_report_each_step() {
+        let executor = Executor::new(
+            Arc::new(SystemProcessRunner),
+            Arc::new(FixedClock),
+            1,
+            Arc::new(|_| {}),
+            true,
+            false,
+        )
+        .unwrap();
+        let completed = executor.run_to_completion(
+            RunMetadata::new(8, "test"),
+            RunPlan::from_rules(vec![
+                task("build", None, &["true", "exit 3", "true"]).with_continue_on_error(vec![1]),
+                task("check", None, &["exit 2", "true"]),
+                task("single", None, &["true"]),
+            ]),
+        );
+
+        let steps = |index: usize| {
+            completed.tasks[index]
+                .steps
+                .iter()
+                .map(|step| (step.command.as_str(), step.state, step.exit_code))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(completed.tasks[0].state, TaskState::Passed);
+        assert_eq!(
+            steps(0),
+            [
+                ("true", TaskState::Passed, Some(0)),
+                ("exit 3", TaskState::Warning, Some(3)),
+                ("true", TaskState::Passed, Some(0)),
+            ]
+        );
+        assert_eq!(completed.tasks[0].steps[1].duration_ms, Some(42));
+        assert_eq!(completed.tasks[1].state, TaskState::Failed);
+        assert_eq!(
+            steps(1),
+            [
+                ("exit 2", TaskState::Failed, Some(2)),
+                ("true", TaskState::Skipped, None),
+            ]
+        );
+        assert!(completed.tasks[2].steps.is_empty());
+    }
+
     #[test]
     fn completed_run_sorts_parallel_snapshots_by_configured_position() {
         let runner = FakeRunner::default();
@@ -2367,6 +3747,8 @@ mod tests {
         started: Vec<(String, String)>,
         completed: HashMap<String, bool>,
         shutdown: HashSet<String>,
+        stop_signals: HashMap<String, (nix::sys::signal::Signal, Duration)>,
+        signals: Vec<(String, nix::sys::signal::Signal)>,
     }
 
     #[derive(Clone, Default)]
@@ -2423,17 +3805,27 @@ mod tests {
             Ok(completed.map(|success| self.terminal_status(success)))
         }
 
+        fn signal(&mut self, signal: nix::sys::signal::Signal) -> io::Result<()> {
+            self.state
+                .lock()
+                .unwrap()
+                .signals
+                .push((self.command.clone(), signal));
+            Ok(())
+        }
+
         fn shutdown(
             &mut self,
-            _signal: nix::sys::signal::Signal,
-            _grace: Duration,
+            signal: nix::sys::signal::Signal,
+            grace: Duration,
             _verbose: bool,
         ) -> ShutdownOutcome {
-            self.state
-                .lock()
-                .unwrap()
-                .shutdown
-                .insert(self.command.clone());
+            let mut state = self.state.lock().unwrap();
+            state.shutdown.insert(self.command.clone());
+            state
+                .stop_signals
+                .insert(self.command.clone(), (signal, grace));
+            drop(state);
             ShutdownOutcome::Terminated(self.terminal_status(true))
         }
     }
@@ -2498,10 +3890,272 @@ mod tests {
         assert!(runner.started_commands().contains(&"c".to_owned()));
         assert_eq!(runner.state.lock().unwrap().active, 2);
 
-        runner.complete("b", true);
-        runner.complete("c", true);
+        runner.complete("b", true);
+        runner.complete("c", true);
+        assert!(matches!(executor.advance(&mut run), Step::Finished));
+        assert!(executor.finish(run).outcome.is_success());
+    }
+
+    /// A clock whose sleeps move it forward without waiting.
+    struct SteppingClock {
+        base: Instant,
+        offset: Mutex<Duration>,
+    }
+
+    impl Clock for SteppingClock {
+        fn now(&self) -> Instant {
+            self.base + *self.offset.lock().unwrap()
+        }
+
+        fn elapsed(&self, started: Instant) -> Duration {
+            self.now().saturating_duration_since(started)
+        }
+
+        fn sleep(&self, duration: Duration) {
+            *self.offset.lock().unwrap() += duration;
+        }
+    }
+
+    #[test]
+    fn always_jobs_still_running_at_the_deadline_are_stopped() {
+        let runner = FakeRunner::default();
+        let clock = Arc::new(SteppingClock {
+            base: Instant::now(),
+            offset: Mutex::new(Duration::ZERO),
+        });
+        let executor = Executor::new(
+            Arc::new(runner.clone()),
+            clock.clone(),
+            1,
+            Arc::new(|_| {}),
+            false,
+            false,
+        )
+        .unwrap();
+        let plan = RunPlan::from_rules(vec![
+            task("serve", None, &["serve"]),
+            task("cleanup", None, &["cleanup"]).with_when(When::Always),
+            task("notify", None, &["notify"]).with_when(When::Always),
+        ]);
+        let mut run = executor.start(RunMetadata::new(1, "test"), plan);
+        assert!(matches!(executor.advance(&mut run), Step::Running));
+
+        executor.cancel(&mut run, None);
+        assert!(*clock.offset.lock().unwrap() >= ALWAYS_TIMEOUT);
+        assert_eq!(runner.started_commands(), ["serve", "cleanup"]);
+        assert!(runner.state.lock().unwrap().shutdown.contains("cleanup"));
+        let states: Vec<_> = run
+            .task_snapshots
+            .iter()
+            .map(|task| (task.name.as_str(), task.state))
+            .collect();
+        assert!(
+            states.contains(&("cleanup", TaskState::Cancelled)),
+            "{states:?}"
+        );
+        assert!(
+            states.contains(&("notify", TaskState::Cancelled)),
+            "{states:?}"
+        );
+    }
+
+    #[test]
+    fn replaced_runs_report_always_jobs_cancelled_without_running_them() {
+        let runner = FakeRunner::default();
+        let executor = fake_executor(runner.clone(), 1, false);
+        let plan = RunPlan::from_rules(vec![
+            task("serve", None, &["serve"]),
+            task("cleanup", None, &["cleanup"]).with_when(When::Always),
+        ]);
+        let mut run = executor.start(RunMetadata::new(1, "test"), plan);
+        assert!(matches!(executor.advance(&mut run), Step::Running));
+
+        executor.replace(&mut run, Some(2));
+        assert_eq!(runner.started_commands(), ["serve"]);
+        assert!(run
+            .task_snapshots
+            .iter()
+            .any(|task| task.name == "cleanup" && task.state == TaskState::Cancelled));
+    }
+
+    fn recording_executor(runner: FakeRunner, limit: usize) -> (Executor, Arc<Mutex<Vec<Event>>>) {
+        let events = Arc::new(Mutex::new(vec![]));
+        let sink = Arc::clone(&events);
+        let executor = Executor::new(
+            Arc::new(runner),
+            Arc::new(FixedClock),
+            limit,
+            Arc::new(move |event| sink.lock().unwrap().push(event)),
+            false,
+            false,
+        )
+        .unwrap();
+        (executor, events)
+    }
+
+    fn lock(name: &str, capacity: usize) -> Vec<crate::rules::ResourceLock> {
+        vec![crate::rules::ResourceLock {
+            name: name.to_owned(),
+            capacity,
+        }]
+    }
+
+    fn waiting_reasons(events: &Mutex<Vec<Event>>) -> Vec<(String, Option<String>)> {
+        events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|event| match event {
+                Event::TaskWaiting { task, .. } => Some((task.name.clone(), task.waiting.clone())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn shared_lock_serializes_its_holders_while_other_jobs_overlap() {
+        let runner = FakeRunner::default();
+        let (executor, events) = recording_executor(runner.clone(), 4);
+        let plan = RunPlan::from_rules(vec![
+            task("migrate", Some("checks"), &["migrate"]).with_locks(lock("db", 1)),
+            task("itest", Some("checks"), &["itest"]).with_locks(lock("db", 1)),
+            task("lint", Some("checks"), &["lint"]),
+        ]);
+        let mut run = executor.start(RunMetadata::new(1, "test"), plan);
+
+        assert!(matches!(executor.advance(&mut run), Step::Running));
+        let started: HashSet<String> = runner.started_commands().into_iter().collect();
+        assert_eq!(
+            started,
+            HashSet::from(["migrate".to_owned(), "lint".to_owned()])
+        );
+        assert_eq!(
+            waiting_reasons(&events),
+            [(
+                "itest".to_owned(),
+                Some("lock 'db' held by migrate".to_owned())
+            )]
+        );
+
+        // Polling again while blocked reports nothing new.
+        assert!(matches!(executor.advance(&mut run), Step::Running));
+        assert_eq!(waiting_reasons(&events).len(), 1);
+
+        runner.complete("migrate", true);
+        assert!(matches!(executor.advance(&mut run), Step::Running));
+        assert!(runner.started_commands().contains(&"itest".to_owned()));
+        assert_eq!(waiting_reasons(&events)[1], ("itest".to_owned(), None));
+
+        runner.complete("itest", true);
+        runner.complete("lint", true);
+        assert!(matches!(executor.advance(&mut run), Step::Finished));
+        let completed = executor.finish(run);
+        assert!(completed.outcome.is_success());
+        assert!(completed.tasks.iter().all(|task| task.waiting.is_none()));
+    }
+
+    #[test]
+    fn lock_capacity_admits_that_many_holders() {
+        let runner = FakeRunner::default();
+        let (executor, events) = recording_executor(runner.clone(), 4);
+        let plan = RunPlan::from_rules(vec![
+            task("a", Some("gpu"), &["a"]).with_locks(lock("gpu", 2)),
+            task("b", Some("gpu"), &["b"]).with_locks(lock("gpu", 2)),
+            task("c", Some("gpu"), &["c"]).with_locks(lock("gpu", 2)),
+        ]);
+        let mut run = executor.start(RunMetadata::new(1, "test"), plan);
+
+        assert!(matches!(executor.advance(&mut run), Step::Running));
+        assert_eq!(runner.state.lock().unwrap().max_active, 2);
+        assert_eq!(
+            waiting_reasons(&events),
+            [("c".to_owned(), Some("lock 'gpu' held by a, b".to_owned()))]
+        );
+    }
+
+    #[test]
+    fn background_services_do_not_hold_locks_for_later_jobs() {
+        let runner = FakeRunner::default();
+        let executor = fake_executor(runner.clone(), 2, false);
+        let plan = RunPlan::from_rules(vec![
+            service_rule("db", &["postgres"], true).with_locks(lock("db", 1)),
+            service_rule("migrate", &["migrate"], false).with_locks(lock("db", 1)),
+        ]);
+        let mut run = executor.start(RunMetadata::new(1, "test"), plan);
+        runner.complete("migrate", true);
+        for _ in 0..3 {
+            executor.advance(&mut run);
+        }
+        assert_eq!(runner.started_commands(), vec!["postgres", "migrate"]);
+        assert!(run.stages.is_empty() && run.active.is_empty());
+        assert!(executor.finish(run).outcome.is_success());
+    }
+
+    #[test]
+    fn cancelling_a_lock_wait_keeps_the_reason_on_the_terminal_snapshot() {
+        let runner = FakeRunner::default();
+        let (executor, _events) = recording_executor(runner.clone(), 4);
+        let plan = RunPlan::from_rules(vec![
+            task("migrate", Some("checks"), &["migrate"]).with_locks(lock("db", 1)),
+            task("itest", Some("checks"), &["itest"]).with_locks(lock("db", 1)),
+        ]);
+        let mut run = executor.start(RunMetadata::new(1, "test"), plan);
+        assert!(matches!(executor.advance(&mut run), Step::Running));
+
+        executor.cancel(&mut run, None);
+        let itest = run
+            .task_snapshots
+            .iter()
+            .find(|task| task.name == "itest")
+            .expect("itest snapshot");
+        assert_eq!(itest.state, TaskState::Cancelled);
+        assert_eq!(itest.waiting.as_deref(), Some("lock 'db' held by migrate"));
+    }
+
+    #[test]
+    fn global_lock_waits_for_the_holder_and_names_it() {
+        use crate::global_lock::{try_acquire, Acquire};
+        let dir =
+            std::env::temp_dir().join(format!("funzzy-executor-global-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let Acquire::Held(outside) = try_acquire(&dir, "target", "sibling-build").unwrap() else {
+            panic!("free lock must be acquired");
+        };
+        let runner = FakeRunner::default();
+        let (executor, events) = recording_executor(runner.clone(), 1);
+        let executor = executor.with_global_lock_dir(dir.clone());
+        let plan = RunPlan::from_rules(vec![
+            task("build", None, &["build"]).with_global_lock(Some("target".to_owned()))
+        ]);
+        let mut run = executor.start(RunMetadata::new(1, "test"), plan);
+
+        assert!(matches!(executor.advance(&mut run), Step::Running));
+        assert!(runner.started_commands().is_empty());
+        let reasons = waiting_reasons(&events);
+        assert_eq!(reasons.len(), 1);
+        let reason = reasons[0].1.clone().unwrap();
+        assert!(
+            reason.starts_with("global lock 'target' held by sibling-build (pid "),
+            "{reason}"
+        );
+
+        drop(outside);
+        assert!(matches!(executor.advance(&mut run), Step::Running));
+        assert_eq!(runner.started_commands(), ["build"]);
+        // Held by this run now: another process would wait.
+        assert!(matches!(
+            try_acquire(&dir, "target", "other").unwrap(),
+            Acquire::Busy(_)
+        ));
+
+        runner.complete("build", true);
         assert!(matches!(executor.advance(&mut run), Step::Finished));
-        assert!(executor.finish(run).outcome.is_success());
+        executor.finish(run);
+        assert!(matches!(
+            try_acquire(&dir, "target", "other").unwrap(),
+            Acquire::Held(_)
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
     }
 
     #[test]
@@ -2609,6 +4263,46 @@ mod tests {
         assert!(!runner.started_commands().contains(&"d".to_owned()));
     }
 
+    #[test]
+    fn allowed_failure_is_a_warning_that_never_stops_the_run() {
+        let runner = FakeRunner::default();
+        let executor = fake_executor(runner.clone(), 1, true);
+        let plan = RunPlan::from_rules(vec![
+            task("advisory", None, &["audit"]).with_allow_failure(true),
+            task("lint", None, &["clippy"]).with_success_codes(vec![0, 1]),
+            task("test", None, &["test"]),
+        ]);
+        let mut run = executor.start(RunMetadata::new(1, "test"), plan);
+        executor.advance(&mut run);
+        runner.complete("audit", false);
+        executor.advance(&mut run);
+        runner.complete("clippy", false);
+        executor.advance(&mut run);
+        runner.complete("test", true);
+        assert!(matches!(executor.advance(&mut run), Step::Finished));
+
+        let completed = executor.finish(run);
+        assert!(completed.outcome.is_success());
+        assert!(!completed.outcome.has_failures());
+        assert!(completed.results.iter().all(Result::is_ok));
+        assert_eq!(
+            completed
+                .tasks
+                .iter()
+                .map(|task| (task.name.as_str(), task.state))
+                .collect::<Vec<_>>(),
+            [
+                ("advisory", TaskState::Warning),
+                ("lint", TaskState::Passed),
+                ("test", TaskState::Passed),
+            ]
+        );
+        assert!(matches!(
+            &completed.outcome.tasks[0].2,
+            TaskOutcome::Warning { failures } if failures.len() == 1
+        ));
+    }
+
     #[test]
     fn concurrency_change_affects_only_newly_planned_generation() {
         // TASK-0090 AC7: swapping the shared bound must not resize a RUNNING
@@ -2659,6 +4353,103 @@ mod tests {
         );
     }
 
+    fn service_states(events: &Mutex<Vec<Event>>) -> Vec<(String, &'static str, Option<String>)> {
+        events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|event| match event {
+                Event::ServiceState { task, .. } => Some((
+                    task.name.clone(),
+                    task.state.label(),
+                    task.failure_reason.clone(),
+                )),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn ready_on(
+        probe: crate::rules::ReadyProbe,
+        timeout: Duration,
+    ) -> Option<crate::rules::Readiness> {
+        Some(crate::rules::Readiness { probe, timeout })
+    }
+
+    #[test]
+    fn later_stages_wait_until_the_service_is_ready() {
+        let dir = std::env::temp_dir().join(format!("funzzy-exec-ready-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let marker = dir.join("up");
+        let runner = FakeRunner::default();
+        let (executor, events) = recording_executor(runner.clone(), 2);
+        let server = task("server", None, &["serve"])
+            .with_service(true)
+            .with_ready(ready_on(
+                crate::rules::ReadyProbe::File(marker.display().to_string()),
+                Duration::from_secs(30),
+            ));
+        let plan = RunPlan::from_rules(vec![server, task("e2e", None, &["e2e"])]);
+        let mut run = executor.start(RunMetadata::new(1, "test"), plan);
+
+        executor.advance(&mut run);
+        executor.advance(&mut run);
+        assert_eq!(runner.started_commands(), vec!["serve".to_owned()]);
+        assert_eq!(
+            service_states(&events),
+            vec![("server".to_owned(), "starting", None)]
+        );
+
+        std::fs::write(&marker, "").unwrap();
+        let started = Instant::now();
+        while !runner.started_commands().contains(&"e2e".to_owned()) {
+            assert!(
+                started.elapsed() < Duration::from_secs(5),
+                "e2e never started"
+            );
+            executor.advance(&mut run);
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(
+            service_states(&events),
+            vec![
+                ("server".to_owned(), "starting", None),
+                ("server".to_owned(), "ready", None),
+            ]
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn a_service_that_never_becomes_ready_fails_the_generation() {
+        let runner = FakeRunner::default();
+        let (executor, events) = recording_executor(runner.clone(), 2);
+        let server = task("server", None, &["serve"])
+            .with_service(true)
+            .with_ready(ready_on(
+                crate::rules::ReadyProbe::File("/nonexistent/funzzy-ready".to_owned()),
+                Duration::ZERO,
+            ));
+        let plan = RunPlan::from_rules(vec![server, task("e2e", None, &["e2e"])]);
+        let mut run = executor.start(RunMetadata::new(1, "test"), plan);
+
+        executor.advance(&mut run);
+        let reason = "not ready within 0s: file /nonexistent/funzzy-ready".to_owned();
+        assert_eq!(
+            service_states(&events).last(),
+            Some(&("server".to_owned(), "unready", Some(reason.clone())))
+        );
+        // Without fail-fast the next stage still runs; the service keeps
+        // running in the background.
+        executor.advance(&mut run);
+        assert!(runner.started_commands().contains(&"e2e".to_owned()));
+        assert!(run
+            .results
+            .contains(&Err(format!("Service server was {reason}"))));
+        assert!(!runner.state.lock().unwrap().shutdown.contains("serve"));
+    }
+
     #[test]
     fn reconcile_services_stops_named_services_and_keeps_others_owned() {
         // TASK-0090 AC6: reconcile retires only the named services (graceful,
@@ -2736,6 +4527,26 @@ mod tests {
         assert!(!state.started.iter().any(|(_, command)| command == "c"));
     }
 
+    #[test]
+    fn cancellation_stops_each_job_with_its_own_signal_and_grace() {
+        use nix::sys::signal::Signal;
+        let runner = FakeRunner::default();
+        let executor = fake_executor(runner.clone(), 2, false);
+        let server = task("server", Some("dev"), &["serve"]).with_stop(crate::rules::StopPolicy {
+            signal: Some(Signal::SIGINT),
+            grace: Some(Duration::from_secs(20)),
+        });
+        let plan = RunPlan::from_rules(vec![server, task("test", Some("dev"), &["check"])]);
+        let mut run = executor.start(RunMetadata::new(1, "test"), plan);
+        executor.advance(&mut run);
+
+        executor.cancel(&mut run, None);
+        let (default_signal, default_grace) = crate::process_owner::shutdown_policy();
+        let stops = runner.state.lock().unwrap().stop_signals.clone();
+        assert_eq!(stops["serve"], (Signal::SIGINT, Duration::from_secs(20)));
+        assert_eq!(stops["check"], (default_signal, default_grace));
+    }
+
     #[test]
     fn spawn_failure_releases_capacity_and_reports_task_failure() {
         let runner = FakeRunner::default();
@@ -2789,6 +4600,46 @@ mod tests {
             .is_none_or(|stream| stream.content.is_empty()));
     }
 
+    #[test]
+    fn paged_output_of_a_multi_command_job_carries_its_steps() {
+        use crate::output::{OutputRegistry, DEFAULT_PAGE_BYTES};
+        let outputs = Arc::new(OutputRegistry::new());
+        let executor = Executor::with_outputs(
+            Arc::new(SystemProcessRunner),
+            Arc::new(SystemClock),
+            1,
+            Arc::new(|_| {}),
+            false,
+            false,
+            Some(outputs.clone()),
+        )
+        .expect("concurrency one is supported");
+
+        let plan = RunPlan::from_rules(vec![task("t", None, &["echo one", "echo two"])]);
+        let completed = executor.run_to_completion(RunMetadata::new(6, "test"), plan);
+        assert!(completed.outcome.is_success());
+
+        let page = outputs
+            .retrieve_page(6, Some("t"), None, DEFAULT_PAGE_BYTES, None)
+            .unwrap();
+        assert_eq!(
+            page.tasks[0].stdout.as_ref().expect("stdout").content,
+            "one\ntwo\n"
+        );
+        let steps: Vec<_> = page.tasks[0]
+            .steps
+            .iter()
+            .map(|step| (step.command.as_str(), step.state, step.stdout_offsets))
+            .collect();
+        assert_eq!(
+            steps,
+            [
+                ("echo one", TaskState::Passed, Some([0, 4])),
+                ("echo two", TaskState::Passed, Some([4, 8])),
+            ]
+        );
+    }
+
     #[test]
     fn started_event_carries_batch_predecessor_and_changed_set() {
         let events = Arc::new(Mutex::new(Vec::new()));
@@ -2892,7 +4743,14 @@ mod tests {
     fn service_restarts_on_unexpected_exit_up_to_the_bound() {
         let runner = FakeRunner::default();
         let executor = fake_executor(runner.clone(), 1, false);
-        let plan = RunPlan::from_rules(vec![service_rule("svc", &["serve"], true)]);
+        // The backoff is real time; this test is about the bound.
+        let restart = crate::rules::RestartPolicy {
+            delay: Duration::ZERO,
+            ..Default::default()
+        };
+        let plan = RunPlan::from_rules(vec![
+            service_rule("svc", &["serve"], true).with_restart(restart)
+        ]);
         let mut run = executor.start(RunMetadata::new(1, "test"), plan);
         executor.advance(&mut run);
         assert_eq!(runner.state.lock().unwrap().started.len(), 1, "first spawn");
@@ -2901,7 +4759,7 @@ mod tests {
         runner.complete("serve", false);
         // Each restart needs a respawn poll + an exit poll; drive well past
         // the bound so the final failure is recorded deterministically.
-        for _ in 0..=(4 + SERVICE_MAX_RESTARTS) {
+        for _ in 0..=(4 + restart.max) {
             let _ = executor.advance(&mut run);
         }
         // After the bound, the service failure is recorded in outcomes.
@@ -2914,11 +4772,390 @@ mod tests {
         );
         assert_eq!(
             runner.state.lock().unwrap().started.len(),
-            1 + SERVICE_MAX_RESTARTS,
+            1 + restart.max,
             "1 + 3 spawns"
         );
     }
 
+    fn service_health(events: &Mutex<Vec<Event>>) -> Vec<(Option<u32>, Option<String>)> {
+        events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|event| match event {
+                Event::ServiceState { task, .. } => Some((task.restarts, task.health.clone())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn restart_backoff_delays_the_respawn_without_blocking() {
+        let runner = FakeRunner::default();
+        let clock = Arc::new(SteppingClock {
+            base: Instant::now(),
+            offset: Mutex::new(Duration::ZERO),
+        });
+        let events = Arc::new(Mutex::new(vec![]));
+        let sink = Arc::clone(&events);
+        let executor = Executor::new(
+            Arc::new(runner.clone()),
+            clock.clone(),
+            1,
+            Arc::new(move |event| sink.lock().unwrap().push(event)),
+            false,
+            false,
+        )
+        .unwrap();
+        let restart = crate::rules::RestartPolicy {
+            max: 2,
+            backoff: crate::rules::Backoff::Exponential,
+            delay: Duration::from_millis(50),
+            reset_after: None,
+        };
+        let plan = RunPlan::from_rules(vec![
+            service_rule("svc", &["serve"], true).with_restart(restart)
+        ]);
+        let mut run = executor.start(RunMetadata::new(1, "test"), plan);
+        executor.advance(&mut run);
+        runner.complete("serve", false);
+
+        executor.advance(&mut run);
+        executor.advance(&mut run);
+        assert_eq!(runner.started_commands().len(), 1, "still backing off");
+        assert_eq!(
+            service_health(&events),
+            vec![(Some(0), None), (Some(1), None)]
+        );
+
+        clock.sleep(Duration::from_millis(49));
+        executor.advance(&mut run);
+        assert_eq!(runner.started_commands().len(), 1, "just before the delay");
+        clock.sleep(Duration::from_millis(1));
+        executor.advance(&mut run);
+        assert_eq!(runner.started_commands().len(), 2, "respawned");
+
+        // The second restart waits twice as long.
+        executor.advance(&mut run);
+        clock.sleep(Duration::from_millis(60));
+        executor.advance(&mut run);
+        assert_eq!(runner.started_commands().len(), 2);
+        clock.sleep(Duration::from_millis(40));
+        executor.advance(&mut run);
+        assert_eq!(runner.started_commands().len(), 3);
+        assert_eq!(restart.delay_for(12), crate::rules::MAX_RESTART_BACKOFF);
+    }
+
+    #[test]
+    fn a_service_that_ran_for_reset_after_gets_its_restart_budget_back() {
+        let runner = FakeRunner::default();
+        let executor = fake_executor(runner.clone(), 1, false);
+        // The fake clock reports every service as having run for 42ms.
+        let restart = crate::rules::RestartPolicy {
+            max: 1,
+            delay: Duration::ZERO,
+            reset_after: Some(Duration::from_millis(10)),
+            ..Default::default()
+        };
+        let plan = RunPlan::from_rules(vec![
+            service_rule("svc", &["serve"], true).with_restart(restart)
+        ]);
+        let mut run = executor.start(RunMetadata::new(1, "test"), plan);
+        executor.advance(&mut run);
+        runner.complete("serve", false);
+        for _ in 0..8 {
+            executor.advance(&mut run);
+        }
+        assert!(runner.started_commands().len() > 2);
+        assert!(run.outcomes.is_empty(), "{:?}", run.outcomes);
+    }
+
+    #[test]
+    fn a_service_failing_its_health_checks_is_restarted() {
+        let runner = FakeRunner::default();
+        let (executor, events) = recording_executor(runner.clone(), 1);
+        let plan = RunPlan::from_rules(vec![service_rule("svc", &["serve"], true)
+            .with_restart(crate::rules::RestartPolicy {
+                max: 1,
+                delay: Duration::ZERO,
+                ..Default::default()
+            })
+            .with_health(Some(crate::rules::HealthCheck {
+                probe: crate::rules::ReadyProbe::File("/nonexistent/funzzy-health".to_owned()),
+                interval: Duration::ZERO,
+                failures: 2,
+            }))]);
+        let mut run = executor.start(RunMetadata::new(1, "test"), plan);
+        // Spawn (scheduling the first check), then two failed checks.
+        for _ in 0..3 {
+            executor.advance(&mut run);
+        }
+        let failing = |count| {
+            Some(format!(
+                "failing {count}/2: file /nonexistent/funzzy-health"
+            ))
+        };
+        assert_eq!(
+            service_health(&events),
+            vec![
+                (Some(0), None),
+                (Some(0), failing(1)),
+                (Some(0), failing(2)),
+                (Some(1), failing(2)),
+            ]
+        );
+        assert_eq!(runner.state.lock().unwrap().stop_signals.len(), 1);
+        executor.advance(&mut run);
+        assert_eq!(runner.started_commands().len(), 2, "restarted");
+
+        // A restart budget of one is spent by the next unhealthy spell.
+        for _ in 0..3 {
+            executor.advance(&mut run);
+        }
+        assert!(matches!(
+            run.outcomes.as_slice(),
+            [(_, _, _, TaskOutcome::Failed { failures })]
+                if failures == &["Service svc has failed after 1 restarts".to_owned()]
+        ));
+    }
+
+    #[test]
+    fn a_matching_change_reloads_kept_services_instead_of_restarting_them() {
+        let runner = FakeRunner::default();
+        let (executor, events) = recording_executor(runner.clone(), 3);
+        let rules = || {
+            vec![
+                service_rule("nginx", &["nginx"], true).with_on_change(
+                    crate::rules::OnChange::Signal(nix::sys::signal::Signal::SIGHUP),
+                ),
+                service_rule("api", &["api"], true)
+                    .with_on_change(crate::rules::OnChange::Command("api reload".to_owned())),
+                service_rule("db", &["db"], true),
+            ]
+        };
+        let mut first = executor.start(RunMetadata::new(1, "test"), RunPlan::from_rules(rules()));
+        while first.services.len() < 3 {
+            executor.advance(&mut first);
+        }
+
+        let plan = RunPlan::from_rules(rules());
+        let kept = executor.keep_services(&mut first, &plan, 2);
+        executor.cancel(&mut first, Some(2));
+        let mut second = executor.start_with_services(RunMetadata::new(2, "test"), plan, kept);
+        executor.advance(&mut second);
+
+        let state = runner.state.lock().unwrap();
+        assert_eq!(
+            state.signals,
+            vec![("nginx".to_owned(), nix::sys::signal::Signal::SIGHUP)]
+        );
+        // Only the restart-on-change service was stopped and spawned again.
+        assert!(state.shutdown.contains("db"));
+        assert!(!state.shutdown.contains("nginx") && !state.shutdown.contains("api"));
+        let started: Vec<&str> = state.started.iter().map(|(_, c)| c.as_str()).collect();
+        assert_eq!(started, vec!["nginx", "api", "db", "api reload", "db"]);
+        drop(state);
+        assert_eq!(second.services.len(), 3);
+
+        let actions: Vec<(u64, String, String)> = events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|event| match event {
+                Event::ServiceAction {
+                    run_id,
+                    task,
+                    action,
+                } => Some((*run_id, task.name.clone(), action.clone())),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            actions,
+            vec![
+                (2, "nginx".to_owned(), "signal SIGHUP".to_owned()),
+                (2, "api".to_owned(), "command api reload".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn kept_services_the_final_plan_drops_are_stopped() {
+        let runner = FakeRunner::default();
+        let executor = fake_executor(runner.clone(), 1, false);
+        let svc =
+            || service_rule("svc", &["serve"], true).with_on_change(crate::rules::OnChange::Ignore);
+        let mut first = executor.start(
+            RunMetadata::new(1, "test"),
+            RunPlan::from_rules(vec![svc()]),
+        );
+        executor.advance(&mut first);
+        let kept = executor.keep_services(&mut first, &RunPlan::from_rules(vec![svc()]), 2);
+        executor.cancel(&mut first, Some(2));
+        assert!(!runner.state.lock().unwrap().shutdown.contains("serve"));
+
+        // A later change in the same burst matched only `test`.
+        let later = RunPlan::from_rules(vec![service_rule("test", &["test"], false)]);
+        let second = executor.start_with_services(RunMetadata::new(3, "test"), later, kept);
+        assert!(second.services.is_empty());
+        assert!(runner.state.lock().unwrap().shutdown.contains("serve"));
+    }
+
+    #[test]
+    fn restart_affected_cancels_only_jobs_the_new_run_schedules_again() {
+        let runner = FakeRunner::default();
+        let (executor, events) = recording_executor(runner.clone(), 2);
+        let mut first = executor.start(
+            RunMetadata::new(1, "test"),
+            RunPlan::from_rules(vec![
+                task("lint", Some("checks"), &["lint"]),
+                task("test", Some("checks"), &["test"]),
+                task("deploy", None, &["deploy"]),
+            ]),
+        );
+        executor.advance(&mut first);
+        assert_eq!(runner.started_commands(), vec!["lint", "test"]);
+
+        let plan = RunPlan::from_rules(vec![task("test", None, &["test"])]);
+        let carried = executor.carry_unaffected(&mut first, &plan, 2);
+        executor.replace(&mut first, None);
+        assert!(runner.state.lock().unwrap().shutdown.contains("test"));
+        assert!(!runner.state.lock().unwrap().shutdown.contains("lint"));
+
+        // The queued later stage moves to the new run and still waits for
+        // the carried job of the stage before it.
+        let mut second = executor.start(RunMetadata::new(2, "test"), plan);
+        executor.adopt(&mut second, carried);
+        runner.complete("test", true);
+        runner.complete("deploy", true);
+        assert!(matches!(executor.advance(&mut second), Step::Running));
+        assert!(matches!(executor.advance(&mut second), Step::Running));
+        assert_eq!(runner.started_commands(), vec!["lint", "test", "test"]);
+        runner.complete("lint", true);
+        while !matches!(executor.advance(&mut second), Step::Finished) {}
+        assert_eq!(
+            runner.started_commands(),
+            vec!["lint", "test", "test", "deploy"]
+        );
+        assert!(executor.finish(second).outcome.is_success());
+
+        let terminal: Vec<(u64, String, TaskState, Option<u64>)> = events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|event| match event {
+                Event::TaskTerminal { run_id, task } => {
+                    Some((*run_id, task.name.clone(), task.state, task.superseded_by))
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            terminal,
+            vec![
+                (1, "test".to_owned(), TaskState::Cancelled, Some(2)),
+                (2, "test".to_owned(), TaskState::Passed, None),
+                (2, "lint".to_owned(), TaskState::Passed, None),
+                (2, "deploy".to_owned(), TaskState::Passed, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn restart_affected_keeps_later_serial_stages_behind_carried_jobs() {
+        let runner = FakeRunner::default();
+        let executor = fake_executor(runner.clone(), 2, false);
+        let rules = || {
+            RunPlan::from_rules(vec![
+                task("build", None, &["build"]),
+                task("test", None, &["test"]),
+            ])
+        };
+        let mut first = executor.start(RunMetadata::new(1, "test"), rules());
+        executor.advance(&mut first);
+        assert_eq!(runner.started_commands(), vec!["build"]);
+
+        let plan = rules().filter(|rule| rule.name == "test");
+        let carried = executor.carry_unaffected(&mut first, &plan, 2);
+        executor.replace(&mut first, None);
+        let mut second = executor.start(RunMetadata::new(2, "test"), plan);
+        executor.adopt(&mut second, carried);
+        assert!(matches!(executor.advance(&mut second), Step::Running));
+        assert!(matches!(executor.advance(&mut second), Step::Running));
+        assert_eq!(runner.started_commands(), vec!["build"], "test waits");
+
+        runner.complete("build", true);
+        executor.advance(&mut second);
+        assert_eq!(runner.started_commands(), vec!["build", "test"]);
+        runner.complete("test", true);
+        while !matches!(executor.advance(&mut second), Step::Finished) {}
+        assert!(executor.finish(second).outcome.is_success());
+    }
+
+    #[test]
+    fn carried_jobs_take_concurrency_slots() {
+        let runner = FakeRunner::default();
+        let executor = fake_executor(runner.clone(), 1, false);
+        let rules = || {
+            RunPlan::from_rules(vec![
+                task("build", None, &["build"]),
+                task("test", None, &["test"]),
+            ])
+        };
+        let mut first = executor.start(RunMetadata::new(1, "test"), rules());
+        runner.complete("build", true);
+        while runner.started_commands().len() < 2 {
+            executor.advance(&mut first);
+        }
+        assert_eq!(runner.started_commands(), vec!["build", "test"]);
+
+        // `test` comes later in the plan, so only the limit holds `build`.
+        let plan = rules().filter(|rule| rule.name == "build");
+        let carried = executor.carry_unaffected(&mut first, &plan, 2);
+        executor.replace(&mut first, None);
+        let mut second = executor.start(RunMetadata::new(2, "test"), plan);
+        executor.adopt(&mut second, carried);
+        executor.advance(&mut second);
+        assert_eq!(runner.started_commands(), vec!["build", "test"]);
+
+        runner.complete("test", true);
+        executor.advance(&mut second);
+        executor.advance(&mut second);
+        assert_eq!(runner.started_commands(), vec!["build", "test", "build"]);
+    }
+
+    #[test]
+    fn stop_commands_run_before_a_cancelled_service_is_signalled() {
+        let runner = FakeRunner::default();
+        let executor = fake_executor(runner.clone(), 1, false);
+        runner.complete("compose down", true);
+        runner.complete("flush", false);
+        let plan = RunPlan::from_rules(vec![
+            service_rule("stack", &["compose up"], true)
+                .with_teardown(vec!["compose down".to_owned()]),
+            service_rule("db", &["db"], true)
+                .with_teardown(vec!["flush".to_owned(), "close".to_owned()]),
+        ]);
+        let mut run = executor.start(RunMetadata::new(1, "test"), plan);
+        executor.advance(&mut run);
+        executor.advance(&mut run);
+        executor.cancel(&mut run, Some(2));
+
+        let state = runner.state.lock().unwrap();
+        assert!(state
+            .started
+            .contains(&("stack".to_owned(), "compose down".to_owned())));
+        assert!(state
+            .started
+            .contains(&("db".to_owned(), "flush".to_owned())));
+        assert!(
+            !state.started.iter().any(|(_, command)| command == "close"),
+            "a failing stop command ends the sequence"
+        );
+        assert!(state.shutdown.contains("compose up"));
+        assert!(state.shutdown.contains("db"));
+    }
+
     #[test]
     fn running_service_is_background_and_does_not_block_generation() {
         let runner = FakeRunner::default();
@@ -0,0 +1,492 @@
+//! In-progress git operation awareness.
+//!
+//! A rebase, merge, or branch switch rewrites many tracked files over several
+//! debounce windows; routing those intermediate batches runs tasks against a
+//! half-applied tree. The watch loop consults [`GitHold`] per batch: while an
+//! operation marker exists under the repository's git directory, changed
+//! paths are held, and once the markers are gone the held set is released as
+//! exactly one coalesced batch.
+//!
+//! [`GitTracker`] drives `on.git`/`jobs[].git` triggers: it compares HEAD,
+//! the checked-out ref, and the index between observations. The files are
+//! read directly, so repository events are seen even though `.git` is never
+//! part of normal traversal or subscription roots.
+
+use crate::identity::{Batch, BatchId};
+use crate::rules::GitTrigger;
+use crate::template::GitTransition;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// One in-progress git operation, detected from its on-disk marker.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GitOperation {
+    /// `rebase-merge/`, `rebase-apply/`, or `REBASE_HEAD`.
+    Rebase,
+    /// `MERGE_HEAD`.
+    Merge,
+    /// `index.lock`: checkout, commit, reset, stash, and friends.
+    IndexLock,
+}
+
+impl GitOperation {
+    /// Stable label used in status snapshots and diagnostics.
+    pub fn label(&self) -> &'static str {
+        match self {
+            GitOperation::Rebase => "rebase",
+            GitOperation::Merge => "merge",
+            GitOperation::IndexLock => "index_lock",
+        }
+    }
+}
+
+/// Resolves the git directory for `root`: the nearest ancestor holding a
+/// `.git` directory, or a `.git` file pointing elsewhere (worktrees and
+/// submodules use `gitdir: <path>`). None outside any repository.
+pub fn git_dir(root: &Path) -> Option<PathBuf> {
+    let root = std::fs::canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+    for dir in root.ancestors() {
+        let candidate = dir.join(".git");
+        if candidate.is_dir() {
+            return Some(candidate);
+        }
+        if candidate.is_file() {
+            let content = std::fs::read_to_string(&candidate).ok()?;
+            let target = content.trim().strip_prefix("gitdir:")?.trim();
+            let target = Path::new(target);
+            return Some(if target.is_absolute() {
+                target.to_path_buf()
+            } else {
+                dir.join(target)
+            });
+        }
+    }
+    None
+}
+
+/// Returns the operation currently in progress under `git_dir`, if any.
+/// Long-running operations win over the transient index lock so the status
+/// names what the user actually started.
+pub fn in_progress(git_dir: &Path) -> Option<GitOperation> {
+    if git_dir.join("rebase-merge").is_dir()
+        || git_dir.join("rebase-apply").is_dir()
+        || git_dir.join("REBASE_HEAD").exists()
+    {
+        return Some(GitOperation::Rebase);
+    }
+    if git_dir.join("MERGE_HEAD").exists() {
+        return Some(GitOperation::Merge);
+    }
+    if git_dir.join("index.lock").exists() {
+        return Some(GitOperation::IndexLock);
+    }
+    None
+}
+
+/// Repository facts compared between observations.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GitSnapshot {
+    /// Raw HEAD content: `ref: refs/heads/<branch>` or a detached commit.
+    head: Option<String>,
+    /// Commit HEAD resolves to; None on an unborn branch.
+    commit: Option<String>,
+    index: Option<SystemTime>,
+}
+
+impl GitSnapshot {
+    pub fn capture(git_dir: &Path) -> Self {
+        let head = std::fs::read_to_string(git_dir.join("HEAD"))
+            .ok()
+            .map(|content| content.trim().to_owned());
+        let commit = match head.as_deref().and_then(|head| head.strip_prefix("ref:")) {
+            Some(reference) => resolve_ref(git_dir, reference.trim()),
+            None => head.clone(),
+        };
+        let index = std::fs::metadata(git_dir.join("index"))
+            .and_then(|meta| meta.modified())
+            .ok();
+        GitSnapshot {
+            head,
+            commit,
+            index,
+        }
+    }
+
+    /// Triggers fired between `self` and a later snapshot.
+    pub fn triggers(&self, after: &GitSnapshot) -> Vec<GitTrigger> {
+        let mut fired = vec![];
+        if self.commit != after.commit {
+            fired.push(GitTrigger::HeadChange);
+            if self.head == after.head
+                && self.head.as_deref().is_some_and(|h| h.starts_with("ref:"))
+            {
+                fired.push(GitTrigger::Commit);
+            }
+        }
+        if self.index != after.index {
+            fired.push(GitTrigger::IndexChange);
+        }
+        fired
+    }
+}
+
+/// Resolves a ref from loose files or `packed-refs`. Linked worktrees keep
+/// branch refs in the common directory named by `commondir`.
+fn resolve_ref(git_dir: &Path, reference: &str) -> Option<String> {
+    let common = std::fs::read_to_string(git_dir.join("commondir"))
+        .ok()
+        .map(|dir| git_dir.join(dir.trim()))
+        .unwrap_or_else(|| git_dir.to_path_buf());
+    for dir in [git_dir, common.as_path()] {
+        if let Ok(content) = std::fs::read_to_string(dir.join(reference)) {
+            return Some(content.trim().to_owned());
+        }
+    }
+    let packed = std::fs::read_to_string(common.join("packed-refs")).ok()?;
+    packed.lines().find_map(|line| {
+        let (commit, name) = line.split_once(' ')?;
+        (name.trim() == reference).then(|| commit.to_owned())
+    })
+}
+
+/// One observed repository change: the fired triggers plus the commit
+/// transition exposed to commands.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GitChange {
+    pub triggers: Vec<GitTrigger>,
+    pub transition: GitTransition,
+}
+
+/// Tracks repository state between observations.
+pub struct GitTracker {
+    git_dir: Option<PathBuf>,
+    last: GitSnapshot,
+}
+
+impl GitTracker {
+    /// Baselines the current state, so startup never reports a change.
+    pub fn new(git_dir: Option<PathBuf>) -> Self {
+        let last = git_dir
+            .as_deref()
+            .map(GitSnapshot::capture)
+            .unwrap_or_default();
+        GitTracker { git_dir, last }
+    }
+
+    /// Returns the change since the previous observation, if any. While a
+    /// rebase, merge or index lock is in progress nothing is reported and
+    /// the baseline stays put, so the whole operation fires once after it.
+    pub fn observe(&mut self) -> Option<GitChange> {
+        let git_dir = self.git_dir.as_deref()?;
+        if in_progress(git_dir).is_some() {
+            return None;
+        }
+        let current = GitSnapshot::capture(git_dir);
+        let triggers = self.last.triggers(&current);
+        let before = std::mem::replace(&mut self.last, current);
+        if triggers.is_empty() {
+            return None;
+        }
+        Some(GitChange {
+            triggers,
+            transition: GitTransition {
+                before: before.commit.unwrap_or_default(),
+                after: self.last.commit.clone().unwrap_or_default(),
+            },
+        })
+    }
+}
+
+/// Outcome of admitting one batch through the hold.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Admission {
+    /// Route this batch now (possibly coalesced with previously held paths).
+    Route(Batch),
+    /// The batch was held; `started` is true when this batch opened the hold.
+    Held {
+        operation: GitOperation,
+        started: bool,
+    },
+}
+
+/// Holds batches while a git operation is in progress and releases them as
+/// one coalesced batch once it finished.
+pub struct GitHold {
+    git_dir: Option<PathBuf>,
+    operation: Option<GitOperation>,
+    held: Vec<String>,
+    last_batch: Option<BatchId>,
+}
+
+impl GitHold {
+    /// Creates a hold for the repository containing `root`; outside a
+    /// repository every batch routes unchanged.
+    pub fn new(root: &Path) -> Self {
+        Self::with_git_dir(git_dir(root))
+    }
+
+    pub fn with_git_dir(git_dir: Option<PathBuf>) -> Self {
+        GitHold {
+            git_dir,
+            operation: None,
+            held: vec![],
+            last_batch: None,
+        }
+    }
+
+    /// The operation batches are currently held for.
+    pub fn operation(&self) -> Option<GitOperation> {
+        self.operation
+    }
+
+    fn detect(&self) -> Option<GitOperation> {
+        self.git_dir.as_deref().and_then(in_progress)
+    }
+
+    /// Admits one batch: held while an operation is in progress, otherwise
+    /// routed together with any paths held before the operation finished.
+    pub fn admit(&mut self, batch: Batch) -> Admission {
+        if let Some(operation) = self.detect() {
+            let started = self.operation.is_none();
+            self.operation = Some(operation);
+            self.held.extend(batch.changed);
+            self.last_batch = Some(batch.id);
+            return Admission::Held { operation, started };
+        }
+        match self.release() {
+            Some(held) => {
+                let mut paths = held.changed;
+                paths.extend(batch.changed);
+                Admission::Route(Batch::normalized(batch.id, paths))
+            }
+            None => Admission::Route(batch),
+        }
+    }
+
+    /// Checks whether an active hold can be released without a new batch
+    /// (called on idle ticks). Returns the coalesced batch once the
+    /// operation finished; None while still in progress or when idle.
+    pub fn poll(&mut self) -> Option<Batch> {
+        self.operation?;
+        if let Some(operation) = self.detect() {
+            self.operation = Some(operation);
+            return None;
+        }
+        self.release()
+    }
+
+    fn release(&mut self) -> Option<Batch> {
+        self.operation.take()?;
+        let id = self.last_batch.take()?;
+        Some(Batch::normalized(id, std::mem::take(&mut self.held)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch(label: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("funzzy-git-state-{}-{}", label, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join(".git")).unwrap();
+        dir
+    }
+
+    fn batch(id: u64, paths: &[&str]) -> Batch {
+        Batch::normalized(
+            BatchId(id),
+            paths.iter().map(|path| path.to_string()).collect(),
+        )
+    }
+
+    #[test]
+    fn detects_each_operation_marker() {
+        let root = scratch("markers");
+        let git = root.join(".git");
+        assert_eq!(in_progress(&git), None);
+
+        std::fs::write(git.join("index.lock"), "").unwrap();
+        assert_eq!(in_progress(&git), Some(GitOperation::IndexLock));
+
+        std::fs::write(git.join("MERGE_HEAD"), "abc").unwrap();
+        assert_eq!(in_progress(&git), Some(GitOperation::Merge));
+
+        std::fs::create_dir(git.join("rebase-merge")).unwrap();
+        assert_eq!(in_progress(&git), Some(GitOperation::Rebase));
+
+        std::fs::remove_dir(git.join("rebase-merge")).unwrap();
+        std::fs::write(git.join("REBASE_HEAD"), "abc").unwrap();
+        assert_eq!(in_progress(&git), Some(GitOperation::Rebase));
+    }
+
+    #[test]
+    fn git_dir_resolves_from_nested_roots_and_gitdir_files() {
+        let root = scratch("resolve");
+        let nested = root.join("a").join("b");
+        std::fs::create_dir_all(&nested).unwrap();
+        assert_eq!(git_dir(&nested), Some(root.join(".git")));
+
+        let worktree = root.join("worktree");
+        std::fs::create_dir_all(&worktree).unwrap();
+        std::fs::write(worktree.join(".git"), "gitdir: ../.git/worktrees/wt\n").unwrap();
+        assert_eq!(
+            git_dir(&worktree),
+            Some(worktree.join("../.git/worktrees/wt"))
+        );
+    }
+
+    #[test]
+    fn batches_route_unchanged_without_an_operation() {
+        let root = scratch("idle");
+        let mut hold = GitHold::new(&root);
+        assert_eq!(
+            hold.admit(batch(1, &["src/a.rs"])),
+            Admission::Route(batch(1, &["src/a.rs"]))
+        );
+        assert_eq!(hold.poll(), None);
+    }
+
+    #[test]
+    fn held_batches_release_as_one_coalesced_batch() {
+        let root = scratch("coalesce");
+        let lock = root.join(".git").join("rebase-merge");
+        std::fs::create_dir(&lock).unwrap();
+        let mut hold = GitHold::new(&root);
+
+        assert_eq!(
+            hold.admit(batch(1, &["src/b.rs"])),
+            Admission::Held {
+                operation: GitOperation::Rebase,
+                started: true
+            }
+        );
+        assert_eq!(
+            hold.admit(batch(2, &["src/a.rs", "src/b.rs"])),
+            Admission::Held {
+                operation: GitOperation::Rebase,
+                started: false
+            }
+        );
+        assert_eq!(hold.poll(), None, "still rebasing");
+
+        std::fs::remove_dir(&lock).unwrap();
+        assert_eq!(hold.poll(), Some(batch(2, &["src/a.rs", "src/b.rs"])));
+        assert_eq!(hold.operation(), None);
+        assert_eq!(hold.poll(), None, "released exactly once");
+    }
+
+    #[test]
+    fn batch_after_operation_merges_with_held_paths() {
+        let root = scratch("merge-after");
+        let marker = root.join(".git").join("MERGE_HEAD");
+        std::fs::write(&marker, "abc").unwrap();
+        let mut hold = GitHold::new(&root);
+        hold.admit(batch(1, &["src/a.rs"]));
+
+        std::fs::remove_file(&marker).unwrap();
+        assert_eq!(
+            hold.admit(batch(5, &["src/c.rs"])),
+            Admission::Route(batch(5, &["src/a.rs", "src/c.rs"]))
+        );
+    }
+
+    #[test]
+    fn outside_a_repository_nothing_is_held() {
+        let mut hold = GitHold::with_git_dir(None);
+        assert_eq!(
+            hold.admit(batch(1, &["a"])),
+            Admission::Route(batch(1, &["a"]))
+        );
+    }
+
+    fn write_head(git: &Path, content: &str) {
+        std::fs::write(git.join("HEAD"), format!("{content}\n")).unwrap();
+    }
+
+    #[test]
+    fn tracker_reports_commit_on_the_same_branch() {
+        let root = scratch("commit");
+        let git = root.join(".git");
+        std::fs::create_dir_all(git.join("refs/heads")).unwrap();
+        write_head(&git, "ref: refs/heads/main");
+        std::fs::write(git.join("refs/heads/main"), "aaa\n").unwrap();
+        let mut tracker = GitTracker::new(Some(git.clone()));
+        assert_eq!(tracker.observe(), None, "baseline never fires");
+
+        std::fs::write(git.join("refs/heads/main"), "bbb\n").unwrap();
+        assert_eq!(
+            tracker.observe(),
+            Some(GitChange {
+                triggers: vec![GitTrigger::HeadChange, GitTrigger::Commit],
+                transition: GitTransition {
+                    before: "aaa".to_owned(),
+                    after: "bbb".to_owned(),
+                },
+            })
+        );
+        assert_eq!(tracker.observe(), None);
+    }
+
+    #[test]
+    fn tracker_defers_changes_until_a_git_operation_finishes() {
+        let root = scratch("rebase");
+        let git = root.join(".git");
+        std::fs::create_dir_all(git.join("refs/heads")).unwrap();
+        write_head(&git, "ref: refs/heads/main");
+        std::fs::write(git.join("refs/heads/main"), "aaa\n").unwrap();
+        let mut tracker = GitTracker::new(Some(git.clone()));
+
+        std::fs::create_dir_all(git.join("rebase-merge")).unwrap();
+        for commit in ["bbb", "ccc"] {
+            write_head(&git, commit);
+            assert_eq!(tracker.observe(), None, "held while rebasing");
+        }
+        std::fs::remove_dir_all(git.join("rebase-merge")).unwrap();
+        std::fs::write(git.join("refs/heads/main"), "ddd\n").unwrap();
+        write_head(&git, "ref: refs/heads/main");
+        let change = tracker.observe().expect("one change after the rebase");
+        assert_eq!(
+            change.triggers,
+            vec![GitTrigger::HeadChange, GitTrigger::Commit]
+        );
+        assert_eq!(change.transition.before, "aaa");
+        assert_eq!(change.transition.after, "ddd");
+        assert_eq!(tracker.observe(), None);
+    }
+
+    #[test]
+    fn tracker_reports_branch_switch_as_head_change_only() {
+        let root = scratch("switch");
+        let git = root.join(".git");
+        std::fs::create_dir_all(git.join("refs/heads")).unwrap();
+        write_head(&git, "ref: refs/heads/main");
+        std::fs::write(git.join("refs/heads/main"), "aaa\n").unwrap();
+        std::fs::write(
+            git.join("packed-refs"),
+            "# pack-refs\nccc refs/heads/topic\n",
+        )
+        .unwrap();
+        let mut tracker = GitTracker::new(Some(git.clone()));
+
+        write_head(&git, "ref: refs/heads/topic");
+        let change = tracker.observe().expect("head moved");
+        assert_eq!(change.triggers, vec![GitTrigger::HeadChange]);
+        assert_eq!(change.transition.after, "ccc");
+    }
+
+    #[test]
+    fn tracker_reports_index_rewrites() {
+        let root = scratch("index");
+        let git = root.join(".git");
+        write_head(&git, "0123abc");
+        let mut tracker = GitTracker::new(Some(git.clone()));
+
+        std::fs::write(git.join("index"), "DIRC").unwrap();
+        let change = tracker.observe().expect("index written");
+        assert_eq!(change.triggers, vec![GitTrigger::IndexChange]);
+        assert_eq!(change.transition.before, "0123abc");
+        assert_eq!(change.transition.after, "0123abc");
+    }
+}
@@ -0,0 +1,172 @@
+//! Cross-process named locks (`jobs[].global_lock`).
+//!
+//! Each lock is an `flock`ed file under `${XDG_STATE_HOME}/funzzy/locks`,
+//! shared by every funzzy process of the user: two watchers in sibling
+//! worktrees that build into one target directory take turns. The holder
+//! writes its identity into the file so a waiting instance can say who it
+//! waits for. The kernel releases the lock when the holder's file closes,
+//! so a crashed holder never leaves a stale lock behind.
+//!
+//! Acquisition never blocks: the executor polls `try_acquire` from its
+//! dispatch loop, which keeps cancellation of a waiting job trivial.
+
+use crate::duration_store::default_state_dir;
+use nix::errno::Errno;
+use nix::fcntl::{Flock, FlockArg};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Who holds a lock, as written into the lock file.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Holder {
+    pub instance: String,
+    pub job: String,
+    pub pid: u32,
+}
+
+impl std::fmt::Display for Holder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} (pid {}, instance {})",
+            self.job, self.pid, self.instance
+        )
+    }
+}
+
+/// A held lock; dropping it releases the lock.
+#[derive(Debug)]
+pub struct GlobalLock {
+    _file: Flock<fs::File>,
+}
+
+#[derive(Debug)]
+pub enum Acquire {
+    Held(GlobalLock),
+    /// Another holder has it; `None` when its identity could not be read.
+    Busy(Option<Holder>),
+}
+
+static INSTANCE: OnceLock<String> = OnceLock::new();
+
+/// Names this process in lock files; the watcher passes its instance token.
+pub fn set_instance(token: &str) {
+    let _ = INSTANCE.set(token.to_owned());
+}
+
+/// The instance name written by this process: the watcher token, or
+/// `pid-N` for finite commands that have none.
+pub fn instance() -> String {
+    INSTANCE
+        .get()
+        .cloned()
+        .unwrap_or_else(|| format!("pid-{}", std::process::id()))
+}
+
+/// Directory holding every lock file.
+pub fn lock_dir() -> PathBuf {
+    default_state_dir().join("funzzy").join("locks")
+}
+
+/// Lock names become file names, so they are limited to a portable set.
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+/// Takes lock `name` in `dir` for `job` without waiting.
+pub fn try_acquire(dir: &Path, name: &str, job: &str) -> Result<Acquire, String> {
+    fs::create_dir_all(dir).map_err(|err| format!("cannot create {}: {}", dir.display(), err))?;
+    let path = dir.join(format!("{name}.lock"));
+    let file = open(&path).map_err(|err| format!("cannot open {}: {}", path.display(), err))?;
+    match Flock::lock(file, FlockArg::LockExclusiveNonblock) {
+        Ok(mut file) => {
+            let holder = Holder {
+                instance: instance(),
+                job: job.to_owned(),
+                pid: std::process::id(),
+            };
+            // The identity is informational; a failed write keeps the lock.
+            let line = serde_json::to_string(&holder).unwrap_or_default();
+            let _ = file
+                .set_len(0)
+                .and_then(|()| writeln!(&mut *file, "{}", line));
+            Ok(Acquire::Held(GlobalLock { _file: file }))
+        }
+        Err((_, Errno::EWOULDBLOCK)) => Ok(Acquire::Busy(read_holder(&path))),
+        Err((_, errno)) => Err(format!("cannot lock {}: {}", path.display(), errno)),
+    }
+}
+
+fn read_holder(path: &Path) -> Option<Holder> {
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str(content.trim()).ok()
+}
+
+fn open(path: &Path) -> std::io::Result<fs::File> {
+    use std::os::unix::fs::OpenOptionsExt;
+    fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .mode(0o600)
+        .open(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "funzzy-global-lock-{}-{}",
+            std::process::id(),
+            name
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn second_holder_is_busy_until_the_first_drops() {
+        let dir = temp_dir("busy");
+        let first = match try_acquire(&dir, "target", "build").unwrap() {
+            Acquire::Held(lock) => lock,
+            Acquire::Busy(_) => panic!("free lock must be acquired"),
+        };
+        match try_acquire(&dir, "target", "test").unwrap() {
+            Acquire::Busy(Some(holder)) => {
+                assert_eq!(holder.job, "build");
+                assert_eq!(holder.pid, std::process::id());
+                assert_eq!(holder.instance, instance());
+            }
+            other => panic!("expected a busy lock, got {other:?}"),
+        }
+        // Other names are independent.
+        assert!(matches!(
+            try_acquire(&dir, "db", "test").unwrap(),
+            Acquire::Held(_)
+        ));
+
+        drop(first);
+        assert!(matches!(
+            try_acquire(&dir, "target", "test").unwrap(),
+            Acquire::Held(_)
+        ));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn names_are_limited_to_file_safe_characters() {
+        assert!(is_valid_name("cargo-target_dir.v2"));
+        for name in ["", ".hidden", "a/b", "../x", "a b"] {
+            assert!(!is_valid_name(name), "{name:?} must be rejected");
+        }
+    }
+}
@@ -15,17 +15,23 @@ pub mod diagnostics;
 pub mod duration_history;
 pub mod duration_recorder;
 pub mod duration_store;
+pub mod editor_noise;
 pub mod environment;
 pub mod errors;
 pub mod event_stream;
+pub mod event_trace;
 pub mod executor;
+pub mod git_state;
 pub mod gitignore;
+pub mod global_lock;
 pub mod identity;
 pub mod logging;
+pub mod matchers;
 pub mod option_catalog;
 pub mod output;
 pub mod plan;
 pub mod process_owner;
+pub mod readiness;
 pub mod reload;
 pub mod reload_coordinator;
 pub mod reload_session;
@@ -34,6 +40,7 @@ pub mod shutdown;
 pub mod snapshot;
 pub mod stdout;
 pub mod template;
+pub mod test_reports;
 pub mod watch_loop;
 pub mod watcher;
 pub mod watcher_state;
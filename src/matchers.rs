@@ -0,0 +1,357 @@
+//! Problem matchers (`jobs[].matchers`): structured diagnostics extracted
+//! from a job's captured output, so agents read `{file, line, column,
+//! severity, message, code}` records instead of parsing compiler text.
+//!
+//! A matcher is one or more regexes with named groups. Each runs over a
+//! whole retained stream in multi-line mode, so one pattern may span lines —
+//! rustc prints the location on the line under the message. A pattern
+//! without a `message` group only names the file for the problem lines after
+//! it, the way eslint's default report groups problems under a file header.
+//! Matching reads the raw capture: timestamps never reach a matcher.
+
+use crate::cmd::CaptureData;
+use crate::rules::{ProblemMatcher, Severity};
+use regex::{Regex, RegexBuilder};
+use serde::{Serialize, Serializer};
+use std::borrow::Cow;
+use std::sync::OnceLock;
+
+/// Built-in matcher names accepted by `jobs[].matchers`.
+pub const BUILTINS: &[&str] = &["rustc", "tsc", "eslint", "go-vet", "pytest"];
+
+/// Diagnostics kept per task and per generation; the rest are dropped and
+/// the list is marked truncated.
+pub const MAX_DIAGNOSTICS: usize = 200;
+
+/// `error[E0308]: mismatched types` followed by `--> src/main.rs:4:18`;
+/// cargo and clippy print the same shape.
+const RUSTC: &str = r"^(?P<severity>error|warning)(?:\[(?P<code>[A-Za-z]+\d*)\])?: (?P<message>.+)\r?\n\s*--> (?P<file>[^\n:]+):(?P<line>\d+):(?P<column>\d+)";
+
+/// `src/a.ts(3,5): error TS2322: message`.
+const TSC: &str = r"^(?P<file>[^\s(][^\n(]*)\((?P<line>\d+),(?P<column>\d+)\): (?P<severity>error|warning) (?P<code>TS\d+): (?P<message>.+)$";
+
+/// `src/a.ts:3:5 - error TS2322: message` (`--pretty`).
+const TSC_PRETTY: &str = r"^(?P<file>[^\s:][^\n:]*):(?P<line>\d+):(?P<column>\d+) - (?P<severity>error|warning) (?P<code>TS\d+): (?P<message>.+)$";
+
+/// File header of eslint's default `stylish` report.
+const ESLINT_FILE: &str = r"^(?P<file>\S[^\n]*\.(?:[cm]?[jt]sx?|vue|svelte|astro))$";
+
+/// `  3:5  error  'x' is unused  no-unused-vars` under a file header.
+const ESLINT_PROBLEM: &str = r"^\s+(?P<line>\d+):(?P<column>\d+)\s+(?P<severity>error|warning)\s+(?P<message>.+?)(?:\s{2,}(?P<code>[@\w/-]+))?$";
+
+/// `src/a.js:3:5: 'x' is unused [Error/no-unused-vars]` (`--format unix`).
+const ESLINT_UNIX: &str = r"^(?P<file>[^\s:][^\n:]*):(?P<line>\d+):(?P<column>\d+): (?P<message>.+?) \[(?P<severity>Error|Warning)(?:/(?P<code>[^\]]+))?\]$";
+
+/// `./main.go:10:2: printf: bad format` and the `vet: ` prefixed form.
+const GO_VET: &str =
+    r"^(?:vet: )?(?P<file>[^\s:][^\n:]*\.go):(?P<line>\d+)(?::(?P<column>\d+))?: (?P<message>.+)$";
+
+/// `tests/test_a.py:12: AssertionError` from a failure's traceback.
+const PYTEST: &str = r"^(?P<file>[^\s:][^\n:]*\.py):(?P<line>\d+): (?P<message>(?:\w+\.)*\w*(?:Error|Exception|Failed|Interrupt)\b.*)$";
+
+/// One extracted problem.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Diagnostic {
+    pub task: String,
+    /// Built-in matcher name, or `custom` for a `regex` matcher.
+    pub matcher: String,
+    pub file: Option<String>,
+    pub line: Option<u64>,
+    pub column: Option<u64>,
+    #[serde(serialize_with = "serialize_severity")]
+    pub severity: Severity,
+    pub message: String,
+    pub code: Option<String>,
+}
+
+fn serialize_severity<S: Serializer>(
+    severity: &Severity,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(severity.label())
+}
+
+/// A compiled problem matcher.
+#[derive(Clone, Debug)]
+pub struct Matcher {
+    name: String,
+    patterns: Vec<Regex>,
+    severity: Severity,
+}
+
+impl Matcher {
+    /// Compiles a configured matcher; None for an unknown built-in or an
+    /// invalid regex, both of which config validation already rejects.
+    pub fn new(spec: &ProblemMatcher) -> Option<Self> {
+        let (patterns, severity) = match spec {
+            ProblemMatcher::Builtin(name) => (builtin_patterns(name)?.to_vec(), Severity::Error),
+            ProblemMatcher::Pattern { regex, severity } => (vec![regex.as_str()], *severity),
+        };
+        let patterns = patterns
+            .into_iter()
+            .map(|pattern| compile(pattern).ok())
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self {
+            name: spec.name().to_owned(),
+            patterns,
+            severity,
+        })
+    }
+
+    /// Every problem in `text`, with its byte offset for ordering.
+    fn scan(&self, task: &str, text: &str) -> Vec<(usize, Diagnostic)> {
+        let mut headers: Vec<(usize, String)> = vec![];
+        let mut problems: Vec<(usize, Diagnostic)> = vec![];
+        for pattern in &self.patterns {
+            let headers_only = !pattern
+                .capture_names()
+                .flatten()
+                .any(|name| name == "message");
+            for captures in pattern.captures_iter(text) {
+                let offset = captures.get(0).map_or(0, |found| found.start());
+                let group = |name: &str| {
+                    captures
+                        .name(name)
+                        .map(|found| found.as_str().trim())
+                        .filter(|value| !value.is_empty())
+                };
+                if headers_only {
+                    if let Some(file) = group("file") {
+                        headers.push((offset, file.to_owned()));
+                    }
+                    continue;
+                }
+                let Some(message) = group("message") else {
+                    continue;
+                };
+                problems.push((
+                    offset,
+                    Diagnostic {
+                        task: task.to_owned(),
+                        matcher: self.name.clone(),
+                        file: group("file").map(str::to_owned),
+                        line: group("line").and_then(|line| line.parse().ok()),
+                        column: group("column").and_then(|column| column.parse().ok()),
+                        severity: group("severity")
+                            .and_then(Severity::parse)
+                            .unwrap_or(self.severity),
+                        message: message.to_owned(),
+                        code: group("code").map(str::to_owned),
+                    },
+                ));
+            }
+        }
+        headers.sort();
+        for (offset, problem) in &mut problems {
+            if problem.file.is_none() {
+                problem.file = headers
+                    .iter()
+                    .rev()
+                    .find(|(header, _)| header < offset)
+                    .map(|(_, file)| file.clone());
+            }
+        }
+        problems
+    }
+}
+
+/// Checks a `regex` matcher: it must compile and capture a `message`.
+pub fn validate_pattern(pattern: &str) -> Result<(), String> {
+    let regex = compile(pattern).map_err(|error| error.to_string())?;
+    if !regex
+        .capture_names()
+        .flatten()
+        .any(|name| name == "message")
+    {
+        return Err("it has no named `message` group, e.g. (?P<message>.+)".to_owned());
+    }
+    Ok(())
+}
+
+fn compile(pattern: &str) -> Result<Regex, regex::Error> {
+    RegexBuilder::new(pattern).multi_line(true).build()
+}
+
+fn builtin_patterns(name: &str) -> Option<&'static [&'static str]> {
+    Some(match name {
+        "rustc" => &[RUSTC],
+        "tsc" => &[TSC, TSC_PRETTY],
+        "eslint" => &[ESLINT_FILE, ESLINT_PROBLEM, ESLINT_UNIX],
+        "go-vet" => &[GO_VET],
+        "pytest" => &[PYTEST],
+        _ => return None,
+    })
+}
+
+/// Runs `matchers` over a task's retained stdout, then stderr, in output
+/// order. Returns at most [`MAX_DIAGNOSTICS`] distinct diagnostics and
+/// whether any were dropped.
+pub fn extract(matchers: &[Matcher], task: &str, data: &CaptureData) -> (Vec<Diagnostic>, bool) {
+    let mut found: Vec<Diagnostic> = vec![];
+    let mut truncated = false;
+    for buffer in [&data.stdout, &data.stderr] {
+        if matchers.is_empty() || buffer.bytes().is_empty() {
+            continue;
+        }
+        let raw = String::from_utf8_lossy(buffer.bytes());
+        let text = strip_ansi(&raw);
+        let mut problems: Vec<(usize, Diagnostic)> = matchers
+            .iter()
+            .flat_map(|matcher| matcher.scan(task, &text))
+            .collect();
+        problems.sort_by_key(|(offset, _)| *offset);
+        for (_, problem) in problems {
+            if found.contains(&problem) {
+                continue;
+            }
+            if found.len() == MAX_DIAGNOSTICS {
+                truncated = true;
+                break;
+            }
+            found.push(problem);
+        }
+    }
+    (found, truncated)
+}
+
+/// Drops terminal color sequences, which tools emit under `tty: true`.
+fn strip_ansi(text: &str) -> Cow<'_, str> {
+    static ESCAPES: OnceLock<Regex> = OnceLock::new();
+    ESCAPES
+        .get_or_init(|| Regex::new(r"\x1b\[[0-9;?]*[A-Za-z]").expect("valid escape pattern"))
+        .replace_all(text, "")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cmd::CaptureHandle;
+
+    fn stderr(text: &str) -> CaptureData {
+        let capture = CaptureHandle::new();
+        capture.append(text.as_bytes(), true);
+        capture.finish()
+    }
+
+    fn builtin(name: &str) -> Vec<Matcher> {
+        vec![Matcher::new(&ProblemMatcher::Builtin(name.to_owned())).expect("built-in")]
+    }
+
+    fn located(diagnostic: &Diagnostic) -> (Option<&str>, Option<u64>, Option<u64>) {
+        (
+            diagnostic.file.as_deref(),
+            diagnostic.line,
+            diagnostic.column,
+        )
+    }
+
+    #[test]
+    fn rustc_messages_take_their_location_from_the_next_line() {
+        let output = "   Compiling app v0.1.0\n\
+error[E0308]: mismatched types\n  --> src/main.rs:4:18\n   |\n\
+warning: unused variable: `x`\n \x1b[1m-->\x1b[0m src/lib.rs:9:9\n\
+error: could not compile `app`\n";
+        let (found, truncated) = extract(&builtin("rustc"), "build", &stderr(output));
+
+        assert!(!truncated);
+        assert_eq!(found.len(), 2, "{found:?}");
+        assert_eq!(located(&found[0]), (Some("src/main.rs"), Some(4), Some(18)));
+        assert_eq!(found[0].severity, Severity::Error);
+        assert_eq!(found[0].code.as_deref(), Some("E0308"));
+        assert_eq!(found[0].message, "mismatched types");
+        assert_eq!(located(&found[1]), (Some("src/lib.rs"), Some(9), Some(9)));
+        assert_eq!(found[1].severity, Severity::Warning);
+        assert_eq!(found[1].task, "build");
+        assert_eq!(found[1].matcher, "rustc");
+    }
+
+    #[test]
+    fn eslint_problems_take_their_file_from_the_header_above() {
+        let output = "\n/repo/src/a.js\n  3:5   error    'x' is assigned a value but never used  no-unused-vars\n  7:1   warning  Unexpected console statement            no-console\n\n\
+/repo/src/b.ts\n  1:10  error  Missing semicolon  semi\n\n✖ 3 problems (2 errors, 1 warning)\n";
+        let (found, _) = extract(&builtin("eslint"), "lint", &stderr(output));
+
+        assert_eq!(found.len(), 3, "{found:?}");
+        assert_eq!(
+            located(&found[0]),
+            (Some("/repo/src/a.js"), Some(3), Some(5))
+        );
+        assert_eq!(found[0].code.as_deref(), Some("no-unused-vars"));
+        assert_eq!(found[0].message, "'x' is assigned a value but never used");
+        assert_eq!(found[1].severity, Severity::Warning);
+        assert_eq!(
+            located(&found[2]),
+            (Some("/repo/src/b.ts"), Some(1), Some(10))
+        );
+        assert_eq!(found[2].code.as_deref(), Some("semi"));
+    }
+
+    #[test]
+    fn tsc_go_vet_and_pytest_lines_are_matched() {
+        let tsc = extract(
+            &builtin("tsc"),
+            "types",
+            &stderr("src/a.ts(3,5): error TS2322: Type 'string' is not assignable to type 'number'.\nsrc/b.ts:8:1 - error TS1005: ';' expected.\n"),
+        )
+        .0;
+        assert_eq!(tsc.len(), 2, "{tsc:?}");
+        assert_eq!(located(&tsc[0]), (Some("src/a.ts"), Some(3), Some(5)));
+        assert_eq!(tsc[1].code.as_deref(), Some("TS1005"));
+
+        let vet = extract(
+            &builtin("go-vet"),
+            "vet",
+            &stderr("# app\nvet: ./main.go:10:2: fmt.Println call has possible Printf formatting directive %d\n"),
+        )
+        .0;
+        assert_eq!(vet.len(), 1, "{vet:?}");
+        assert_eq!(located(&vet[0]), (Some("./main.go"), Some(10), Some(2)));
+        assert_eq!(vet[0].severity, Severity::Error);
+
+        let pytest = extract(
+            &builtin("pytest"),
+            "tests",
+            &stderr("    def test_sum():\n>       assert 1 == 2\nE       assert 1 == 2\n\ntests/test_sum.py:4: AssertionError\n"),
+        )
+        .0;
+        assert_eq!(pytest.len(), 1, "{pytest:?}");
+        assert_eq!(
+            located(&pytest[0]),
+            (Some("tests/test_sum.py"), Some(4), None)
+        );
+        assert_eq!(pytest[0].message, "AssertionError");
+    }
+
+    #[test]
+    fn custom_patterns_use_named_groups_and_a_default_severity() {
+        let matcher = Matcher::new(&ProblemMatcher::Pattern {
+            regex: r"^lint: (?P<file>[^:]+):(?P<line>\d+) (?P<message>.+)$".to_owned(),
+            severity: Severity::Warning,
+        })
+        .expect("valid pattern");
+        let (found, _) = extract(
+            &[matcher],
+            "lint",
+            &stderr("lint: a.md:3 trailing space\nok\n"),
+        );
+
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].matcher, "custom");
+        assert_eq!(found[0].severity, Severity::Warning);
+        assert_eq!(located(&found[0]), (Some("a.md"), Some(3), None));
+        assert!(validate_pattern(r"^(?P<file>\S+)$").is_err());
+        assert!(validate_pattern(r"(?P<message>.+").is_err());
+    }
+
+    #[test]
+    fn diagnostics_are_bounded_and_marked_truncated() {
+        let output: String = (0..MAX_DIAGNOSTICS + 5)
+            .map(|line| format!("./main.go:{}:1: problem\n", line + 1))
+            .collect();
+        let (found, truncated) = extract(&builtin("go-vet"), "vet", &stderr(&output));
+
+        assert_eq!(found.len(), MAX_DIAGNOSTICS);
+        assert!(truncated);
+    }
+}
@@ -29,6 +29,21 @@ pub enum SpecKind {
     Duration,
     Enum(&'static [&'static str]),
     StringMap,
+    /// Name -> positive integer.
+    IntMap,
+    /// Integer within an inclusive range.
+    Range(i64, i64),
+    /// One integer or a list of integers within an inclusive range.
+    RangeList(i64, i64),
+    /// Mapping restricted to the listed keys; values are scalars such as
+    /// sizes, durations, counts or commands.
+    Fields(&'static [&'static str]),
+    /// One name or a list whose items are names from the first slice or
+    /// mappings restricted to the keys of the second.
+    NamesOrFields(&'static [&'static str], &'static [&'static str]),
+    /// One command or a list whose items are commands or
+    /// `{ run, continue_on_error }` steps.
+    Steps,
 }
 
 /// One catalog entry. `example` holds raw YAML lines without leading
@@ -44,9 +59,21 @@ pub struct OptionSpec {
     pub kind: SpecKind,
 }
 
-const OUTPUT_VALUES: &[&str] = &["inherit", "quiet", "capture", "show-on-failure"];
-const BACKEND_VALUES: &[&str] = &["native", "poll", "auto"];
+const OUTPUT_VALUES: &[&str] = &["inherit", "quiet", "capture", "show-on-failure", "grouped"];
+const BACKEND_VALUES: &[&str] = &["native", "poll", "auto", "stdin"];
 const RECOVERY_POLICY_VALUES: &[&str] = &["prompt", "skip"];
+/// Keys accepted under `jobs[].limits`.
+pub const LIMIT_FIELDS: &[&str] = &["memory", "cpu", "open_files", "core"];
+/// Keys accepted under `jobs[].ready`: one probe plus `timeout`.
+pub const READY_FIELDS: &[&str] = &["tcp", "log", "file", "command", "timeout"];
+/// Keys accepted under `jobs[].restart`.
+pub const RESTART_FIELDS: &[&str] = &["max", "backoff", "delay", "reset_after"];
+/// Keys accepted under `jobs[].health`: one probe plus its schedule.
+pub const HEALTH_FIELDS: &[&str] = &["tcp", "file", "command", "interval", "failures"];
+/// Keys accepted under `jobs[].reports`.
+pub const REPORT_FIELDS: &[&str] = &["junit", "tap"];
+/// Keys of a custom `jobs[].matchers` entry.
+pub const MATCHER_FIELDS: &[&str] = &["regex", "severity"];
 
 /// Ordered `on:` properties — order is stable and defines comment/schema order
 /// (INIT-TEMPLATE-CONTRACT §8).
@@ -97,7 +124,7 @@ const ON_SPECS: &[OptionSpec] = &[
         required: false,
         default: Some("auto"),
         help: "Watch backend: native first, then poll when native is unavailable.",
-        values: Some("native | poll | auto"),
+        values: Some("native | poll | auto | stdin"),
         example: &["watch_backend: poll"],
         kind: SpecKind::Enum(BACKEND_VALUES),
     },
@@ -111,6 +138,36 @@ const ON_SPECS: &[OptionSpec] = &[
         example: &["poll_interval: 200ms"],
         kind: SpecKind::Duration,
     },
+    OptionSpec {
+        name: "event_source",
+        owner: Owner::On,
+        required: false,
+        default: None,
+        help: "Read change events from another watcher instead of the filesystem.",
+        values: Some("stdin | fifo:PATH"),
+        example: &["event_source: fifo:.tmp/funzzy.events"],
+        kind: SpecKind::String,
+    },
+    OptionSpec {
+        name: "settle",
+        owner: Owner::On,
+        required: false,
+        default: None,
+        help: "Wait until changed files keep the same size and mtime for this long before running.",
+        values: None,
+        example: &["settle: 500ms"],
+        kind: SpecKind::Duration,
+    },
+    OptionSpec {
+        name: "settle_max",
+        owner: Owner::On,
+        required: false,
+        default: Some("30s"),
+        help: "Upper bound on one batch's settle wait (only meaningful with `settle`).",
+        values: None,
+        example: &["settle_max: 2m"],
+        kind: SpecKind::Duration,
+    },
     OptionSpec {
         name: "respect_gitignore",
         owner: Owner::On,
@@ -121,6 +178,26 @@ const ON_SPECS: &[OptionSpec] = &[
         example: &["respect_gitignore: true"],
         kind: SpecKind::Bool,
     },
+    OptionSpec {
+        name: "editor_noise",
+        owner: Owner::On,
+        required: false,
+        default: Some("true"),
+        help: "Suppress editor swap/backup/temp files; atomic saves route as the real file.",
+        values: None,
+        example: &["editor_noise: false"],
+        kind: SpecKind::Bool,
+    },
+    OptionSpec {
+        name: "git",
+        owner: Owner::On,
+        required: false,
+        default: None,
+        help: "Repository events that run every job (merged first).",
+        values: Some("head_change | commit | index_change"),
+        example: &["git: head_change"],
+        kind: SpecKind::StringList,
+    },
 ];
 
 const EXECUTION_SPECS: &[OptionSpec] = &[
@@ -140,7 +217,7 @@ const EXECUTION_SPECS: &[OptionSpec] = &[
         required: false,
         default: Some("inherit"),
         help: "Default output policy for every job.",
-        values: Some("inherit | quiet | capture | show-on-failure"),
+        values: Some("inherit | quiet | capture | show-on-failure | grouped"),
         example: &["output: quiet"],
         kind: SpecKind::Enum(OUTPUT_VALUES),
     },
@@ -154,6 +231,26 @@ const EXECUTION_SPECS: &[OptionSpec] = &[
         example: &["recovery_policy: prompt"],
         kind: SpecKind::Enum(RECOVERY_POLICY_VALUES),
     },
+    OptionSpec {
+        name: "resources",
+        owner: Owner::Execution,
+        required: false,
+        default: None,
+        help: "Capacity of each named lock: how many jobs holding it may run at once (undeclared locks are exclusive).",
+        values: None,
+        example: &["resources: { db: 1, gpu: 2 }"],
+        kind: SpecKind::IntMap,
+    },
+    OptionSpec {
+        name: "timestamps",
+        owner: Owner::Execution,
+        required: false,
+        default: Some("none"),
+        help: "Prefix each output line with the UTC time it was printed or the time since its job started; --timestamps overrides it.",
+        values: Some("wall | elapsed | none"),
+        example: &["timestamps: elapsed"],
+        kind: SpecKind::Enum(crate::rules::Timestamps::VALUES),
+    },
 ];
 
 const HOOK_SPECS: &[OptionSpec] = &[
@@ -206,10 +303,10 @@ const JOB_SPECS: &[OptionSpec] = &[
         owner: Owner::Job,
         required: true,
         default: None,
-        help: "Command(s): a shell string or an argv list. Template variables: {{filepath}}, {{paths}}, {{relative_filepath}}.",
+        help: "Command(s) or { run, continue_on_error } steps. Template variables: {{filepath}}, {{paths}}, {{relative_filepath}}.",
         values: None,
         example: &["run: [\"echo\", \"{{filepath}}\", \"{{paths}}\"]"],
-        kind: SpecKind::StringList,
+        kind: SpecKind::Steps,
     },
     OptionSpec {
         name: "recovery",
@@ -297,10 +394,210 @@ const JOB_SPECS: &[OptionSpec] = &[
         required: false,
         default: None,
         help: "Job output policy override; inherits execution.output when absent.",
-        values: Some("inherit | quiet | capture | show-on-failure"),
+        values: Some("inherit | quiet | capture | show-on-failure | grouped"),
         example: &["output: show-on-failure"],
         kind: SpecKind::Enum(OUTPUT_VALUES),
     },
+    OptionSpec {
+        name: "git",
+        owner: Owner::Job,
+        required: false,
+        default: None,
+        help: "Repository events that run this job; commands see {{git.before}} and {{git.after}}.",
+        values: Some("head_change | commit | index_change"),
+        example: &["git: [head_change, commit]"],
+        kind: SpecKind::StringList,
+    },
+    OptionSpec {
+        name: "locks",
+        owner: Owner::Job,
+        required: false,
+        default: None,
+        help: "Named resources held while the job runs; jobs sharing a lock never overlap beyond its capacity.",
+        values: None,
+        example: &["locks: [db]"],
+        kind: SpecKind::StringList,
+    },
+    OptionSpec {
+        name: "global_lock",
+        owner: Owner::Job,
+        required: false,
+        default: None,
+        help: "Lock shared with every funzzy process of the user; jobs naming it wait for each other across watchers.",
+        values: None,
+        example: &["global_lock: cargo-target"],
+        kind: SpecKind::String,
+    },
+    OptionSpec {
+        name: "limits",
+        owner: Owner::Job,
+        required: false,
+        default: None,
+        help: "Resource limits for the job's processes: memory (address space), cpu (time), open_files and core (dump size).",
+        values: None,
+        example: &["limits: { memory: 4G, cpu: 10m, open_files: 1024, core: 0 }"],
+        kind: SpecKind::Fields(LIMIT_FIELDS),
+    },
+    OptionSpec {
+        name: "nice",
+        owner: Owner::Job,
+        required: false,
+        default: None,
+        help: "CPU scheduling priority of the job's processes; higher is nicer.",
+        values: Some("-20..19"),
+        example: &["nice: 10"],
+        kind: SpecKind::Range(-20, 19),
+    },
+    OptionSpec {
+        name: "ionice",
+        owner: Owner::Job,
+        required: false,
+        default: None,
+        help: "I/O scheduling class of the job's processes (Linux only).",
+        values: Some("idle | best-effort | best-effort:0-7"),
+        example: &["ionice: idle"],
+        kind: SpecKind::String,
+    },
+    OptionSpec {
+        name: "tty",
+        owner: Owner::Job,
+        required: false,
+        default: Some("false"),
+        help: "Run under a pseudo-terminal so tools keep colors and progress output; retained output is stripped of ANSI codes.",
+        values: None,
+        example: &["tty: true"],
+        kind: SpecKind::Bool,
+    },
+    OptionSpec {
+        name: "interactive",
+        owner: Owner::Job,
+        required: false,
+        default: Some("false"),
+        help: "Give the job the terminal (keyboard input and Ctrl-C) while it runs; serial and service jobs only.",
+        values: None,
+        example: &["interactive: true"],
+        kind: SpecKind::Bool,
+    },
+    OptionSpec {
+        name: "ready",
+        owner: Owner::Job,
+        required: false,
+        default: None,
+        help: "Service readiness probe gating later jobs: tcp port, log regex, file, or command; timeout defaults to 30s.",
+        values: None,
+        example: &["service: true", "ready: { tcp: 3000, timeout: 60s }"],
+        kind: SpecKind::Fields(READY_FIELDS),
+    },
+    OptionSpec {
+        name: "restart",
+        owner: Owner::Job,
+        required: false,
+        default: None,
+        help: "Service restarts after an unexpected exit: max (3), backoff fixed | exponential, delay (500ms), reset_after.",
+        values: None,
+        example: &["service: true", "restart: { max: 10, backoff: exponential, reset_after: 1m }"],
+        kind: SpecKind::Fields(RESTART_FIELDS),
+    },
+    OptionSpec {
+        name: "health",
+        owner: Owner::Job,
+        required: false,
+        default: None,
+        help: "Periodic service health check (tcp, file or command); failures (3) checks in a row every interval (10s) restart it.",
+        values: None,
+        example: &["service: true", "health: { command: curl -sf localhost:3000/health, interval: 10s }"],
+        kind: SpecKind::Fields(HEALTH_FIELDS),
+    },
+    OptionSpec {
+        name: "on_change",
+        owner: Owner::Job,
+        required: false,
+        default: Some("restart"),
+        help: "What a matching change does to the running service: restart it, signal its process group, run a reload command, or nothing.",
+        values: Some("restart | signal:NAME | command:CMD | ignore"),
+        example: &["service: true", "on_change: signal:HUP"],
+        kind: SpecKind::String,
+    },
+    OptionSpec {
+        name: "stop",
+        owner: Owner::Job,
+        required: false,
+        default: None,
+        help: "Shell commands run before the service's processes are signalled on restart, removal and close; bounded to 30s.",
+        values: None,
+        example: &["service: true", "stop: docker compose down"],
+        kind: SpecKind::StringList,
+    },
+    OptionSpec {
+        name: "stop_signal",
+        owner: Owner::Job,
+        required: false,
+        default: Some("SIGTERM"),
+        help: "Signal sent to the job's processes on cancel, restart, reload and close.",
+        values: Some("SIGTERM | SIGINT | SIGHUP | SIGQUIT | SIGUSR1 | SIGUSR2"),
+        example: &["stop_signal: SIGINT"],
+        kind: SpecKind::Enum(crate::config::STOP_SIGNALS),
+    },
+    OptionSpec {
+        name: "stop_grace",
+        owner: Owner::Job,
+        required: false,
+        default: Some("5s"),
+        help: "How long a stopping job may take before it is force-killed.",
+        values: None,
+        example: &["stop_grace: 20s"],
+        kind: SpecKind::Duration,
+    },
+    OptionSpec {
+        name: "matchers",
+        owner: Owner::Job,
+        required: false,
+        default: None,
+        help: "Problem matchers turning the job's output into diagnostics: built-in names or { regex, severity } with named groups file, line, column, severity, message, code.",
+        values: Some("rustc | tsc | eslint | go-vet | pytest | { regex, severity }"),
+        example: &["matchers: [rustc]"],
+        kind: SpecKind::NamesOrFields(crate::matchers::BUILTINS, MATCHER_FIELDS),
+    },
+    OptionSpec {
+        name: "reports",
+        owner: Owner::Job,
+        required: false,
+        default: None,
+        help: "Test reports read when the job finishes: junit (glob of JUnit XML files written during the run) and tap: stdout.",
+        values: None,
+        example: &["reports: { junit: 'target/nextest/**/junit.xml' }"],
+        kind: SpecKind::Fields(REPORT_FIELDS),
+    },
+    OptionSpec {
+        name: "success_codes",
+        owner: Owner::Job,
+        required: false,
+        default: Some("[0]"),
+        help: "Exit codes counted as success, e.g. a linter that exits 1 for warnings.",
+        values: None,
+        example: &["success_codes: [0, 1]"],
+        kind: SpecKind::RangeList(0, 255),
+    },
+    OptionSpec {
+        name: "allow_failure",
+        owner: Owner::Job,
+        required: false,
+        default: Some("false"),
+        help: "Report failures as warnings that never fail the run, stop it under --fail-fast or trigger hooks.failure.",
+        values: None,
+        example: &["allow_failure: true"],
+        kind: SpecKind::Bool,
+    },
+    OptionSpec {
+        name: "when",
+        owner: Owner::Job,
+        required: false,
+        default: Some("on_success"),
+        help: "Run unless fail-fast stopped the run (on_success), only after an earlier failure (on_failure), or even after fail-fast and cancellation (always).",
+        values: Some("on_success | on_failure | always"),
+        example: &["when: always"],
+        kind: SpecKind::Enum(crate::rules::When::VALUES),
+    },
 ];
 
 /// Preferred root keys (INIT-TEMPLATE-CONTRACT §3.1).
@@ -400,12 +697,23 @@ mod tests {
                 "debounce",
                 "watch_backend",
                 "poll_interval",
-                "respect_gitignore"
+                "event_source",
+                "settle",
+                "settle_max",
+                "respect_gitignore",
+                "editor_noise",
+                "git"
             ]
         );
         assert_eq!(
             property_names(Owner::Execution),
-            ["concurrency", "output", "recovery_policy"]
+            [
+                "concurrency",
+                "output",
+                "recovery_policy",
+                "resources",
+                "timestamps"
+            ]
         );
         assert_eq!(
             property_names(Owner::Hooks),
@@ -427,6 +735,26 @@ mod tests {
             "env",
             "service",
             "output",
+            "git",
+            "locks",
+            "global_lock",
+            "limits",
+            "nice",
+            "ionice",
+            "tty",
+            "interactive",
+            "ready",
+            "restart",
+            "health",
+            "on_change",
+            "stop",
+            "stop_signal",
+            "stop_grace",
+            "matchers",
+            "reports",
+            "success_codes",
+            "allow_failure",
+            "when",
         ];
         let actual: Vec<&str> = property_names(Owner::Job);
         assert_eq!(actual, expected);
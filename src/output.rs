@@ -9,7 +9,11 @@
 //! boundary, documented in the retrieval command's help).
 
 use crate::cmd::{CaptureBuffer, CaptureData};
+use crate::executor::StepSnapshot;
+use crate::matchers::{Diagnostic, MAX_DIAGNOSTICS};
+use crate::test_reports::TestSummary;
 use serde::Serialize;
+use std::borrow::Cow;
 use std::collections::VecDeque;
 use std::sync::Mutex;
 
@@ -41,6 +45,14 @@ pub struct TaskOutput {
     pub task: String,
     pub stdout: CaptureBuffer,
     pub stderr: CaptureBuffer,
+    /// Problem-matcher diagnostics (`jobs[].matchers`), and whether the
+    /// per-task bound dropped any.
+    pub diagnostics: Vec<Diagnostic>,
+    pub diagnostics_truncated: bool,
+    /// Test report summary (`jobs[].reports`).
+    pub tests: Option<TestSummary>,
+    /// Per-command results of a multi-command job.
+    pub steps: Vec<StepSnapshot>,
 }
 
 impl TaskOutput {
@@ -127,6 +139,10 @@ impl OutputRegistry {
             task,
             stdout: data.stdout,
             stderr: data.stderr,
+            diagnostics: vec![],
+            diagnostics_truncated: false,
+            tests: None,
+            steps: vec![],
         };
 
         let existing = inner
@@ -168,6 +184,112 @@ impl OutputRegistry {
         }
     }
 
+    /// Attaches a task's problem-matcher diagnostics to its recorded output.
+    /// Nothing is kept once the generation was evicted.
+    pub fn record_diagnostics(
+        &self,
+        generation: u64,
+        task: &str,
+        diagnostics: Vec<Diagnostic>,
+        truncated: bool,
+    ) {
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(task_output) = inner
+            .generations
+            .iter_mut()
+            .find(|entry| entry.generation == generation)
+            .and_then(|entry| entry.tasks.iter_mut().find(|output| output.task == task))
+        {
+            task_output.diagnostics = diagnostics;
+            task_output.diagnostics_truncated = truncated;
+        }
+    }
+
+    /// Attaches a task's test report summary to its recorded output.
+    pub fn record_tests(&self, generation: u64, task: &str, tests: TestSummary) {
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(task_output) = inner
+            .generations
+            .iter_mut()
+            .find(|entry| entry.generation == generation)
+            .and_then(|entry| entry.tasks.iter_mut().find(|output| output.task == task))
+        {
+            task_output.tests = Some(tests);
+        }
+    }
+
+    /// Attaches a multi-command task's step results to its recorded output;
+    /// their offsets locate each step's output in the retained streams.
+    pub fn record_steps(&self, generation: u64, task: &str, steps: Vec<StepSnapshot>) {
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(task_output) = inner
+            .generations
+            .iter_mut()
+            .find(|entry| entry.generation == generation)
+            .and_then(|entry| entry.tasks.iter_mut().find(|output| output.task == task))
+        {
+            task_output.steps = steps;
+        }
+    }
+
+    /// Diagnostics of one generation in task completion order, optionally
+    /// for one exact task, bounded by [`MAX_DIAGNOSTICS`] overall. An unknown
+    /// task lists the retained task IDs and is never guessed.
+    pub fn diagnostics(
+        &self,
+        generation: u64,
+        task: Option<&str>,
+    ) -> Result<GenerationDiagnostics, RetrievalError> {
+        let inner = self.inner.lock().unwrap();
+        let entry = inner
+            .generations
+            .iter()
+            .find(|entry| entry.generation == generation)
+            .ok_or_else(|| RetrievalError::GenerationNotFound {
+                retained: inner
+                    .generations
+                    .iter()
+                    .map(|entry| entry.generation)
+                    .collect(),
+            })?;
+        let wanted = task.map(str::trim).filter(|task| !task.is_empty());
+        if let Some(wanted) = wanted {
+            if !entry.tasks.iter().any(|output| output.task == wanted) {
+                return Err(RetrievalError::TaskNotFound {
+                    task: wanted.to_string(),
+                    ambiguous: false,
+                    candidates: entry
+                        .tasks
+                        .iter()
+                        .map(|output| output.task.clone())
+                        .collect(),
+                });
+            }
+        }
+
+        let mut result = GenerationDiagnostics {
+            generation,
+            revision: entry.revision,
+            diagnostics: vec![],
+            truncated: false,
+        };
+        for output in entry
+            .tasks
+            .iter()
+            .filter(|output| wanted.is_none_or(|wanted| output.task == wanted))
+        {
+            result.truncated |= output.diagnostics_truncated;
+            for diagnostic in &output.diagnostics {
+                if result.diagnostics.len() == MAX_DIAGNOSTICS {
+                    result.truncated = true;
+                    break;
+                }
+                result.diagnostics.push(diagnostic.clone());
+            }
+        }
+        Ok(result)
+    }
+
     /// Retrieves bounded output for one generation (contract §6): optional
     /// task and stream filters, `tail` = last N lines per stream, `full` =
     /// everything retained (still bounded by the global budget).
@@ -485,12 +607,26 @@ impl OutputRegistry {
                 continue;
             }
             let text = String::from_utf8_lossy(&buffer.bytes()[begin..]).into_owned();
+            // Timestamped lines get their prefix on the page; cursors and
+            // byte counts stay in raw bytes.
+            let content = |keep: usize| -> Cow<'_, str> {
+                if buffer.is_stamped() {
+                    Cow::Owned(buffer.stamped_text(begin, begin + keep))
+                } else {
+                    Cow::Borrowed(&text[..keep])
+                }
+            };
 
             // Try to append the whole remaining stream; if the serialized
             // page then exceeds the budget, trim to a char boundary and
             // leave a continuation cursor.
             let mut candidate = tasks.clone();
-            set_stream(&mut candidate, task_id, *is_stderr, &text, buffer);
+            set_stream(
+                &mut candidate,
+                task_output,
+                *is_stderr,
+                &content(text.len()),
+            );
             let page = RetrievedOutput {
                 generation,
                 revision: entry.revision,
@@ -521,7 +657,7 @@ impl OutputRegistry {
                 let mid = (lo + hi).div_ceil(2);
                 let keep = boundaries[mid];
                 let mut candidate = tasks.clone();
-                set_stream(&mut candidate, task_id, *is_stderr, &text[..keep], buffer);
+                set_stream(&mut candidate, task_output, *is_stderr, &content(keep));
                 let page = RetrievedOutput {
                     generation,
                     revision: entry.revision,
@@ -541,7 +677,7 @@ impl OutputRegistry {
             let keep = boundaries[lo];
             if keep > 0 {
                 let mut trimmed = tasks.clone();
-                set_stream(&mut trimmed, task_id, *is_stderr, &text[..keep], buffer);
+                set_stream(&mut trimmed, task_output, *is_stderr, &content(keep));
                 tasks = trimmed;
                 returned += keep;
             }
@@ -705,19 +841,26 @@ fn retrieved_task(
         id: task_output.task.clone(),
         stdout: render_stream(&task_output.stdout, stream, tail, full, false),
         stderr: render_stream(&task_output.stderr, stream, tail, full, true),
+        tests: task_output.tests.clone(),
+        steps: task_output.steps.clone(),
     }
 }
 
 /// Sets one stream's paged content on an existing (or freshly appended) task
-/// entry, carrying the stream's bounds metadata (contract §5).
+/// entry, carrying the stream's bounds metadata (contract §5). A fresh entry
+/// carries the task's test summary and steps like an unpaged retrieval.
 fn set_stream(
     tasks: &mut Vec<RetrievedTask>,
-    task_id: &str,
+    task_output: &TaskOutput,
     is_stderr: bool,
     content: &str,
-    buffer: &CaptureBuffer,
 ) {
-    if let Some(task) = tasks.iter_mut().find(|task| task.id == task_id) {
+    let buffer = if is_stderr {
+        &task_output.stderr
+    } else {
+        &task_output.stdout
+    };
+    if let Some(task) = tasks.iter_mut().find(|task| task.id == task_output.task) {
         let stream = stream_output(content, buffer);
         if is_stderr {
             task.stderr = Some(stream);
@@ -726,9 +869,11 @@ fn set_stream(
         }
     } else {
         let mut task = RetrievedTask {
-            id: task_id.to_owned(),
+            id: task_output.task.clone(),
             stdout: None,
             stderr: None,
+            tests: task_output.tests.clone(),
+            steps: task_output.steps.clone(),
         };
         let stream = stream_output(content, buffer);
         if is_stderr {
@@ -766,7 +911,11 @@ fn render_stream(
         Some(other) if other != "stdout" && other != "stderr" => return None,
         _ => {}
     }
-    let text = String::from_utf8_lossy(buffer.bytes());
+    let text = if buffer.is_stamped() {
+        Cow::Owned(buffer.stamped_text(0, buffer.bytes().len()))
+    } else {
+        String::from_utf8_lossy(buffer.bytes())
+    };
     let content = if full {
         text.to_string()
     } else {
@@ -802,6 +951,12 @@ pub struct RetrievedTask {
     pub id: String,
     pub stdout: Option<StreamOutput>,
     pub stderr: Option<StreamOutput>,
+    /// Test report summary; omitted when the job declares no `reports`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tests: Option<TestSummary>,
+    /// Step results of a multi-command job; omitted otherwise.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub steps: Vec<StepSnapshot>,
 }
 
 /// Retrieval domain result (contract §6): consumed by both the structured
@@ -835,6 +990,18 @@ pub struct RetrievedOutput {
     pub truncated: Option<bool>,
 }
 
+/// Problem-matcher diagnostics of one generation (`diagnostics` method).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GenerationDiagnostics {
+    pub generation: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revision: Option<u64>,
+    pub diagnostics: Vec<Diagnostic>,
+    /// True when a per-task or per-generation bound dropped diagnostics.
+    pub truncated: bool,
+}
+
 /// Concise failure evidence attached to a failed generation's observation.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -1282,6 +1449,8 @@ mod tests {
                     truncated: false,
                 }),
                 stderr: None,
+                tests: None,
+                steps: vec![],
             }],
             next_cursor: Some("cursor".to_owned()),
             returned_bytes: Some(0),
@@ -1299,6 +1468,23 @@ mod tests {
         assert_eq!(page.truncated, Some(true));
     }
 
+    #[test]
+    fn page_carries_the_task_test_summary() {
+        let registry = OutputRegistry::new();
+        record(
+            &registry,
+            7,
+            "t",
+            &["ok 1 - adds\n", "not ok 2 - subtracts\n"],
+        );
+        let tests = crate::test_reports::parse_tap("ok 1 - adds\nnot ok 2 - subtracts\n");
+        registry.record_tests(7, "t", tests.clone());
+        let page = registry
+            .retrieve_page(7, None, None, DEFAULT_PAGE_BYTES, None)
+            .expect("page");
+        assert_eq!(page.tasks[0].tests, Some(tests));
+    }
+
     #[test]
     fn page_resumes_exactly_from_cursor_without_skip_or_duplicate() {
         let registry = OutputRegistry::new();
@@ -1318,6 +1504,8 @@ mod tests {
                     truncated: false,
                 }),
                 stderr: None,
+                tests: None,
+                steps: vec![],
             }],
             next_cursor: Some("cursor".to_owned()),
             returned_bytes: Some(0),
@@ -1364,6 +1552,8 @@ mod tests {
                     truncated: false,
                 }),
                 stderr: None,
+                tests: None,
+                steps: vec![],
             }],
             next_cursor: Some("cursor".to_owned()),
             returned_bytes: Some(0),
@@ -1414,6 +1604,8 @@ mod tests {
                     truncated: false,
                 }),
                 stderr: None,
+                tests: None,
+                steps: vec![],
             }],
             next_cursor: Some("cursor".to_owned()),
             returned_bytes: Some(0),
@@ -1515,4 +1707,49 @@ mod tests {
         assert_eq!(page.tasks.len(), 1);
         assert!(page.next_cursor.is_none());
     }
+
+    #[test]
+    fn timestamped_lines_are_prefixed_on_retrieval_but_evidence_stays_raw() {
+        use crate::cmd::Stamp;
+        use crate::rules::Timestamps;
+        let handle = CaptureHandle::new();
+        for (line, millis) in [("compiling\n", 5), ("done\n", 1_250)] {
+            let stamp = Stamp {
+                mode: Timestamps::Elapsed,
+                millis,
+            };
+            handle.append_stamped(line.as_bytes(), false, Some(stamp));
+        }
+        let registry = OutputRegistry::new();
+        registry.record(4, "build".to_owned(), handle.finish(), None, None);
+
+        let stamped = "[+0.005s] compiling\n[+1.250s] done\n";
+        let tail = registry.retrieve(4, None, None, Some(1), false).unwrap();
+        let stdout = tail.tasks[0].stdout.as_ref().unwrap();
+        assert_eq!(stdout.content, "[+1.250s] done\n");
+        assert_eq!(stdout.retained_bytes, 15, "raw byte counts");
+
+        let page = registry
+            .retrieve_page(4, None, None, DEFAULT_PAGE_BYTES, None)
+            .unwrap();
+        assert_eq!(page.tasks[0].stdout.as_ref().unwrap().content, stamped);
+        assert_eq!(page.returned_bytes, Some(15));
+
+        // A page cut mid-stream resumes with raw offsets and keeps prefixes.
+        let budget = serde_json::to_vec(&page).unwrap().len() - 4;
+        let first = registry.retrieve_page(4, None, None, budget, None).unwrap();
+        let cursor = first.next_cursor.clone().expect("continuation");
+        let rest = registry
+            .retrieve_page(4, None, None, DEFAULT_PAGE_BYTES, Some(&cursor))
+            .unwrap();
+        let joined = format!(
+            "{}{}",
+            first.tasks[0].stdout.as_ref().unwrap().content,
+            rest.tasks[0].stdout.as_ref().unwrap().content
+        );
+        assert_eq!(joined, stamped);
+
+        let evidence = registry.failure_evidence(4, 40, "fz-test", &[]).unwrap();
+        assert_eq!(evidence.excerpt, "compiling\ndone\n");
+    }
 }
@@ -10,7 +10,7 @@
 //! process, stdout, control-socket, or threading side effects.
 
 use crate::rules::{CommandLine, OutputPolicy, Rules};
-use crate::template::{self, TemplateOptions};
+use crate::template::{self, GitTransition, TemplateOptions};
 use sha2::{Digest, Sha256};
 use std::collections::BTreeMap;
 use std::path::{Component, Path, PathBuf};
@@ -24,6 +24,19 @@ pub const SIGNATURE_SCHEMA_VERSION: u64 = 2;
 pub struct TaskContext {
     pub cwd: Option<PathBuf>,
     pub environment: BTreeMap<String, String>,
+    /// Resource limits and priority set in the child before exec.
+    pub limits: crate::rules::ProcessLimits,
+    /// Attach stdout/stderr to a pseudo-terminal instead of pipes.
+    pub tty: bool,
+    /// Make the task the terminal's foreground process group while it runs.
+    pub interactive: bool,
+    /// Signal and grace used when the task's process group is stopped.
+    pub stop: crate::rules::StopPolicy,
+    /// Commands run before a service's process group is signalled
+    /// (`jobs[].stop`).
+    pub teardown: Vec<String>,
+    /// Prefix added to each forwarded output line.
+    pub timestamps: crate::rules::Timestamps,
 }
 
 /// Stable execution identity (contract §5): canonical SHA-256 over the run
@@ -87,7 +100,13 @@ pub struct RunPlan {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TaskOutcome {
     Passed,
-    Failed { failures: Vec<String> },
+    Failed {
+        failures: Vec<String>,
+    },
+    /// Failed with `allow_failure` set: reported, but not a run failure.
+    Warning {
+        failures: Vec<String>,
+    },
     Cancelled,
     Skipped,
 }
@@ -252,6 +271,12 @@ impl RunPlan {
                 context: TaskContext {
                     cwd: rule.cwd().map(PathBuf::from),
                     environment: rule.environment().clone(),
+                    limits: rule.limits(),
+                    tty: rule.tty(),
+                    interactive: rule.interactive(),
+                    stop: rule.stop(),
+                    teardown: rule.teardown().to_vec(),
+                    timestamps: rule.timestamps(),
                 },
                 rule,
                 output,
@@ -350,6 +375,36 @@ impl RunPlan {
         (RunPlan { stages }, unknown)
     }
 
+    /// Substitutes `{{git.before}}`/`{{git.after}}` in every command and
+    /// recovery command, leaving the remaining variables to [`Self::expand`].
+    pub fn with_git_transition(mut self, transition: &GitTransition) -> RunPlan {
+        let substitute = |command: &mut CommandLine| {
+            *command = match command {
+                CommandLine::Shell(line) => {
+                    CommandLine::Shell(template::expand_git(line, transition))
+                }
+                CommandLine::Argv(argv) => CommandLine::Argv(
+                    argv.iter()
+                        .map(|arg| template::expand_git(arg, transition))
+                        .collect(),
+                ),
+            };
+        };
+        for stage in &mut self.stages {
+            let tasks = match stage {
+                Stage::Serial(task) => std::slice::from_mut(task),
+                Stage::Parallel { tasks, .. } => tasks.as_mut_slice(),
+            };
+            for task in tasks {
+                task.commands.iter_mut().for_each(substitute);
+                if let Some(recovery) = task.recovery_commands.as_mut() {
+                    recovery.iter_mut().for_each(substitute);
+                }
+            }
+        }
+        self
+    }
+
     /// Human diagnostics expose effective cwd and environment names, never
     /// environment values.
     pub fn context_summary(&self) -> String {
@@ -585,13 +640,18 @@ impl RunOutcome {
     }
 
     /// True when every recorded task passed (no failures, no cancellation).
+    /// Allowed failures count as passed, and so do jobs skipped by their
+    /// `when` condition.
     pub fn is_success(&self) -> bool {
-        self.tasks
-            .iter()
-            .all(|(_, _, outcome)| matches!(outcome, TaskOutcome::Passed))
+        self.tasks.iter().all(|(_, _, outcome)| {
+            matches!(
+                outcome,
+                TaskOutcome::Passed | TaskOutcome::Warning { .. } | TaskOutcome::Skipped
+            )
+        })
     }
 
-    /// True when at least one task failed.
+    /// True when at least one task failed; allowed failures do not count.
     pub fn has_failures(&self) -> bool {
         self.tasks
             .iter()
@@ -15,10 +15,24 @@
 
 use nix::sys::signal::{self, Signal};
 use nix::unistd::Pid;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Mutex;
 use std::time::{Duration, Instant};
 
+use crate::cmd::{ChildProcess, ProcessRunner};
 use crate::diagnostics;
+use crate::plan::TaskContext;
+use crate::rules::{CommandLine, StopPolicy};
+use crate::stdout;
+
+/// Set once `shutdown_all` starts signalling: from then on a child dying
+/// from a signal was stopped by funzzy, not by a resource limit.
+static STOPPING: AtomicBool = AtomicBool::new(false);
+
+/// Whether the process-wide shutdown path has signalled the owned groups.
+pub fn stopping() -> bool {
+    STOPPING.load(Ordering::SeqCst)
+}
 
 /// Registered owned process-group IDs (each equals the leader's PID, since
 /// tasks call `setpgid(0, 0)` before exec).
@@ -32,10 +46,147 @@ pub fn register(pgid: i32) {
     }
 }
 
+/// Per-group stop overrides (`jobs[].stop_signal` / `jobs[].stop_grace`),
+/// so the process-wide shutdown path stops each job the way it asked to be.
+static STOP_POLICIES: Mutex<Vec<(i32, StopPolicy)>> = Mutex::new(Vec::new());
+
+/// Records how an owned group wants to be stopped. A default policy keeps
+/// the global one and is not stored.
+pub fn set_stop_policy(pgid: i32, stop: StopPolicy) {
+    let mut policies = STOP_POLICIES.lock().expect("stop policies mutex poisoned");
+    policies.retain(|(g, _)| *g != pgid);
+    if !stop.is_empty() {
+        policies.push((pgid, stop));
+    }
+}
+
+/// How long a job's `stop` commands may run before they are killed and its
+/// process group is signalled anyway.
+pub const TEARDOWN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// `stop` commands of owned service groups (`jobs[].stop`), with the job
+/// name and the context they run in, so the process-wide shutdown path runs
+/// them before it signals the groups.
+static TEARDOWNS: Mutex<Vec<(i32, String, TaskContext)>> = Mutex::new(Vec::new());
+
+/// Records the `stop` commands of the job leading an owned group. A context
+/// without any is not stored.
+pub fn set_teardown(pgid: i32, job: &str, context: &TaskContext) {
+    let mut teardowns = TEARDOWNS.lock().expect("teardowns mutex poisoned");
+    teardowns.retain(|(g, _, _)| *g != pgid);
+    if !context.teardown.is_empty() {
+        teardowns.push((pgid, job.to_owned(), context.clone()));
+    }
+}
+
+/// Claims the `stop` commands recorded for `pgid` so only one caller runs
+/// them; false when there were none or another caller already took them.
+pub fn take_teardown(pgid: i32) -> bool {
+    let mut teardowns = TEARDOWNS.lock().expect("teardowns mutex poisoned");
+    let before = teardowns.len();
+    teardowns.retain(|(g, _, _)| *g != pgid);
+    teardowns.len() != before
+}
+
 /// Forgets an owned process group (e.g., after it exited and was reaped).
 pub fn unregister(pgid: i32) {
     let mut groups = OWNED_GROUPS.lock().expect("owned groups mutex poisoned");
     groups.retain(|g| *g != pgid);
+    drop(groups);
+    set_stop_policy(pgid, StopPolicy::default());
+    set_teardown(pgid, "", &TaskContext::default());
+}
+
+/// Runs a job's `stop` commands in order with the job's cwd and
+/// environment, before its process group is signalled. A failing command
+/// ends the sequence; commands still running once `bound` elapses are
+/// killed. Failures are warnings: the group is stopped either way.
+pub fn run_teardown(
+    runner: &dyn ProcessRunner,
+    job: &str,
+    context: &TaskContext,
+    bound: Duration,
+    verbose: bool,
+) {
+    let stop_context = TaskContext {
+        cwd: context.cwd.clone(),
+        environment: context.environment.clone(),
+        ..TaskContext::default()
+    };
+    let deadline = Instant::now() + bound;
+    for command in &context.teardown {
+        if verbose {
+            diagnostics::debug(&diagnostics::Record {
+                decision: Some("cancel"),
+                note: Some(format!("running stop command of '{}': {}", job, command)),
+                ..Default::default()
+            });
+        }
+        let line = CommandLine::Shell(command.clone());
+        let mut child = match runner.spawn(job, &line, &stop_context, None, None, false) {
+            Ok(child) => child,
+            Err(err) => {
+                stdout::warn(&format!("job '{}' stop command failed: {}", job, err));
+                return;
+            }
+        };
+        match wait_until(child.as_mut(), deadline) {
+            Some(Ok(())) => {}
+            Some(Err(err)) => {
+                stdout::warn(&format!(
+                    "job '{}' stop command '{}' failed: {}",
+                    job, command, err
+                ));
+                return;
+            }
+            None => {
+                stdout::warn(&format!(
+                    "job '{}' stop command '{}' did not finish within {:?}; stopping it",
+                    job, command, bound
+                ));
+                child.shutdown(Signal::SIGKILL, Duration::ZERO, verbose);
+                return;
+            }
+        }
+    }
+}
+
+/// Waits for a stop command until `deadline`: its outcome once it exited,
+/// or None while it is still running.
+fn wait_until(child: &mut dyn ChildProcess, deadline: Instant) -> Option<Result<(), String>> {
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) if status.success() => return Some(Ok(())),
+            Ok(Some(status)) => return Some(Err(format!("exited with {}", status))),
+            Ok(None) if Instant::now() < deadline => {
+                std::thread::sleep(Duration::from_millis(20));
+            }
+            Ok(None) => return None,
+            Err(err) => return Some(Err(err.to_string())),
+        }
+    }
+}
+
+/// Runs the `stop` commands of every owned group that declared them, most
+/// recently started first, and forgets them. Part of the process-wide
+/// shutdown path: called before [`shutdown_all`] signals the groups.
+pub fn run_teardowns(runner: &dyn ProcessRunner, verbose: bool) {
+    let teardowns = std::mem::take(&mut *TEARDOWNS.lock().expect("teardowns mutex poisoned"));
+    for (pgid, job, context) in teardowns.into_iter().rev() {
+        if group_alive(pgid) {
+            run_teardown(runner, &job, &context, TEARDOWN_TIMEOUT, verbose);
+        }
+    }
+}
+
+fn stop_override(pgid: i32) -> StopPolicy {
+    STOP_POLICIES
+        .lock()
+        .expect("stop policies mutex poisoned")
+        .iter()
+        .find(|(g, _)| *g == pgid)
+        .map(|(_, stop)| *stop)
+        .unwrap_or_default()
 }
 
 /// True if any process still exists in the group (`kill(-pgid, 0)` probe).
@@ -49,17 +200,25 @@ fn group_alive(pgid: i32) -> bool {
 
 /// Shuts every owned process group down: sends `signal` to the whole group,
 /// waits up to `grace`, then force-kills (`SIGKILL`) whatever remains.
+/// Groups with a job stop policy use their own signal and grace instead.
 ///
 /// This is the Ctrl-C / process-shutdown path. Normal per-run cancellation
 /// (`executor::Run::cancel`) reaps via the owned `LoggedChild`; this path is
 /// used when the whole process is stopping and just needs to reach every
 /// descendant group.
 pub fn shutdown_all(signal: Signal, grace: Duration, verbose: bool) -> ShutdownTally {
-    let groups: Vec<i32> = OWNED_GROUPS
+    let groups: Vec<(i32, Signal, Duration)> = OWNED_GROUPS
         .lock()
         .expect("owned groups mutex poisoned")
         .iter()
-        .copied()
+        .map(|pgid| {
+            let stop = stop_override(*pgid);
+            (
+                *pgid,
+                stop.signal.unwrap_or(signal),
+                stop.grace.unwrap_or(grace),
+            )
+        })
         .collect();
 
     let mut tally = ShutdownTally {
@@ -69,9 +228,10 @@ pub fn shutdown_all(signal: Signal, grace: Duration, verbose: bool) -> ShutdownT
     if groups.is_empty() {
         return tally;
     }
+    STOPPING.store(true, Ordering::SeqCst);
 
     // 1. Initial signal to every owned group.
-    for pgid in &groups {
+    for (pgid, signal, _) in &groups {
         if verbose {
             diagnostics::debug(&diagnostics::Record {
                 decision: Some("cancel"),
@@ -82,34 +242,44 @@ pub fn shutdown_all(signal: Signal, grace: Duration, verbose: bool) -> ShutdownT
                 ..Default::default()
             });
         }
-        let _ = signal::kill(Pid::from_raw(-*pgid), signal);
+        let _ = signal::kill(Pid::from_raw(-*pgid), *signal);
     }
 
-    // 2. Wait up to grace for groups to vanish.
-    let deadline = Instant::now() + grace;
-    let mut pending = groups.clone();
-    while Instant::now() < deadline && !pending.is_empty() {
-        std::thread::sleep(Duration::from_millis(20));
-        pending.retain(|pgid| group_alive(*pgid));
-    }
-
-    tally.signaled = (groups.len() - pending.len()) as u32;
-
-    // 3. Escalate: SIGKILL any group still alive.
-    for pgid in &pending {
-        if verbose {
-            diagnostics::debug(&diagnostics::Record {
-                decision: Some("cancel"),
-                note: Some(format!(
-                    "grace of {:?} elapsed; force-killing process group -{}",
-                    grace, pgid
-                )),
-                ..Default::default()
-            });
+    // 2. Wait for groups to vanish; 3. escalate to SIGKILL for any group
+    // still alive once its own grace has elapsed.
+    let started = Instant::now();
+    let mut pending: Vec<(i32, Duration)> = groups
+        .iter()
+        .map(|(pgid, _, grace)| (*pgid, *grace))
+        .collect();
+    loop {
+        pending.retain(|(pgid, grace)| {
+            if !group_alive(*pgid) {
+                tally.signaled += 1;
+                return false;
+            }
+            if started.elapsed() < *grace {
+                return true;
+            }
+            if verbose {
+                diagnostics::debug(&diagnostics::Record {
+                    decision: Some("cancel"),
+                    note: Some(format!(
+                        "grace of {:?} elapsed; force-killing process group -{}",
+                        grace, pgid
+                    )),
+                    ..Default::default()
+                });
+            }
+            let _ = signal::kill(Pid::from_raw(-*pgid), Signal::SIGKILL);
+            tally.force_killed += 1;
+            false
+        });
+        if pending.is_empty() {
+            break;
         }
-        let _ = signal::kill(Pid::from_raw(-*pgid), Signal::SIGKILL);
+        std::thread::sleep(Duration::from_millis(20));
     }
-    tally.force_killed = pending.len() as u32;
 
     tally
 }
@@ -135,6 +305,33 @@ pub fn shutdown_policy() -> (Signal, Duration) {
     (signal, Duration::from_millis(grace_ms))
 }
 
+/// The signal and grace a job's stop policy resolves to.
+pub fn stop_policy(stop: StopPolicy) -> (Signal, Duration) {
+    let (signal, grace) = shutdown_policy();
+    (stop.signal.unwrap_or(signal), stop.grace.unwrap_or(grace))
+}
+
+/// The longest grace any owned group may take to stop: the bound callers
+/// wait for a cancel or reconciliation to be acknowledged.
+pub fn longest_grace() -> Duration {
+    let (_, grace) = shutdown_policy();
+    let grace = STOP_POLICIES
+        .lock()
+        .expect("stop policies mutex poisoned")
+        .iter()
+        .filter_map(|(_, stop)| stop.grace)
+        .fold(grace, Duration::max);
+    if TEARDOWNS
+        .lock()
+        .expect("teardowns mutex poisoned")
+        .is_empty()
+    {
+        grace
+    } else {
+        grace + TEARDOWN_TIMEOUT
+    }
+}
+
 /// Parses the configured graceful-cancel signal. Unsupported values fall
 /// back deterministically to SIGTERM rather than silently disabling cleanup.
 fn shutdown_signal(value: Option<&str>) -> Signal {
@@ -196,6 +393,67 @@ mod tests {
         assert_eq!(grace, Duration::from_millis(5_000));
     }
 
+    #[test]
+    fn job_stop_policies_override_the_defaults_while_the_group_is_owned() {
+        const GROUP: i32 = 999_992;
+        std::env::remove_var("FUNZZY_CANCEL_SIGNAL");
+        std::env::remove_var("FUNZZY_CANCEL_GRACE_MS");
+        let stop = StopPolicy {
+            signal: Some(Signal::SIGINT),
+            grace: Some(Duration::from_secs(600)),
+        };
+        assert_eq!(
+            stop_policy(stop),
+            (Signal::SIGINT, Duration::from_secs(600))
+        );
+        assert_eq!(
+            stop_policy(StopPolicy {
+                signal: None,
+                grace: Some(Duration::from_secs(1)),
+            }),
+            (Signal::SIGTERM, Duration::from_secs(1))
+        );
+
+        register(GROUP);
+        set_stop_policy(GROUP, stop);
+        assert_eq!(stop_override(GROUP), stop);
+        assert!(longest_grace() >= Duration::from_secs(600));
+        unregister(GROUP);
+        assert_eq!(stop_override(GROUP), StopPolicy::default());
+    }
+
+    #[test]
+    fn teardowns_are_tracked_while_the_group_is_owned() {
+        const GROUP: i32 = 999_993;
+        let context = TaskContext {
+            teardown: vec!["docker compose down".to_owned()],
+            ..TaskContext::default()
+        };
+        let tracked = || {
+            TEARDOWNS
+                .lock()
+                .expect("mutex")
+                .iter()
+                .any(|(g, job, _)| *g == GROUP && job == "stack")
+        };
+
+        register(GROUP);
+        set_teardown(GROUP, "stack", &context);
+        assert!(tracked());
+        assert!(longest_grace() >= TEARDOWN_TIMEOUT);
+        unregister(GROUP);
+        assert!(!tracked());
+
+        // Stop commands run once: the first claim wins.
+        set_teardown(GROUP, "stack", &context);
+        assert!(take_teardown(GROUP));
+        assert!(!tracked());
+        assert!(!take_teardown(GROUP));
+
+        set_teardown(GROUP, "stack", &TaskContext::default());
+        assert!(!tracked(), "a job without stop commands is not stored");
+    }
+
     #[test]
     fn shutdown_signal_accepts_supported_values_and_defaults_invalid_values() {
         assert_eq!(shutdown_signal(Some("INT")), Signal::SIGINT);
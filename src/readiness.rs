@@ -0,0 +1,475 @@
+//! Service readiness probes (`jobs[].ready`) and health checks
+//! (`jobs[].health`).
+//!
+//! A service with a probe holds its stage open: later stages start only once
+//! the probe passes. A health check keeps probing a running service and asks
+//! for a restart after enough failures in a row. Probes never block the
+//! executor: a TCP probe resolves and connects on a short-lived thread, a
+//! probe command runs as a child, and both are polled like any task command;
+//! a log probe scans only output written since its last attempt.
+
+use crate::cmd::{CaptureHandle, ChildProcess, ProcessRunner};
+use crate::plan::TaskContext;
+use crate::rules::{CommandLine, HealthCheck, Readiness, ReadyProbe};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// Pause between probe attempts.
+const PROBE_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Upper bound of one TCP connect attempt, per resolved address.
+const CONNECT_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Grace given to a probe command stopped with its service.
+const PROBE_STOP_GRACE: Duration = Duration::from_millis(200);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Poll {
+    Starting,
+    Ready,
+    /// The timeout elapsed; carries the short reason for snapshots.
+    Unready(String),
+}
+
+/// What a probe may look at for one service.
+pub struct Target<'a> {
+    pub task: &'a str,
+    pub context: &'a TaskContext,
+    pub capture: Option<&'a CaptureHandle>,
+    pub runner: &'a dyn ProcessRunner,
+}
+
+/// Probe state of one starting service.
+pub struct ReadinessCheck {
+    readiness: Readiness,
+    prober: Prober,
+    deadline: Option<Instant>,
+}
+
+impl ReadinessCheck {
+    pub fn new(readiness: &Readiness) -> Self {
+        Self {
+            readiness: readiness.clone(),
+            prober: Prober::new(&readiness.probe, PROBE_INTERVAL),
+            deadline: None,
+        }
+    }
+
+    /// Whether probing has begun.
+    pub fn started(&self) -> bool {
+        self.deadline.is_some()
+    }
+
+    /// Whether the service needs its output captured for the probe.
+    pub fn reads_output(&self) -> bool {
+        matches!(self.readiness.probe, ReadyProbe::Log(_))
+    }
+
+    /// Makes at most one probe attempt. The timeout counts from the first
+    /// poll, which follows the service's spawn.
+    pub fn poll(&mut self, now: Instant, target: Target<'_>) -> Poll {
+        let deadline = *self.deadline.get_or_insert(now + self.readiness.timeout);
+        if self.prober.attempt(now, &target) == Some(true) {
+            self.stop();
+            return Poll::Ready;
+        }
+        if now >= deadline {
+            self.stop();
+            return Poll::Unready(format!(
+                "not ready within {}: {}",
+                crate::config::format_grace(self.readiness.timeout),
+                self.readiness.probe.label()
+            ));
+        }
+        Poll::Starting
+    }
+
+    /// Stops a probe command still running.
+    pub fn stop(&mut self) {
+        self.prober.stop();
+    }
+}
+
+/// Health check state of one running service.
+pub struct HealthMonitor {
+    check: HealthCheck,
+    prober: Prober,
+    failed: usize,
+}
+
+impl HealthMonitor {
+    pub fn new(check: &HealthCheck) -> Self {
+        Self {
+            check: check.clone(),
+            prober: Prober::new(&check.probe, check.interval),
+            failed: 0,
+        }
+    }
+
+    /// Makes at most one check; returns the result line of a check that
+    /// completed, e.g. `passing` or `failing 1/3: tcp localhost:3000`. The
+    /// first check runs one interval after the first poll, and a probe
+    /// command still running after an interval counts as failed.
+    pub fn poll(&mut self, now: Instant, target: Target<'_>) -> Option<String> {
+        if self.prober.next_attempt.is_none() {
+            self.prober.next_attempt = Some(now + self.check.interval);
+            return None;
+        }
+        let passed = match self.prober.attempt(now, &target) {
+            Some(passed) => passed,
+            None if self.prober.overdue(now) => {
+                self.prober.stop();
+                false
+            }
+            None => return None,
+        };
+        if passed {
+            self.failed = 0;
+            return Some("passing".to_owned());
+        }
+        self.failed += 1;
+        Some(format!(
+            "failing {}/{}: {}",
+            self.failed,
+            self.check.failures,
+            self.check.probe.label()
+        ))
+    }
+
+    /// Whether enough checks failed in a row to restart the service.
+    pub fn exhausted(&self) -> bool {
+        self.failed >= self.check.failures
+    }
+
+    /// Starts over, as for a freshly spawned service.
+    pub fn reset(&mut self) {
+        self.prober.stop();
+        self.prober.next_attempt = None;
+        self.prober.scanned = (0, 0);
+        self.failed = 0;
+    }
+}
+
+/// An attempt still in flight.
+enum Pending {
+    Command(Box<dyn ChildProcess>),
+    /// A connect thread; it sends whether any address accepted.
+    Connect(mpsc::Receiver<bool>),
+}
+
+/// One probe attempted at most once per interval.
+struct Prober {
+    probe: ReadyProbe,
+    /// Compiled `ReadyProbe::Log` pattern.
+    pattern: Option<regex::Regex>,
+    interval: Duration,
+    next_attempt: Option<Instant>,
+    /// An attempt in flight and when it began.
+    pending: Option<(Pending, Instant)>,
+    /// Stdout and stderr offsets the log probe has scanned up to.
+    scanned: (u64, u64),
+}
+
+impl Prober {
+    fn new(probe: &ReadyProbe, interval: Duration) -> Self {
+        let pattern = match probe {
+            // Validated when the config was read.
+            ReadyProbe::Log(pattern) => regex::Regex::new(pattern).ok(),
+            _ => None,
+        };
+        Self {
+            probe: probe.clone(),
+            pattern,
+            interval,
+            next_attempt: None,
+            pending: None,
+            scanned: (0, 0),
+        }
+    }
+
+    /// The result of an attempt this poll completes; `None` while no attempt
+    /// is due or a probe command is still running.
+    fn attempt(&mut self, now: Instant, target: &Target<'_>) -> Option<bool> {
+        if let Some((pending, _)) = self.pending.as_mut() {
+            let result = match pending {
+                Pending::Command(child) => match child.try_wait() {
+                    Ok(None) => None,
+                    Ok(Some(status)) => Some(status.success()),
+                    Err(_) => Some(false),
+                },
+                Pending::Connect(result) => match result.try_recv() {
+                    Err(mpsc::TryRecvError::Empty) => None,
+                    Ok(connected) => Some(connected),
+                    Err(mpsc::TryRecvError::Disconnected) => Some(false),
+                },
+            };
+            if result.is_some() {
+                self.pending = None;
+            }
+            return result;
+        }
+        if self.next_attempt.is_some_and(|next| now < next) {
+            return None;
+        }
+        self.next_attempt = Some(now + self.interval);
+        match &self.probe {
+            ReadyProbe::Tcp { host, port } => {
+                // Name resolution and connect both block, so neither runs
+                // on the executor's thread.
+                let (sender, result) = mpsc::channel();
+                let address = (host.clone(), *port);
+                let spawned = std::thread::Builder::new()
+                    .name("funzzy-probe".to_owned())
+                    .spawn(move || {
+                        let connected = address
+                            .to_socket_addrs()
+                            .map(|mut addresses| {
+                                addresses.any(|address| {
+                                    TcpStream::connect_timeout(&address, CONNECT_TIMEOUT).is_ok()
+                                })
+                            })
+                            .unwrap_or(false);
+                        let _ = sender.send(connected);
+                    });
+                match spawned {
+                    Ok(_) => {
+                        self.pending = Some((Pending::Connect(result), now));
+                        None
+                    }
+                    Err(_) => Some(false),
+                }
+            }
+            ReadyProbe::Log(_) => Some(match (&self.pattern, target.capture) {
+                (Some(pattern), Some(capture)) => {
+                    capture.any_new_line_matches(pattern, &mut self.scanned)
+                }
+                _ => false,
+            }),
+            ReadyProbe::File(path) => {
+                let path = PathBuf::from(path);
+                Some(match &target.context.cwd {
+                    Some(cwd) => cwd.join(path).exists(),
+                    None => path.exists(),
+                })
+            }
+            ReadyProbe::Command(command) => {
+                // The probe runs with the service's cwd and environment but
+                // none of its terminal, limits or lock settings.
+                let context = TaskContext {
+                    cwd: target.context.cwd.clone(),
+                    environment: target.context.environment.clone(),
+                    ..TaskContext::default()
+                };
+                match target.runner.spawn(
+                    target.task,
+                    &CommandLine::Shell(command.clone()),
+                    &context,
+                    None,
+                    None,
+                    true,
+                ) {
+                    Ok(child) => {
+                        self.pending = Some((Pending::Command(child), now));
+                        None
+                    }
+                    Err(_) => Some(false),
+                }
+            }
+        }
+    }
+
+    /// Whether an attempt has been in flight for longer than one interval;
+    /// a connect is also given its own timeout.
+    fn overdue(&self, now: Instant) -> bool {
+        self.pending.as_ref().is_some_and(|(pending, spawned)| {
+            let bound = match pending {
+                Pending::Command(_) => self.interval,
+                Pending::Connect(_) => self.interval.max(CONNECT_TIMEOUT),
+            };
+            now.saturating_duration_since(*spawned) >= bound
+        })
+    }
+
+    fn stop(&mut self) {
+        // A connect thread ends on its own within its connect timeouts.
+        if let Some((Pending::Command(mut child), _)) = self.pending.take() {
+            child.shutdown(nix::sys::signal::Signal::SIGKILL, PROBE_STOP_GRACE, false);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::executor::SystemProcessRunner;
+
+    fn check(probe: ReadyProbe, timeout: Duration) -> ReadinessCheck {
+        ReadinessCheck::new(&Readiness { probe, timeout })
+    }
+
+    fn poll(
+        check: &mut ReadinessCheck,
+        context: &TaskContext,
+        capture: Option<&CaptureHandle>,
+    ) -> Poll {
+        check.poll(
+            Instant::now(),
+            Target {
+                task: "server",
+                context,
+                capture,
+                runner: &SystemProcessRunner,
+            },
+        )
+    }
+
+    /// Polls until an attempt in flight completes.
+    fn settle<T: PartialEq>(pending: T, mut poll: impl FnMut() -> T) -> T {
+        let started = Instant::now();
+        loop {
+            match poll() {
+                outcome if outcome == pending && started.elapsed() < Duration::from_secs(5) => {
+                    std::thread::sleep(Duration::from_millis(5))
+                }
+                outcome => break outcome,
+            }
+        }
+    }
+
+    #[test]
+    fn tcp_probe_passes_once_the_port_accepts() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let mut ready = check(
+            ReadyProbe::Tcp {
+                host: "127.0.0.1".to_owned(),
+                port,
+            },
+            Duration::from_secs(5),
+        );
+        // The connect runs off the polling thread.
+        assert_eq!(
+            poll(&mut ready, &TaskContext::default(), None),
+            Poll::Starting
+        );
+        assert_eq!(
+            settle(Poll::Starting, || poll(
+                &mut ready,
+                &TaskContext::default(),
+                None
+            )),
+            Poll::Ready
+        );
+
+        drop(listener);
+        let mut closed = check(
+            ReadyProbe::Tcp {
+                host: "127.0.0.1".to_owned(),
+                port,
+            },
+            Duration::ZERO,
+        );
+        assert_eq!(
+            poll(&mut closed, &TaskContext::default(), None),
+            Poll::Unready(format!("not ready within 0s: tcp 127.0.0.1:{port}"))
+        );
+    }
+
+    #[test]
+    fn log_probe_matches_captured_lines() {
+        let capture = CaptureHandle::new();
+        let mut ready = check(
+            ReadyProbe::Log("listening on :\\d+".to_owned()),
+            Duration::from_secs(5),
+        );
+        assert!(ready.reads_output());
+        capture.append(b"compiling\n", false);
+        assert_eq!(
+            poll(&mut ready, &TaskContext::default(), Some(&capture)),
+            Poll::Starting
+        );
+        capture.append(b"listening on :8080\n", true);
+        // Attempts are spaced by the probe interval.
+        std::thread::sleep(PROBE_INTERVAL);
+        assert_eq!(
+            poll(&mut ready, &TaskContext::default(), Some(&capture)),
+            Poll::Ready
+        );
+    }
+
+    #[test]
+    fn file_and_command_probes_run_in_the_service_cwd() {
+        let dir = std::env::temp_dir().join(format!("funzzy-ready-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let context = TaskContext {
+            cwd: Some(dir.clone()),
+            ..TaskContext::default()
+        };
+
+        let mut file = check(
+            ReadyProbe::File("ready.pid".to_owned()),
+            Duration::from_secs(5),
+        );
+        assert_eq!(poll(&mut file, &context, None), Poll::Starting);
+        std::fs::write(dir.join("ready.pid"), "1").unwrap();
+        std::thread::sleep(PROBE_INTERVAL);
+        assert_eq!(poll(&mut file, &context, None), Poll::Ready);
+
+        let mut command = check(
+            ReadyProbe::Command("test -f ready.pid".to_owned()),
+            Duration::from_secs(5),
+        );
+        assert_eq!(
+            settle(Poll::Starting, || poll(&mut command, &context, None)),
+            Poll::Ready
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn health_checks_count_failures_in_a_row_until_a_restart_is_due() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let mut health = HealthMonitor::new(&HealthCheck {
+            probe: ReadyProbe::Tcp {
+                host: "127.0.0.1".to_owned(),
+                port,
+            },
+            interval: Duration::ZERO,
+            failures: 2,
+        });
+        let context = TaskContext::default();
+        let poll = |health: &mut HealthMonitor| {
+            health.poll(
+                Instant::now(),
+                Target {
+                    task: "server",
+                    context: &context,
+                    capture: None,
+                    runner: &SystemProcessRunner,
+                },
+            )
+        };
+
+        // The first poll only schedules the first check.
+        assert_eq!(poll(&mut health), None);
+        assert_eq!(
+            settle(None, || poll(&mut health)),
+            Some("passing".to_owned())
+        );
+
+        drop(listener);
+        let failing = |count| format!("failing {count}/2: tcp 127.0.0.1:{port}");
+        assert_eq!(settle(None, || poll(&mut health)), Some(failing(1)));
+        assert!(!health.exhausted());
+        assert_eq!(settle(None, || poll(&mut health)), Some(failing(2)));
+        assert!(health.exhausted());
+
+        health.reset();
+        assert!(!health.exhausted());
+        assert_eq!(poll(&mut health), None);
+    }
+}
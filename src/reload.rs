@@ -95,6 +95,9 @@ pub fn validate_candidate(
         .unwrap_or(defaults.backend);
     let respect_gitignore =
         crate::config::respect_gitignore_from_yaml(content).map_err(semantic)?;
+    let editor_noise = crate::config::editor_noise_from_yaml(content).map_err(semantic)?;
+    let settle = crate::config::settle_from_yaml(content).map_err(semantic)?;
+    let event_source = crate::config::event_source_from_yaml(content).map_err(semantic)?;
     let recovery_policy =
         crate::config::recovery_policy_from_yaml_with_default(content, defaults.recovery_policy)
             .map_err(semantic)?;
@@ -115,7 +118,10 @@ pub fn validate_candidate(
         hooks,
         session_hooks,
         control_socket,
-    ))
+    )
+    .with_editor_noise(editor_noise)
+    .with_settle(settle)
+    .with_event_source(event_source))
 }
 
 /// One complete reload decision after observing a candidate: publish a new
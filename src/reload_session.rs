@@ -297,7 +297,9 @@ impl ReloadSession {
                         }
                     }
                 },
+                || {},
                 debounce,
+                None,
                 backend,
                 false,
                 None,
@@ -399,7 +401,6 @@ fn fatal_reload(
 /// Builds a fresh `Watches` from a validated config candidate, bound to the
 /// new revision (TASK-0090 commit). The candidate's OWN declared policy
 /// (concurrency/debounce/backend/gitignore/recovery/hooks) is parsed from the content;
-
 /// missing keys keep the startup defaults — so a policy change committed by
 /// the reload is actually applied to post-commit generations (TASK-0092).
 fn build_watches_from_content(
@@ -421,6 +422,11 @@ fn build_watches_from_content(
         .unwrap_or(defaults.backend);
     let respect_gitignore =
         crate::config::respect_gitignore_from_yaml(content).map_err(|err| err.to_string())?;
+    let editor_noise =
+        crate::config::editor_noise_from_yaml(content).map_err(|err| err.to_string())?;
+    let settle = crate::config::settle_from_yaml(content).map_err(|err| err.to_string())?;
+    let event_source =
+        crate::config::event_source_from_yaml(content).map_err(|err| err.to_string())?;
     let recovery_policy =
         crate::config::recovery_policy_from_yaml_with_default(content, defaults.recovery_policy)
             .map_err(|err| err.to_string())?;
@@ -433,7 +439,10 @@ fn build_watches_from_content(
         Watches::with_root_and_concurrency(rules, root.to_path_buf(), concurrency)
             .with_debounce(debounce)
             .with_backend(backend)
+            .with_settle(settle)
+            .with_event_source(event_source)
             .with_gitignore(respect_gitignore)
+            .with_editor_noise(editor_noise)
             .with_recovery_policy(recovery_policy)
             .with_hooks(hooks)
             .with_session_hooks(session_hooks)
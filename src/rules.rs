@@ -9,7 +9,9 @@
 extern crate glob;
 
 use self::glob::Pattern;
+use nix::sys::signal::Signal;
 use std::collections::BTreeMap;
+use std::time::Duration;
 
 /// Per-job output policy (OUTPUT-POLICY-CONTRACT, TASK-0041). `Inherit` is
 /// the default and matches streaming behavior.
@@ -20,6 +22,357 @@ pub enum OutputPolicy {
     Quiet,
     Capture,
     ShowOnFailure,
+    /// Holds a parallel job's output and prints it as one block when the
+    /// job finishes; serial jobs stream as with `Inherit`.
+    Grouped,
+}
+
+/// Prefix added to each forwarded output line (`execution.timestamps`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Timestamps {
+    #[default]
+    None,
+    /// UTC wall-clock time the line was read.
+    Wall,
+    /// Time since the job started.
+    Elapsed,
+}
+
+impl Timestamps {
+    pub const VALUES: &'static [&'static str] = &["wall", "elapsed", "none"];
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "wall" => Some(Timestamps::Wall),
+            "elapsed" => Some(Timestamps::Elapsed),
+            "none" => Some(Timestamps::None),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Timestamps::None => "none",
+            Timestamps::Wall => "wall",
+            Timestamps::Elapsed => "elapsed",
+        }
+    }
+}
+
+/// Severity of a diagnostic extracted by a problem matcher.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Severity {
+    #[default]
+    Error,
+    Warning,
+    Info,
+}
+
+impl Severity {
+    pub const VALUES: &'static [&'static str] = &["error", "warning", "info"];
+
+    /// Reads a severity as tools print it: `error`, `Warning`, `warn`,
+    /// `note`, `help` and the like.
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "error" | "err" | "e" | "fatal" | "failed" => Some(Severity::Error),
+            "warning" | "warn" | "w" => Some(Severity::Warning),
+            "info" | "information" | "note" | "help" | "hint" | "i" => Some(Severity::Info),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "info",
+        }
+    }
+}
+
+/// Turns a job's captured output into diagnostics (`jobs[].matchers`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ProblemMatcher {
+    /// A built-in matcher by name, e.g. `rustc` or `eslint`.
+    Builtin(String),
+    /// A regex with named groups `file`, `line`, `column`, `severity`,
+    /// `message` and `code`; `severity` applies when the regex has no
+    /// `severity` group or it matched unknown text.
+    Pattern { regex: String, severity: Severity },
+}
+
+impl ProblemMatcher {
+    /// Name reported on each diagnostic: the built-in name or `custom`.
+    pub fn name(&self) -> &str {
+        match self {
+            ProblemMatcher::Builtin(name) => name,
+            ProblemMatcher::Pattern { .. } => "custom",
+        }
+    }
+}
+
+/// Test reports read once a job finishes (`jobs[].reports`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct TestReports {
+    /// Glob of JUnit XML files the job writes, relative to its cwd.
+    pub junit: Option<String>,
+    /// Parse TAP from the job's stdout.
+    pub tap: bool,
+}
+
+impl TestReports {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Repository event a job can subscribe to through `on.git` / `jobs[].git`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GitTrigger {
+    /// HEAD resolves to a different commit (checkout, reset, pull, commit).
+    HeadChange,
+    /// The checked-out branch advanced without switching branches.
+    Commit,
+    /// The staging area (`.git/index`) was rewritten.
+    IndexChange,
+}
+
+impl GitTrigger {
+    pub const VALUES: &'static [&'static str] = &["head_change", "commit", "index_change"];
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "head_change" => Some(GitTrigger::HeadChange),
+            "commit" => Some(GitTrigger::Commit),
+            "index_change" => Some(GitTrigger::IndexChange),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            GitTrigger::HeadChange => "head_change",
+            GitTrigger::Commit => "commit",
+            GitTrigger::IndexChange => "index_change",
+        }
+    }
+}
+
+/// A named resource a job holds while it runs (`jobs[].locks`). At most
+/// `capacity` jobs hold it at once (`execution.resources`, default 1).
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ResourceLock {
+    pub name: String,
+    pub capacity: usize,
+}
+
+/// I/O scheduling class for a job's processes (`jobs[].ionice`). Applied
+/// with `ioprio_set` on Linux; ignored elsewhere.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IoPriority {
+    Idle,
+    /// Best-effort class with its level, 0 (highest) to 7 (lowest).
+    BestEffort(u8),
+}
+
+impl IoPriority {
+    pub fn label(&self) -> String {
+        match self {
+            IoPriority::Idle => "idle".to_owned(),
+            IoPriority::BestEffort(level) => format!("best-effort:{}", level),
+        }
+    }
+}
+
+/// Per-job process limits and priority (`jobs[].limits`, `jobs[].nice`,
+/// `jobs[].ionice`), applied to every command of the job before exec.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ProcessLimits {
+    /// Address space in bytes (`RLIMIT_AS`).
+    pub memory: Option<u64>,
+    /// CPU time in seconds (`RLIMIT_CPU`).
+    pub cpu_seconds: Option<u64>,
+    /// Open file descriptors (`RLIMIT_NOFILE`).
+    pub open_files: Option<u64>,
+    /// Core dump size in bytes (`RLIMIT_CORE`); 0 disables core dumps.
+    pub core: Option<u64>,
+    pub nice: Option<i32>,
+    pub ionice: Option<IoPriority>,
+}
+
+impl ProcessLimits {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// A check that a service is up (`jobs[].ready`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ReadyProbe {
+    /// A TCP connection to `host:port` succeeds.
+    Tcp { host: String, port: u16 },
+    /// A line of the service's output matches this regex.
+    Log(String),
+    /// This path exists, relative to the job's cwd.
+    File(String),
+    /// This shell command exits 0.
+    Command(String),
+}
+
+impl ReadyProbe {
+    pub fn label(&self) -> String {
+        match self {
+            ReadyProbe::Tcp { host, port } => format!("tcp {}:{}", host, port),
+            ReadyProbe::Log(pattern) => format!("log /{}/", pattern),
+            ReadyProbe::File(path) => format!("file {}", path),
+            ReadyProbe::Command(command) => format!("command {}", command),
+        }
+    }
+}
+
+/// Readiness of a service: later stages start once `probe` passes, or
+/// the service is reported unready after `timeout`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Readiness {
+    pub probe: ReadyProbe,
+    pub timeout: Duration,
+}
+
+/// How the pause before a service restart grows (`jobs[].restart.backoff`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Backoff {
+    #[default]
+    Fixed,
+    /// Doubles with each restart since the counter was last reset.
+    Exponential,
+}
+
+/// Longest pause an exponential backoff grows to.
+pub const MAX_RESTART_BACKOFF: Duration = Duration::from_secs(30);
+
+/// How a service is restarted after an unexpected exit (`jobs[].restart`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RestartPolicy {
+    /// Restarts allowed before the service is reported failed.
+    pub max: usize,
+    pub backoff: Backoff,
+    /// Pause before the first restart.
+    pub delay: Duration,
+    /// A service that ran at least this long gets its restart budget back.
+    pub reset_after: Option<Duration>,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            max: 3,
+            backoff: Backoff::Fixed,
+            delay: Duration::from_millis(500),
+            reset_after: None,
+        }
+    }
+}
+
+impl RestartPolicy {
+    /// Pause before the `attempt`-th restart (1-based).
+    pub fn delay_for(&self, attempt: usize) -> Duration {
+        match self.backoff {
+            Backoff::Fixed => self.delay,
+            Backoff::Exponential => {
+                let factor = 1u32 << attempt.saturating_sub(1).min(16);
+                self.delay.saturating_mul(factor).min(MAX_RESTART_BACKOFF)
+            }
+        }
+    }
+}
+
+/// Periodic liveness check of a running service (`jobs[].health`). A
+/// service failing `failures` checks in a row is restarted.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HealthCheck {
+    pub probe: ReadyProbe,
+    pub interval: Duration,
+    pub failures: usize,
+}
+
+/// What a change matching a running service does (`jobs[].on_change`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub enum OnChange {
+    /// Stop the service and start it again.
+    #[default]
+    Restart,
+    /// Deliver a signal to the service's process group, e.g. `SIGHUP` for
+    /// a graceful config reload.
+    Signal(Signal),
+    /// Run a reload command, e.g. `nginx -s reload`.
+    Command(String),
+    /// Keep the service running untouched.
+    Ignore,
+}
+
+impl OnChange {
+    /// The action as written in the config: `signal:HUP`, `command:CMD`.
+    pub fn label(&self) -> String {
+        match self {
+            OnChange::Restart => "restart".to_owned(),
+            OnChange::Signal(signal) => {
+                format!("signal:{}", signal.as_str().trim_start_matches("SIG"))
+            }
+            OnChange::Command(command) => format!("command:{}", command),
+            OnChange::Ignore => "ignore".to_owned(),
+        }
+    }
+}
+
+/// When a job runs relative to the outcome of earlier stages
+/// (`jobs[].when`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum When {
+    /// Run unless `--fail-fast` already stopped the run.
+    #[default]
+    OnSuccess,
+    /// Run only once an earlier job failed, e.g. to collect evidence.
+    OnFailure,
+    /// Run after fail-fast and cancellation too, e.g. to clean up.
+    Always,
+}
+
+impl When {
+    pub const VALUES: &'static [&'static str] = &["on_success", "on_failure", "always"];
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim() {
+            "on_success" => Some(When::OnSuccess),
+            "on_failure" => Some(When::OnFailure),
+            "always" => Some(When::Always),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            When::OnSuccess => "on_success",
+            When::OnFailure => "on_failure",
+            When::Always => "always",
+        }
+    }
+}
+
+/// How a job's processes are stopped on cancel, restart, reload and close
+/// (`jobs[].stop_signal`, `jobs[].stop_grace`). Unset fields fall back to
+/// the global cancel policy.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct StopPolicy {
+    pub signal: Option<Signal>,
+    /// Wait after the signal before the group is force-killed.
+    pub grace: Option<Duration>,
+}
+
+impl StopPolicy {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -51,8 +404,40 @@ pub struct Rules {
     inherited: Vec<String>,
     /// Per-job output policy (TASK-0041); Inherit is the default.
     output: OutputPolicy,
+    timestamps: Timestamps,
     /// Managed long-running service task (TASK-0035); opt-in, default false.
     service: bool,
+    /// Repository events that trigger this job in addition to `change`.
+    git: Vec<GitTrigger>,
+    /// Resources held while the job runs, in declaration order.
+    locks: Vec<ResourceLock>,
+    /// Cross-process lock held while the job runs (`jobs[].global_lock`).
+    global_lock: Option<String>,
+    limits: ProcessLimits,
+    /// Run the job's commands under a pseudo-terminal (`jobs[].tty`).
+    tty: bool,
+    /// Hand the terminal (stdin and its signals) to the job while it runs.
+    interactive: bool,
+    stop: StopPolicy,
+    /// Probe gating later stages on a service being up (`jobs[].ready`).
+    ready: Option<Readiness>,
+    restart: RestartPolicy,
+    /// Periodic liveness check of a service (`jobs[].health`).
+    health: Option<HealthCheck>,
+    on_change: OnChange,
+    /// Commands that stop a service's external state before its process
+    /// group is signalled (`jobs[].stop`).
+    teardown: Vec<String>,
+    /// Problem matchers run over the job's output (`jobs[].matchers`).
+    matchers: Vec<ProblemMatcher>,
+    reports: TestReports,
+    /// Exit codes counted as success (`jobs[].success_codes`).
+    success_codes: Vec<i32>,
+    /// Report failures as warnings that never fail the run.
+    allow_failure: bool,
+    when: When,
+    /// Indexes of `run` steps whose failure does not fail the job.
+    continue_on_error: Vec<usize>,
 }
 
 impl Rules {
@@ -76,7 +461,26 @@ impl Rules {
             environment: BTreeMap::new(),
             inherited: vec![],
             output: OutputPolicy::Inherit,
+            timestamps: Timestamps::None,
             service: false,
+            git: vec![],
+            locks: vec![],
+            global_lock: None,
+            limits: ProcessLimits::default(),
+            tty: false,
+            interactive: false,
+            stop: StopPolicy::default(),
+            ready: None,
+            restart: RestartPolicy::default(),
+            health: None,
+            on_change: OnChange::Restart,
+            teardown: vec![],
+            matchers: vec![],
+            reports: TestReports::default(),
+            success_codes: vec![0],
+            allow_failure: false,
+            when: When::OnSuccess,
+            continue_on_error: vec![],
         }
     }
 
@@ -103,7 +507,26 @@ impl Rules {
             environment: BTreeMap::new(),
             inherited: vec![],
             output: OutputPolicy::Inherit,
+            timestamps: Timestamps::None,
             service: false,
+            git: vec![],
+            locks: vec![],
+            global_lock: None,
+            limits: ProcessLimits::default(),
+            tty: false,
+            interactive: false,
+            stop: StopPolicy::default(),
+            ready: None,
+            restart: RestartPolicy::default(),
+            health: None,
+            on_change: OnChange::Restart,
+            teardown: vec![],
+            matchers: vec![],
+            reports: TestReports::default(),
+            success_codes: vec![0],
+            allow_failure: false,
+            when: When::OnSuccess,
+            continue_on_error: vec![],
         }
     }
 
@@ -158,6 +581,16 @@ impl Rules {
         self.output
     }
 
+    pub fn with_timestamps(mut self, timestamps: Timestamps) -> Self {
+        self.timestamps = timestamps;
+        self
+    }
+
+    /// How the job's output lines are timestamped.
+    pub fn timestamps(&self) -> Timestamps {
+        self.timestamps
+    }
+
     /// Marks the job as a managed long-running service (TASK-0035).
     pub fn with_service(mut self, service: bool) -> Self {
         self.service = service;
@@ -169,6 +602,183 @@ impl Rules {
         self.service
     }
 
+    /// Subscribes the job to repository events (`on.git` merged with
+    /// `jobs[].git`), deduplicated in declaration order.
+    pub fn with_git_triggers(mut self, triggers: Vec<GitTrigger>) -> Self {
+        self.git = triggers;
+        self
+    }
+
+    /// Repository events this job runs on.
+    pub fn git_triggers(&self) -> &[GitTrigger] {
+        &self.git
+    }
+
+    pub fn with_locks(mut self, locks: Vec<ResourceLock>) -> Self {
+        self.locks = locks;
+        self
+    }
+
+    /// Resources this job must hold before it may start.
+    pub fn locks(&self) -> &[ResourceLock] {
+        &self.locks
+    }
+
+    pub fn with_global_lock(mut self, lock: Option<String>) -> Self {
+        self.global_lock = lock;
+        self
+    }
+
+    /// Lock shared with other funzzy processes, if any.
+    pub fn global_lock(&self) -> Option<&str> {
+        self.global_lock.as_deref()
+    }
+
+    pub fn with_limits(mut self, limits: ProcessLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Resource limits and priority applied to the job's processes.
+    pub fn limits(&self) -> ProcessLimits {
+        self.limits
+    }
+
+    pub fn with_tty(mut self, tty: bool) -> Self {
+        self.tty = tty;
+        self
+    }
+
+    /// Whether commands see a terminal on stdout and stderr.
+    pub fn tty(&self) -> bool {
+        self.tty
+    }
+
+    pub fn with_interactive(mut self, interactive: bool) -> Self {
+        self.interactive = interactive;
+        self
+    }
+
+    /// Whether the job borrows the controlling terminal while it runs.
+    pub fn interactive(&self) -> bool {
+        self.interactive
+    }
+
+    pub fn with_stop(mut self, stop: StopPolicy) -> Self {
+        self.stop = stop;
+        self
+    }
+
+    /// Signal and grace period used to stop the job's processes.
+    pub fn stop(&self) -> StopPolicy {
+        self.stop
+    }
+
+    pub fn with_ready(mut self, ready: Option<Readiness>) -> Self {
+        self.ready = ready;
+        self
+    }
+
+    /// The readiness probe of a service, if it declares one.
+    pub fn ready(&self) -> Option<&Readiness> {
+        self.ready.as_ref()
+    }
+
+    pub fn with_restart(mut self, restart: RestartPolicy) -> Self {
+        self.restart = restart;
+        self
+    }
+
+    /// How a service is restarted after an unexpected exit.
+    pub fn restart(&self) -> RestartPolicy {
+        self.restart
+    }
+
+    pub fn with_health(mut self, health: Option<HealthCheck>) -> Self {
+        self.health = health;
+        self
+    }
+
+    /// The periodic health check of a service, if it declares one.
+    pub fn health(&self) -> Option<&HealthCheck> {
+        self.health.as_ref()
+    }
+
+    pub fn with_on_change(mut self, on_change: OnChange) -> Self {
+        self.on_change = on_change;
+        self
+    }
+
+    /// What a matching change does to the running service.
+    pub fn on_change(&self) -> &OnChange {
+        &self.on_change
+    }
+
+    pub fn with_teardown(mut self, commands: Vec<String>) -> Self {
+        self.teardown = commands;
+        self
+    }
+
+    /// The service's `stop` commands, in order.
+    pub fn teardown(&self) -> &[String] {
+        &self.teardown
+    }
+
+    pub fn with_matchers(mut self, matchers: Vec<ProblemMatcher>) -> Self {
+        self.matchers = matchers;
+        self
+    }
+
+    pub fn matchers(&self) -> &[ProblemMatcher] {
+        &self.matchers
+    }
+
+    pub fn with_reports(mut self, reports: TestReports) -> Self {
+        self.reports = reports;
+        self
+    }
+
+    pub fn reports(&self) -> &TestReports {
+        &self.reports
+    }
+
+    pub fn with_success_codes(mut self, codes: Vec<i32>) -> Self {
+        self.success_codes = codes;
+        self
+    }
+
+    pub fn success_codes(&self) -> &[i32] {
+        &self.success_codes
+    }
+
+    pub fn with_allow_failure(mut self, allow_failure: bool) -> Self {
+        self.allow_failure = allow_failure;
+        self
+    }
+
+    pub fn allow_failure(&self) -> bool {
+        self.allow_failure
+    }
+
+    pub fn with_when(mut self, when: When) -> Self {
+        self.when = when;
+        self
+    }
+
+    pub fn when(&self) -> When {
+        self.when
+    }
+
+    pub fn with_continue_on_error(mut self, steps: Vec<usize>) -> Self {
+        self.continue_on_error = steps;
+        self
+    }
+
+    /// Whether a failure of the `run` step at `index` lets the job go on.
+    pub fn continues_on_error(&self, index: usize) -> bool {
+        self.continue_on_error.contains(&index)
+    }
+
     pub fn with_parallel(mut self, group: String) -> Self {
         self.parallel = Some(group);
         self
@@ -370,7 +980,7 @@ impl Rules {
             ));
         }
 
-        if self.watch_patterns().is_empty() && !self.run_on_init() {
+        if self.watch_patterns().is_empty() && !self.run_on_init() && self.git.is_empty() {
             return Err(format!(
                 "job '{}' must contain a `change` and/or `run_on_init` property.",
                 name
@@ -514,6 +1124,10 @@ pub fn available_targets(rules: &[Rules]) -> String {
         if rule.run_on_init {
             output.push_str("    run_on_init: true\n");
         }
+        if !rule.git.is_empty() {
+            let git: Vec<&str> = rule.git.iter().map(|trigger| trigger.label()).collect();
+            output.push_str(&format!("    git: {}\n", git.join(", ")));
+        }
         if rule.recovery.is_some() {
             output.push_str("    recovery: configured (approval required)\n");
         }
@@ -68,6 +68,9 @@ pub struct SystemProcessReaper;
 
 impl ProcessReaper for SystemProcessReaper {
     fn reap(&self, verbose: bool) {
+        // Services' `stop` commands run while their groups are still up;
+        // the close hook runs after the reap, so it still comes last.
+        crate::process_owner::run_teardowns(&crate::executor::SystemProcessRunner, verbose);
         let (signal, grace) = crate::process_owner::shutdown_policy();
         let _ = crate::process_owner::shutdown_all(signal, grace, verbose);
     }
@@ -374,6 +377,10 @@ mod tests {
             })
         }
 
+        fn signal(&mut self, _signal: nix::sys::signal::Signal) -> io::Result<()> {
+            Ok(())
+        }
+
         fn shutdown(
             &mut self,
             _signal: nix::sys::signal::Signal,
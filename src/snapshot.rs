@@ -81,6 +81,14 @@ pub struct CorrelatedSnapshot {
     /// output exists. Rendered identically by status, await, and subscribe.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub failure_evidence: Option<crate::output::FailureEvidence>,
+    /// Problem-matcher diagnostics of the latest generation (`jobs[].matchers`),
+    /// bounded like the `diagnostics` method; omitted when there are none.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub diagnostics: Vec<crate::matchers::Diagnostic>,
+    /// In-progress git operation filesystem batches are held for, reported
+    /// alongside the `held_for_git` state; omitted when nothing is held.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub git_operation: Option<String>,
 }
 
 struct Subscriber {
@@ -217,6 +225,12 @@ impl SnapshotBroker {
         } else {
             None
         };
+        let diagnostics = self
+            .outputs
+            .as_ref()
+            .and_then(|outputs| outputs.diagnostics(state.generation(), None).ok())
+            .map(|found| found.diagnostics)
+            .unwrap_or_default();
         CorrelatedSnapshot {
             instance: self.instance.clone(),
             generation: state.generation(),
@@ -250,6 +264,8 @@ impl SnapshotBroker {
                 .as_ref()
                 .map(|lifecycle| lifecycle.current()),
             failure_evidence,
+            diagnostics,
+            git_operation: state.git_operation().map(str::to_owned),
         }
     }
 
@@ -390,6 +406,13 @@ mod tests {
                 name: "check".to_owned(),
                 state: crate::executor::TaskState::Passed,
                 duration_ms: Some(42),
+                waiting: None,
+                failure_reason: None,
+                restarts: None,
+                health: None,
+                tests: None,
+                steps: vec![],
+                superseded_by: None,
             },
         });
         broker.publish();
@@ -535,6 +558,13 @@ mod tests {
                 name: "test @agent-final".to_owned(),
                 state: crate::executor::TaskState::Passed,
                 duration_ms: Some(42),
+                waiting: None,
+                failure_reason: None,
+                restarts: None,
+                health: None,
+                tests: None,
+                steps: vec![],
+                superseded_by: None,
             },
         });
         state.lock().unwrap().apply(Event::Finished {
@@ -119,36 +119,41 @@ pub fn print_time_elapsed(_elapsed_param: std::time::Duration) -> () {
 
 /// Produces the deterministic per-job duration table shared by every local
 /// result path. Callers supply executor terminal snapshots already sorted by
-/// configured declaration order; this function never measures time.
+/// configured declaration order; this function never measures time. Steps
+/// of multi-command jobs are indented under their job.
 pub fn job_duration_rows(tasks: &[crate::executor::TaskSnapshot]) -> Vec<String> {
     if tasks.is_empty() {
         return vec![];
     }
 
-    let identities: Vec<String> = tasks
-        .iter()
-        .map(|task| match task.id == task.name {
+    let mut entries: Vec<(String, String, Option<u64>)> = vec![];
+    for task in tasks {
+        let identity = match task.id == task.name {
             true => task.name.clone(),
             false => format!("[{}] {}", task.id, task.name),
-        })
-        .collect();
-    let states: Vec<&str> = tasks
-        .iter()
-        .map(|task| match task.state {
-            crate::executor::TaskState::Passed => "passed",
-            crate::executor::TaskState::Failed => "failed",
-            crate::executor::TaskState::Cancelled => "cancelled",
-        })
-        .collect();
-    let name_width = identities
+        };
+        entries.push((identity, task.state.label().to_owned(), task.duration_ms));
+        for step in &task.steps {
+            let state = match step.exit_code {
+                Some(code) if code != 0 => format!("{} ({code})", step.state.label()),
+                _ => step.state.label().to_owned(),
+            };
+            entries.push((
+                format!("  {}. {}", step.index, step.command),
+                state,
+                step.duration_ms,
+            ));
+        }
+    }
+    let name_width = entries
         .iter()
-        .map(String::len)
+        .map(|(identity, _, _)| identity.len())
         .max()
         .unwrap_or(3)
         .max("JOB".len());
-    let state_width = states
+    let state_width = entries
         .iter()
-        .map(|state| state.len())
+        .map(|(_, state, _)| state.len())
         .max()
         .unwrap_or(6)
         .max("RESULT".len());
@@ -156,23 +161,43 @@ pub fn job_duration_rows(tasks: &[crate::executor::TaskSnapshot]) -> Vec<String>
         "{:<name_width$}  {:<state_width$}  DURATION",
         "JOB", "RESULT"
     )];
-    rows.extend(
-        identities
-            .iter()
-            .zip(states)
-            .zip(tasks)
-            .map(|((identity, state), task)| {
-                let duration = task
-                    .duration_ms
-                    .map(format_job_duration)
-                    .unwrap_or_else(|| "-".to_owned());
-                format!("{identity:<name_width$}  {state:<state_width$}  {duration}")
-            }),
-    );
+    rows.extend(entries.iter().map(|(identity, state, duration_ms)| {
+        let duration = duration_ms
+            .map(format_job_duration)
+            .unwrap_or_else(|| "-".to_owned());
+        format!("{identity:<name_width$}  {state:<state_width$}  {duration}")
+    }));
+    rows
+}
+
+/// Per-job test counts and failing test names printed under the duration
+/// table; empty when no finished job declared `reports`.
+pub fn test_summary_rows(tasks: &[crate::executor::TaskSnapshot]) -> Vec<String> {
+    let mut rows = vec![];
+    for task in tasks {
+        let Some(tests) = &task.tests else {
+            continue;
+        };
+        let identity = match task.id == task.name {
+            true => task.name.clone(),
+            false => format!("[{}] {}", task.id, task.name),
+        };
+        rows.push(format!("Tests {identity}: {}", tests.counts()));
+        for failure in &tests.failures {
+            rows.push(match &failure.message {
+                Some(message) => format!("  FAILED {}: {message}", failure.name),
+                None => format!("  FAILED {}", failure.name),
+            });
+        }
+        let unlisted = tests.failed.saturating_sub(tests.failures.len() as u64);
+        if unlisted > 0 {
+            rows.push(format!("  ... and {unlisted} more"));
+        }
+    }
     rows
 }
 
-fn format_job_duration(duration_ms: u64) -> String {
+pub fn format_job_duration(duration_ms: u64) -> String {
     if duration_ms < 100 {
         format!("{duration_ms}ms")
     } else {
@@ -209,6 +234,7 @@ pub fn present_results(
                 let status = match task_outcome {
                     crate::plan::TaskOutcome::Passed => "passed",
                     crate::plan::TaskOutcome::Failed { .. } => "failed",
+                    crate::plan::TaskOutcome::Warning { .. } => "warning",
                     crate::plan::TaskOutcome::Cancelled => "cancelled",
                     crate::plan::TaskOutcome::Skipped => "skipped",
                 };
@@ -219,7 +245,10 @@ pub fn present_results(
         }
     }
 
-    for row in job_duration_rows(tasks) {
+    for row in job_duration_rows(tasks)
+        .into_iter()
+        .chain(test_summary_rows(tasks))
+    {
         println!("{}", row);
         logging::log_line(&row);
     }
@@ -282,6 +311,13 @@ mod tests {
                 name: "format".to_owned(),
                 state: TaskState::Passed,
                 duration_ms: Some(700),
+                waiting: None,
+                failure_reason: None,
+                restarts: None,
+                health: None,
+                tests: None,
+                steps: vec![],
+                superseded_by: None,
             },
             TaskSnapshot {
                 position: 1,
@@ -289,6 +325,13 @@ mod tests {
                 name: "lint".to_owned(),
                 state: TaskState::Failed,
                 duration_ms: Some(1_800),
+                waiting: None,
+                failure_reason: None,
+                restarts: None,
+                health: None,
+                tests: None,
+                steps: vec![],
+                superseded_by: None,
             },
             TaskSnapshot {
                 position: 2,
@@ -296,6 +339,13 @@ mod tests {
                 name: "docs".to_owned(),
                 state: TaskState::Cancelled,
                 duration_ms: None,
+                waiting: None,
+                failure_reason: None,
+                restarts: None,
+                health: None,
+                tests: None,
+                steps: vec![],
+                superseded_by: None,
             },
         ]);
 
@@ -1,7 +1,8 @@
 //! Pure command template expansion.
 //!
-//! Expands `{{filepath}}`, `{{absolute_path}}`, `{{relative_filepath}}` and
-//! `{{relative_path}}` placeholders inside commands. This module has no YAML
+//! Expands `{{filepath}}`, `{{absolute_path}}`, `{{relative_filepath}}`,
+//! `{{relative_path}}`, `{{paths}}`, and `{{git.before}}`/`{{git.after}}`
+//! placeholders inside commands. This module has no YAML
 //! parsing and no console output: unknown variables are collected and
 //! reported to the caller, which decides how to present them.
 
@@ -30,6 +31,36 @@ fn shell_escape(path: &str) -> String {
     format!("'{}'", path.replace('\'', "'\\''"))
 }
 
+/// Old and new commit of a git-triggered run, exposed as `{{git.before}}` and
+/// `{{git.after}}`. Empty strings stand for "no commit" (unborn branch).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GitTransition {
+    pub before: String,
+    pub after: String,
+}
+
+/// Substitutes only the git placeholders, leaving every other variable for
+/// the regular expansion pass. Applied to git-triggered plans before
+/// scheduling; runs without a transition expand them to empty strings.
+pub fn expand_git(command: &str, transition: &GitTransition) -> String {
+    if !command.contains("{{") {
+        return command.to_owned();
+    }
+    let mut parts = command.split("{{");
+    let mut expanded = parts.next().unwrap_or_default().to_owned();
+    for part in parts {
+        match part.split_once("}}") {
+            Some((variable, rest)) => match variable.trim() {
+                "git.before" => expanded.push_str(&format!("{}{}", transition.before, rest)),
+                "git.after" => expanded.push_str(&format!("{}{}", transition.after, rest)),
+                _ => expanded.push_str(&format!("{{{{{}", part)),
+            },
+            None => expanded.push_str(&format!("{{{{{}", part)),
+        }
+    }
+    expanded
+}
+
 pub struct TemplateOutput {
     pub commands: Vec<String>,
     pub unknown_variables: Vec<String>,
@@ -139,6 +170,9 @@ fn expand_command(
                         // TASK-0031: the complete normalized changed-path set of
                         // the triggering batch, shell-escaped and space-joined.
                         "paths" => format!("{}{}", paths, rest),
+                        // Substituted by `expand_git` for git-triggered runs;
+                        // any other trigger has no commit transition.
+                        "git.before" | "git.after" => rest.to_owned(),
                         _ => {
                             unknown_variables.push(tpl.to_owned());
                             format!("{}{}{}{}", "{{", parts[0], "}}", parts[1])
@@ -388,4 +422,33 @@ mod paths_tests {
         );
         assert_eq!(output.commands, vec!["echo []".to_owned()]);
     }
+
+    #[test]
+    fn it_expands_git_transition_and_leaves_other_variables() {
+        let transition = super::GitTransition {
+            before: "abc".to_owned(),
+            after: "def".to_owned(),
+        };
+        assert_eq!(
+            super::expand_git(
+                "git diff {{git.before}}..{{ git.after }} -- {{filepath}}",
+                &transition
+            ),
+            "git diff abc..def -- {{filepath}}"
+        );
+    }
+
+    #[test]
+    fn git_variables_expand_empty_without_a_transition() {
+        let output = template(
+            vec!["echo [{{git.before}}]".to_owned()],
+            TemplateOptions {
+                filepath: None,
+                paths: vec![],
+                current_dir: "/".to_owned(),
+            },
+        );
+        assert_eq!(output.commands, vec!["echo []"]);
+        assert!(output.unknown_variables.is_empty());
+    }
 }
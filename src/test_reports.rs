@@ -0,0 +1,465 @@
+//! Test reports (`jobs[].reports`): per-test pass/fail/skip counts and the
+//! failing test names, read once a job finishes from the JUnit XML files it
+//! wrote and from TAP on its stdout.
+//!
+//! TAP is read from the captured stdout, which keeps only the last
+//! `CAPTURE_STREAM_BYTES` of a stream; a suite whose output outgrew the
+//! capture is counted from the retained tail only.
+//!
+//! JUnit files are matched by glob relative to the job's cwd and read only
+//! when written since the job started, so a report left by an earlier run
+//! never describes this one. The XML reader understands what test runners
+//! emit (`testcase` elements with `failure`, `error` and `skipped`
+//! children); it is not a general XML parser.
+
+use crate::rules::TestReports;
+use regex::Regex;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime};
+
+/// Failing tests listed per job; the `failed` count still covers the rest.
+pub const MAX_FAILURES: usize = 50;
+
+/// Longest failure message kept, in characters.
+const MAX_MESSAGE_CHARS: usize = 300;
+
+/// Slack for file systems that store modification times in whole seconds.
+const MTIME_SLACK: Duration = Duration::from_secs(1);
+
+/// Per-test outcome counts of one job.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestSummary {
+    pub passed: u64,
+    pub failed: u64,
+    pub skipped: u64,
+    /// The first [`MAX_FAILURES`] failing tests in report order.
+    pub failures: Vec<TestFailure>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct TestFailure {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl TestSummary {
+    pub fn is_empty(&self) -> bool {
+        self.passed + self.failed + self.skipped == 0
+    }
+
+    /// `3 passed, 1 failed, 0 skipped`, as printed by `fzz run`.
+    pub fn counts(&self) -> String {
+        format!(
+            "{} passed, {} failed, {} skipped",
+            self.passed, self.failed, self.skipped
+        )
+    }
+
+    fn fail(&mut self, name: String, message: Option<String>) {
+        self.failed += 1;
+        if self.failures.len() < MAX_FAILURES {
+            self.failures.push(TestFailure {
+                name,
+                message: message.map(|message| first_line(&message)),
+            });
+        }
+    }
+
+    fn merge(&mut self, other: TestSummary) {
+        self.passed += other.passed;
+        self.skipped += other.skipped;
+        self.failed += other.failed;
+        let room = MAX_FAILURES.saturating_sub(self.failures.len());
+        self.failures.extend(other.failures.into_iter().take(room));
+    }
+}
+
+/// Reads the reports a finished job declares. `since` is when its first
+/// command started; None when no report yielded a test.
+pub fn collect(
+    reports: &TestReports,
+    cwd: Option<&Path>,
+    since: SystemTime,
+    stdout: &[u8],
+) -> Option<TestSummary> {
+    let mut summary = TestSummary::default();
+    if let Some(pattern) = &reports.junit {
+        let pattern = match cwd {
+            Some(cwd) => cwd.join(pattern),
+            None => PathBuf::from(pattern),
+        };
+        let since = since.checked_sub(MTIME_SLACK).unwrap_or(since);
+        let paths = glob::glob(&pattern.to_string_lossy())
+            .into_iter()
+            .flatten()
+            .flatten();
+        for path in paths {
+            let fresh = std::fs::metadata(&path)
+                .and_then(|metadata| metadata.modified())
+                .is_ok_and(|modified| modified >= since);
+            if let (true, Ok(xml)) = (fresh, std::fs::read_to_string(&path)) {
+                summary.merge(parse_junit(&xml));
+            }
+        }
+    }
+    if reports.tap {
+        summary.merge(parse_tap(&String::from_utf8_lossy(stdout)));
+    }
+    (!summary.is_empty()).then_some(summary)
+}
+
+#[derive(PartialEq)]
+enum Outcome {
+    Passed,
+    Failed,
+    Skipped,
+}
+
+struct Case {
+    name: String,
+    outcome: Outcome,
+    message: Option<String>,
+    /// The failure's `type`, reported when it carries no message.
+    kind: Option<String>,
+}
+
+/// Counts the `testcase` elements of a JUnit XML document.
+pub fn parse_junit(xml: &str) -> TestSummary {
+    let mut summary = TestSummary::default();
+    let mut case: Option<Case> = None;
+    let mut in_failure = false;
+    let mut rest = xml;
+    while let Some(start) = rest.find('<') {
+        if in_failure {
+            take_message(&mut case, &decode(&rest[..start]));
+        }
+        rest = &rest[start..];
+        if let Some(body) = rest.strip_prefix("<![CDATA[") {
+            let end = body.find("]]>").unwrap_or(body.len());
+            if in_failure {
+                take_message(&mut case, &body[..end]);
+            }
+            rest = body.get(end + 3..).unwrap_or_default();
+            continue;
+        }
+        if let Some(body) = rest.strip_prefix("<!--") {
+            rest = body.find("-->").map_or("", |end| &body[end + 3..]);
+            continue;
+        }
+        let Some(end) = tag_end(rest) else {
+            break;
+        };
+        let tag = &rest[1..end];
+        rest = &rest[end + 1..];
+        if tag.starts_with('?') || tag.starts_with('!') {
+            continue;
+        }
+        let closing = tag.starts_with('/');
+        let self_closing = tag.ends_with('/');
+        let tag = tag.trim_start_matches('/').trim_end_matches('/');
+        let element = tag.split_whitespace().next().unwrap_or_default();
+        match (closing, element) {
+            (false, "testcase") => {
+                finish(&mut summary, case.take());
+                let name = attribute(tag, "name").unwrap_or_default();
+                let name = match attribute(tag, "classname") {
+                    Some(class) if !class.is_empty() && class != name => {
+                        format!("{}::{}", class, name)
+                    }
+                    _ => name,
+                };
+                case = Some(Case {
+                    name,
+                    outcome: Outcome::Passed,
+                    message: None,
+                    kind: None,
+                });
+                if self_closing {
+                    finish(&mut summary, case.take());
+                }
+            }
+            (true, "testcase") => finish(&mut summary, case.take()),
+            (false, "failure" | "error") => {
+                if let Some(case) = &mut case {
+                    case.outcome = Outcome::Failed;
+                    case.message =
+                        attribute(tag, "message").filter(|message| !message.trim().is_empty());
+                    case.kind = attribute(tag, "type");
+                }
+                in_failure = !self_closing;
+            }
+            (true, "failure" | "error") => in_failure = false,
+            (false, "skipped") => {
+                if let Some(case) = case.as_mut().filter(|case| case.outcome == Outcome::Passed) {
+                    case.outcome = Outcome::Skipped;
+                }
+            }
+            _ => {}
+        }
+    }
+    finish(&mut summary, case.take());
+    summary
+}
+
+fn finish(summary: &mut TestSummary, case: Option<Case>) {
+    let Some(case) = case else {
+        return;
+    };
+    match case.outcome {
+        Outcome::Passed => summary.passed += 1,
+        Outcome::Skipped => summary.skipped += 1,
+        Outcome::Failed => summary.fail(case.name, case.message.or(case.kind)),
+    }
+}
+
+/// Uses the failure body when the element had no `message` attribute.
+fn take_message(case: &mut Option<Case>, text: &str) {
+    if let Some(case) = case.as_mut().filter(|case| case.message.is_none()) {
+        if !text.trim().is_empty() {
+            case.message = Some(text.to_owned());
+        }
+    }
+}
+
+/// Index of the `>` closing a tag, skipping quoted attribute values.
+fn tag_end(tag: &str) -> Option<usize> {
+    let mut quote = None;
+    for (index, character) in tag.char_indices() {
+        match (quote, character) {
+            (None, '"' | '\'') => quote = Some(character),
+            (Some(open), _) if open == character => quote = None,
+            (None, '>') => return Some(index),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    static ATTRIBUTES: OnceLock<Regex> = OnceLock::new();
+    ATTRIBUTES
+        .get_or_init(|| {
+            Regex::new(r#"([\w:.-]+)\s*=\s*(?:"([^"]*)"|'([^']*)')"#).expect("valid attributes")
+        })
+        .captures_iter(tag)
+        .find(|captures| &captures[1] == name)
+        .and_then(|captures| captures.get(2).or_else(|| captures.get(3)))
+        .map(|value| decode(value.as_str()))
+}
+
+/// Resolves the predefined and numeric XML entities.
+fn decode(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_owned();
+    }
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let entity = rest.find(';').filter(|end| *end <= 10).and_then(|end| {
+            let value = match &rest[1..end] {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                numeric => numeric
+                    .strip_prefix("#x")
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .or_else(|| numeric.strip_prefix('#')?.parse().ok())
+                    .and_then(char::from_u32),
+            };
+            value.map(|value| (value, end))
+        });
+        match entity {
+            Some((value, end)) => {
+                decoded.push(value);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+/// Counts the top-level test points of a TAP stream. `# SKIP` points are
+/// skipped; `# TODO` points never fail the count.
+pub fn parse_tap(text: &str) -> TestSummary {
+    static POINT: OnceLock<Regex> = OnceLock::new();
+    let point = POINT.get_or_init(|| {
+        Regex::new(r"^(not )?ok\b(?:\s+(\d+))?(?:\s*-)?\s*([^#]*?)\s*(?:#\s*(\w+).*)?$")
+            .expect("valid TAP test point")
+    });
+    let mut summary = TestSummary::default();
+    let lines: Vec<&str> = text.lines().collect();
+    for (index, line) in lines.iter().enumerate() {
+        let Some(captures) = point.captures(line.trim_end()) else {
+            continue;
+        };
+        let failed = captures.get(1).is_some();
+        let directive = captures
+            .get(4)
+            .map(|directive| directive.as_str().to_ascii_lowercase());
+        match directive.as_deref() {
+            Some(directive) if directive.starts_with("skip") => summary.skipped += 1,
+            Some(directive) if directive.starts_with("todo") => {
+                if failed {
+                    summary.skipped += 1;
+                } else {
+                    summary.passed += 1;
+                }
+            }
+            _ if failed => {
+                let name = match (captures[3].trim(), captures.get(2)) {
+                    ("", Some(number)) => format!("test {}", number.as_str()),
+                    ("", None) => format!("test {}", summary.failed + 1),
+                    (name, _) => name.to_owned(),
+                };
+                summary.fail(name, tap_message(&lines[index + 1..]));
+            }
+            _ => summary.passed += 1,
+        }
+    }
+    summary
+}
+
+/// `message:` of the YAML diagnostic block following a failed test point.
+fn tap_message(lines: &[&str]) -> Option<String> {
+    if lines.first().map(|line| line.trim()) != Some("---") {
+        return None;
+    }
+    lines[1..]
+        .iter()
+        .take_while(|line| line.trim() != "...")
+        .find_map(|line| line.trim().strip_prefix("message:"))
+        .map(|message| {
+            message
+                .trim()
+                .trim_matches(|quote| quote == '\'' || quote == '"')
+                .to_owned()
+        })
+        .filter(|message| !message.is_empty())
+}
+
+fn first_line(message: &str) -> String {
+    let line = message
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or_default();
+    match line.char_indices().nth(MAX_MESSAGE_CHARS) {
+        Some((cut, _)) => format!("{}...", &line[..cut]),
+        None => line.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn junit_counts_outcomes_and_names_failures() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites tests="5" failures="2">
+  <testsuite name="app" tests="5">
+    <testcase name="parser::accepts_empty" classname="app" time="0.001"/>
+    <testcase name="parser::rejects_tabs" classname="app">
+      <failure message="assertion `left == right` failed&#10;  left: 1" type="panic"/>
+    </testcase>
+    <testcase name="io::reads" classname="app"><error type="timeout"><![CDATA[
+thread 'io::reads' panicked at src/io.rs:4:5
+]]></error></testcase>
+    <testcase name="slow" classname="app"><skipped/></testcase>
+    <!-- <testcase name="commented"/> -->
+    <testcase name="x &amp; y" classname="app"></testcase>
+  </testsuite>
+</testsuites>"#;
+        let summary = parse_junit(xml);
+
+        assert_eq!((summary.passed, summary.failed, summary.skipped), (2, 2, 1));
+        assert_eq!(
+            summary.failures,
+            vec![
+                TestFailure {
+                    name: "app::parser::rejects_tabs".to_owned(),
+                    message: Some("assertion `left == right` failed".to_owned()),
+                },
+                TestFailure {
+                    name: "app::io::reads".to_owned(),
+                    message: Some("thread 'io::reads' panicked at src/io.rs:4:5".to_owned()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn junit_failure_body_is_the_message_without_an_attribute() {
+        let summary = parse_junit(
+            "<testcase name=\"t\" classname=\"t\"><failure>\n  expected 3, got 4\n  at line 9</failure></testcase>",
+        );
+        assert_eq!(summary.failures[0].name, "t");
+        assert_eq!(
+            summary.failures[0].message.as_deref(),
+            Some("expected 3, got 4")
+        );
+    }
+
+    #[test]
+    fn tap_counts_points_directives_and_diagnostics() {
+        let tap = "TAP version 13\n1..5\nok 1 - adds\nnot ok 2 - subtracts\n  ---\n  message: 'expected 1 to equal 2'\n  ...\nok 3 - network # SKIP offline\nnot ok 4 - later # TODO not done\nnot ok 5\n    ok 1 - nested subtest\n";
+        let summary = parse_tap(tap);
+
+        assert_eq!((summary.passed, summary.failed, summary.skipped), (1, 2, 2));
+        assert_eq!(summary.failures[0].name, "subtracts");
+        assert_eq!(
+            summary.failures[0].message.as_deref(),
+            Some("expected 1 to equal 2")
+        );
+        assert_eq!(summary.failures[1].name, "test 5");
+        assert_eq!(summary.failures[1].message, None);
+    }
+
+    #[test]
+    fn collect_reads_only_reports_written_during_the_run() {
+        let directory = std::env::temp_dir().join(format!("funzzy-reports-{}", std::process::id()));
+        std::fs::create_dir_all(directory.join("reports")).unwrap();
+        std::fs::write(
+            directory.join("reports/junit.xml"),
+            "<testcase name=\"a\"/><testcase name=\"b\"><failure message=\"boom\"/></testcase>",
+        )
+        .unwrap();
+        let reports = TestReports {
+            junit: Some("reports/*.xml".to_owned()),
+            tap: true,
+        };
+        let started = SystemTime::now() - Duration::from_secs(5);
+
+        let summary =
+            collect(&reports, Some(&directory), started, b"ok 1 - tap point\n").expect("summary");
+        assert_eq!((summary.passed, summary.failed), (2, 1));
+        assert_eq!(summary.failures[0].name, "b");
+
+        let later = SystemTime::now() + Duration::from_secs(60);
+        assert_eq!(collect(&reports, Some(&directory), later, b""), None);
+        std::fs::remove_dir_all(directory).ok();
+    }
+
+    #[test]
+    fn failures_are_bounded_but_counted() {
+        let tap: String = (0..MAX_FAILURES + 10)
+            .map(|test| format!("not ok {} - case {}\n", test + 1, test))
+            .collect();
+        let summary = parse_tap(&tap);
+        assert_eq!(summary.failed as usize, MAX_FAILURES + 10);
+        assert_eq!(summary.failures.len(), MAX_FAILURES);
+    }
+}
@@ -15,8 +15,10 @@ use crate::control::{
 use crate::diagnostics;
 use crate::duration_recorder::DurationRecorder;
 use crate::errors::FzzError;
+use crate::event_trace;
 use crate::executor::RunMetadata;
-use crate::identity::{Batch, BatchId};
+use crate::git_state::{self, Admission, GitChange, GitHold, GitOperation, GitTracker};
+use crate::identity::{AtomicSequence, Batch, BatchId};
 use crate::output::OutputRegistry;
 use crate::plan::RunPlan;
 use crate::snapshot::SnapshotBroker;
@@ -80,12 +82,36 @@ pub trait RunStrategy {
         revision: Option<ConfigRevision>,
     ) -> Option<u64>;
 
+    /// Executes the plan selected by repository events alone (`on.git` /
+    /// `jobs[].git`), with `trigger` naming the event (`git:<event>`).
+    /// Behaves like a change run without a filesystem batch.
+    fn run_git(
+        &self,
+        plan: RunPlan,
+        trigger: &str,
+        revision: Option<ConfigRevision>,
+    ) -> Option<u64>;
+
     /// Called with each normalized batch before routing (default no-op), so
     /// the pending-debounce observation reflects open windows.
     fn on_batch(&self, _batch: &Batch) {}
 
     /// Called after the batch finished routing (scheduled or explicit no-op).
     fn on_batch_complete(&self) {}
+
+    /// Called when filesystem batches start being held for an in-progress
+    /// git operation, and again if the operation kind changes.
+    fn on_git_hold(&self, _operation: GitOperation) {}
+
+    /// Called once the git operation finished, before the coalesced batch
+    /// routes.
+    fn on_git_release(&self) {}
+
+    /// The recorded trace this strategy replays (`fzz replay`) in place of
+    /// any backend; None for live watching.
+    fn replay(&self) -> Option<&event_trace::Replay> {
+        None
+    }
 }
 
 /// Runs the watch loop: registers filesystem watches, publishes readiness,
@@ -200,98 +226,259 @@ pub fn watch_loop(
         .as_ref()
         .map(|coordinator| coordinator.requested_flag());
     let ready_shutdown = shutdown;
+    // A replayed trace describes someone else's tree: the content gate and
+    // repository state of this checkout must not filter or add to it.
+    let replaying = strategy.replay().is_some();
     let gate = std::cell::RefCell::new(ModificationGate::new());
     let baseline_paths = initial
         .baseline_paths()
         .into_iter()
         .map(|path| path.display().to_string())
         .collect::<Vec<_>>();
-    gate.borrow_mut().seed(&baseline_paths);
-
-    watcher::events(
-        list_of_watched_paths,
-        || {
-            if let Some(ready) = &reload_ready {
-                let _ = ready.lock().unwrap().recv_timeout(Duration::from_secs(30));
+    if !replaying {
+        gate.borrow_mut().seed(&baseline_paths);
+    }
+    let git_dir = git_state::git_dir(initial.root()).filter(|_| !replaying);
+    let git_hold = std::cell::RefCell::new(GitHold::with_git_dir(git_dir.clone()));
+    let was_held = std::cell::Cell::new(false);
+    // Built on first use and dropped while no job subscribes to git events
+    // (a reload may add or remove them), so other projects never poll
+    // `.git`; a fresh tracker baselines and never fires on creation.
+    let git_tracker: std::cell::RefCell<Option<GitTracker>> = std::cell::RefCell::new(None);
+    let observe_git = || {
+        let mut tracker = git_tracker.borrow_mut();
+        if git_dir.is_none() || !watches.lock().unwrap().uses_git() {
+            *tracker = None;
+            return None;
+        }
+        tracker
+            .get_or_insert_with(|| GitTracker::new(git_dir.clone()))
+            .observe()
+    };
+
+    let route = |batch: Batch, git: Option<GitChange>| {
+        // Lock once per batch: the whole routing decision (match/ignore,
+        // plan, trigger, frozen revision) reads one committed revision
+        // (contract §4). The revision rides the schedule so the generated
+        // run freezes exactly the routed revision (TASK-0091, AC7).
+        let watches_guard = watches.lock().unwrap();
+        let revision = watches_guard.revision().cloned();
+        let fired = git.as_ref().map(|change| change.triggers.as_slice());
+        match watches_guard.route_plan(&batch.changed, fired.unwrap_or_default()) {
+            Some((plan, trigger)) => {
+                if !replaying {
+                    stdout::clear_screen();
+                }
+                if verbose {
+                    emit_matched_decisions(&watches_guard, &batch, &trigger);
+                }
+                let plan = match &git {
+                    Some(change) => plan.with_git_transition(&change.transition),
+                    None => plan,
+                };
+                let generation = strategy.run_change(plan, &trigger, &batch, revision);
+                if verbose {
+                    observe_triggers(&watches_guard, &batch, &trigger, generation);
+                }
             }
-            strategy.on_ready();
-            if let Some(shutdown) = &ready_shutdown {
-                shutdown.mark_ready();
+            None => {
+                if verbose {
+                    emit_non_matched_decisions(&watches_guard, &batch);
+                }
             }
+        }
+        drop(watches_guard);
+        strategy.on_batch_complete();
+    };
+
+    // Repository events between filesystem batches (`on.git`): the tracker
+    // baseline advances on every observation, so one HEAD move fires once.
+    let route_git = |change: GitChange| {
+        let watches_guard = watches.lock().unwrap();
+        let revision = watches_guard.revision().cloned();
+        let Some((plan, trigger)) = watches_guard.route_plan(&[], &change.triggers) else {
+            return;
+        };
+        drop(watches_guard);
+        stdout::clear_screen();
+        let plan = plan.with_git_transition(&change.transition);
+        let commands = plan.commands().len();
+        let generation = strategy.run_git(plan, &trigger, revision);
+        if verbose {
+            diagnostics::debug(&diagnostics::Record {
+                source: Some("git"),
+                decision: Some("scheduled"),
+                generation,
+                policy: Some(strategy.policy()),
+                commands: Some(commands),
+                note: Some(trigger),
+                ..Default::default()
+            });
+        }
+    };
 
-            let initial = watches.lock().unwrap().clone();
-            let init_revision = initial.revision().cloned();
-            match init_action(initial.run_on_init_plan(), run_on_init) {
-                InitAction::Run(plan) => {
-                    stdout::info("Running on init commands.");
-                    let commands = plan.commands().len();
-                    let generation = strategy.run_init(plan, init_revision);
-                    // Blocking strategies emit their own in-process run
-                    // record; only non-blocking init schedules are recorded
-                    // here with their generation.
-                    if verbose && generation.is_some() {
-                        diagnostics::debug(&diagnostics::Record {
-                            source: Some("init"),
-                            decision: Some("scheduled"),
-                            generation,
-                            policy: Some(strategy.policy()),
-                            commands: Some(commands),
-                            ..Default::default()
-                        });
-                    }
+    let on_ready = || {
+        if let Some(ready) = &reload_ready {
+            let _ = ready.lock().unwrap().recv_timeout(Duration::from_secs(30));
+        }
+        strategy.on_ready();
+        if let Some(shutdown) = &ready_shutdown {
+            shutdown.mark_ready();
+        }
+
+        let initial = watches.lock().unwrap().clone();
+        let init_revision = initial.revision().cloned();
+        match init_action(initial.run_on_init_plan(), run_on_init) {
+            InitAction::Run(plan) => {
+                stdout::info("Running on init commands.");
+                let commands = plan.commands().len();
+                let generation = strategy.run_init(plan, init_revision);
+                // Blocking strategies emit their own in-process run
+                // record; only non-blocking init schedules are recorded
+                // here with their generation.
+                if verbose && generation.is_some() {
+                    diagnostics::debug(&diagnostics::Record {
+                        source: Some("init"),
+                        decision: Some("scheduled"),
+                        generation,
+                        policy: Some(strategy.policy()),
+                        commands: Some(commands),
+                        ..Default::default()
+                    });
                 }
-                InitAction::Wait => stdout::info("Watching..."),
             }
-        },
-        |batch_id: u64, events: &[FileEvent]| {
-            let batch = Batch::normalized(
-                BatchId(batch_id),
-                events.iter().map(|event| event.path.clone()).collect(),
-            );
-            if batch.is_empty() {
-                return;
+            InitAction::Wait => stdout::info("Watching..."),
+        }
+    };
+    let handler = |batch_id: u64, events: &[FileEvent]| {
+        let raw: Vec<String> = events.iter().map(|event| event.path.clone()).collect();
+        // Editor artifacts (`on.editor_noise`) are dropped or mapped to
+        // their real file before normalization, so one save is one path.
+        let paths = watches.lock().unwrap().filter_editor_noise(raw.clone());
+        if verbose {
+            for path in raw.iter().filter(|path| !paths.contains(path)) {
+                diagnostics::debug(&diagnostics::Record {
+                    batch: Some(batch_id),
+                    source: Some("filesystem"),
+                    path: Some(path.clone()),
+                    decision: Some("editor_noise"),
+                    note: crate::editor_noise::classify(path).map(|noise| match noise {
+                        crate::editor_noise::Noise::Drop { editor } => {
+                            format!("{editor} artifact suppressed")
+                        }
+                        crate::editor_noise::Noise::Target { editor, target } => {
+                            format!("{editor} atomic save of {target}")
+                        }
+                    }),
+                    ..Default::default()
+                });
             }
-            strategy.on_batch(&batch);
-            // Content-change gate (TASK-0114): only paths actually modified
-            // since their last routed batch may schedule work; notify's
-            // chatter re-delivery is filtered before matching.
-            let changed_paths = gate.borrow_mut().changed(batch.changed.clone());
-            if changed_paths.is_empty() {
-                return;
+        }
+        let batch = Batch::normalized(BatchId(batch_id), paths);
+        event_trace::record(batch_id, events, &batch.changed);
+        if batch.is_empty() {
+            return;
+        }
+        strategy.on_batch(&batch);
+        // Content-change gate (TASK-0114): only paths actually modified
+        // since their last routed batch may schedule work; notify's
+        // chatter re-delivery is filtered before matching.
+        let changed_paths = if replaying {
+            batch.changed.clone()
+        } else {
+            gate.borrow_mut().changed(batch.changed.clone())
+        };
+        if changed_paths.is_empty() {
+            return;
+        }
+        let batch = Batch::normalized(batch.id, changed_paths);
+        // Git operation hold: a rebase or branch switch rewrites the tree
+        // over many windows. Held batches stay pending (the debounce
+        // observation stays open) until one coalesced batch routes.
+        let admission = git_hold.borrow_mut().admit(batch);
+        match admission {
+            Admission::Route(batch) => {
+                if was_held.replace(false) {
+                    strategy.on_git_release();
+                }
+                let git = observe_git();
+                route(batch, git);
             }
-            let batch = Batch::normalized(batch.id, changed_paths);
-            // Lock once per batch: the whole routing decision (match/ignore,
-            // plan, trigger, frozen revision) reads one committed revision
-            // (contract §4). The revision rides the schedule so the generated
-            // run freezes exactly the routed revision (TASK-0091, AC7).
-            let watches_guard = watches.lock().unwrap();
-            let revision = watches_guard.revision().cloned();
-            match watches_guard.watch_plan_batch(&batch.changed) {
-                Some((plan, trigger)) => {
-                    stdout::clear_screen();
-                    if verbose {
-                        emit_matched_decisions(&watches_guard, &batch, &trigger);
-                    }
-                    let generation = strategy.run_change(plan, &trigger, &batch, revision);
-                    if verbose {
-                        observe_triggers(&watches_guard, &batch, &trigger, generation);
-                    }
+            Admission::Held { operation, started } => {
+                if started {
+                    stdout::info(&format!(
+                        "Holding changes while git {} is in progress.",
+                        operation.label()
+                    ));
                 }
-                None => {
-                    if verbose {
-                        emit_non_matched_decisions(&watches_guard, &batch);
-                    }
+                was_held.set(true);
+                strategy.on_git_hold(operation);
+                if verbose {
+                    diagnostics::debug(&diagnostics::Record {
+                        batch: Some(batch_id),
+                        source: Some("git"),
+                        decision: Some("held"),
+                        note: Some(operation.label().to_owned()),
+                        ..Default::default()
+                    });
                 }
             }
-            drop(watches_guard);
-            strategy.on_batch_complete();
-        },
-        debounce,
-        initial.backend(),
-        verbose,
-        swap_rx,
-        shutdown_flag,
-    )
+        }
+    };
+    let on_idle = || {
+        if !was_held.get() {
+            if let Some(change) = observe_git() {
+                route_git(change);
+            }
+            return;
+        }
+        let released = git_hold.borrow_mut().poll();
+        if let Some(batch) = released {
+            was_held.set(false);
+            strategy.on_git_release();
+            let git = observe_git();
+            route(batch, git);
+        }
+    };
+    // An external event source replaces the filesystem backend entirely;
+    // its batches take the same handler, gate and busy policy path.
+    if let Some(replay) = strategy.replay() {
+        return watcher::replay_events(
+            replay,
+            initial.root(),
+            on_ready,
+            handler,
+            verbose,
+            shutdown_flag,
+        )
+        .map_err(FzzError::GenericError);
+    }
+    match initial.event_source() {
+        Some(source) => watcher::external_events(
+            source.clone(),
+            initial.root(),
+            on_ready,
+            handler,
+            on_idle,
+            debounce,
+            initial.settle(),
+            verbose,
+            swap_rx,
+            shutdown_flag,
+        ),
+        None => watcher::events(
+            list_of_watched_paths,
+            on_ready,
+            handler,
+            on_idle,
+            debounce,
+            initial.settle(),
+            initial.backend(),
+            verbose,
+            swap_rx,
+            shutdown_flag,
+        ),
+    }
     .map_err(FzzError::GenericError)
 }
 
@@ -452,6 +639,24 @@ impl RunStrategy for BlockingStrategy {
         None
     }
 
+    fn run_git(
+        &self,
+        plan: RunPlan,
+        trigger: &str,
+        _revision: Option<ConfigRevision>,
+    ) -> Option<u64> {
+        match self.workflow.run(plan, RunMetadata::new(0, trigger), None) {
+            Ok(completed) => stdout::present_results(
+                completed.results,
+                completed.elapsed,
+                Some(&completed.outcome),
+                &completed.tasks,
+            ),
+            Err(error) => stdout::error(&error),
+        }
+        None
+    }
+
     fn run_change(
         &self,
         plan: RunPlan,
@@ -474,6 +679,103 @@ impl RunStrategy for BlockingStrategy {
     }
 }
 
+/// Dry-run executor for `fzz replay`: numbers each generation the loop
+/// would schedule and prints its trigger and job selection instead of
+/// running anything. Each generation keeps the replay clock busy for as long
+/// as its recorded counterpart ran, so a newer change either supersedes it
+/// (`restart`) or waits for it (`wait`) exactly as it did when recorded.
+pub struct DryRunStrategy {
+    replay: event_trace::Replay,
+    restart: bool,
+    generations: AtomicSequence,
+    /// Generations scheduled without a batch so far (init, git).
+    unbatched: std::sync::atomic::AtomicUsize,
+    /// The generation the replay clock still considers running, and the
+    /// offset at which it finishes.
+    busy: Mutex<Option<(u64, Duration)>>,
+}
+
+impl DryRunStrategy {
+    pub fn new(replay: event_trace::Replay, restart: bool) -> Self {
+        DryRunStrategy {
+            replay,
+            restart,
+            generations: AtomicSequence::new(),
+            unbatched: std::sync::atomic::AtomicUsize::new(0),
+            busy: Mutex::new(None),
+        }
+    }
+
+    fn report(&self, plan: &RunPlan, trigger: &str, batch: Option<u64>) -> Option<u64> {
+        let generation = self.generations.next();
+        let runs_for = match batch {
+            Some(batch) => self.replay.trace.batch_run(batch),
+            None => self.replay.trace.unbatched_run(
+                self.unbatched
+                    .fetch_add(1, std::sync::atomic::Ordering::SeqCst),
+            ),
+        };
+        let now = self.replay.clock.offset();
+        let mut starts = now;
+        let mut note = String::new();
+        let mut busy = self.busy.lock().unwrap();
+        if let Some((active, finishes)) = *busy {
+            if finishes > now && self.restart {
+                note = format!(" (supersedes generation {})", active);
+            } else if finishes > now {
+                starts = finishes;
+                note = format!(
+                    " (waits {}ms for generation {})",
+                    (finishes - now).as_millis(),
+                    active
+                );
+            }
+        }
+        *busy = Some((generation, starts + runs_for.unwrap_or_default()));
+        stdout::info(&format!(
+            "generation {} ({}): {}{}",
+            generation,
+            trigger,
+            plan.task_names().join(", "),
+            note
+        ));
+        Some(generation)
+    }
+}
+
+impl RunStrategy for DryRunStrategy {
+    fn policy(&self) -> &'static str {
+        "dry-run"
+    }
+
+    fn replay(&self) -> Option<&event_trace::Replay> {
+        Some(&self.replay)
+    }
+
+    fn run_init(&self, plan: RunPlan, _revision: Option<ConfigRevision>) -> Option<u64> {
+        self.report(&plan, "init", None)
+    }
+
+    fn run_git(
+        &self,
+        plan: RunPlan,
+        trigger: &str,
+        _revision: Option<ConfigRevision>,
+    ) -> Option<u64> {
+        self.report(&plan, trigger, None)
+    }
+
+    fn run_change(
+        &self,
+        plan: RunPlan,
+        filepath: &str,
+        batch: &Batch,
+        _revision: Option<ConfigRevision>,
+    ) -> Option<u64> {
+        self.report(&plan, filepath, Some(batch.id.0))
+    }
+}
+
 /// Cancellable executor: schedules runs on the worker, cancelling any active
 /// run before replacement work, and publishes the control surface after
 /// readiness.
@@ -752,7 +1054,7 @@ impl NonBlockStrategy {
     }
 
     /// Runs a control-requested target through the worker run contract:
-    /// cancel the active run, then schedule the target's rules with its
+    /// schedule the target's rules, superseding the active run, with its
     /// structural target identity and execution signature (TASK-0054).
     /// `sequential` (TASK-0073) requests effective concurrency one for this
     /// exact generation only; later native generations keep their configured
@@ -779,9 +1081,6 @@ impl NonBlockStrategy {
             }
         };
         let commands = plan.commands().len();
-        self.worker
-            .cancel_running_tasks()
-            .map_err(ControlRunError::Internal)?;
         let run_id = self
             .worker
             .schedule_target(plan, target, sequential, revision.clone())
@@ -790,7 +1089,7 @@ impl NonBlockStrategy {
             source: Some("control"),
             decision: Some("scheduled"),
             generation: Some(run_id),
-            policy: Some("restart"),
+            policy: Some(self.policy()),
             commands: Some(commands),
             task: Some(target.to_owned()),
             ..Default::default()
@@ -837,7 +1136,6 @@ impl NonBlockStrategy {
             }
         };
         let commands = plan.commands().len();
-        self.worker.cancel_running_tasks()?;
         let run_id = self.worker.schedule_plan_correlated(
             plan,
             path,
@@ -850,7 +1148,7 @@ impl NonBlockStrategy {
             source: Some("control"),
             decision: Some("scheduled"),
             generation: Some(run_id),
-            policy: Some("restart"),
+            policy: Some(self.policy()),
             commands: Some(commands),
             path: Some(path.to_owned()),
             ..Default::default()
@@ -861,7 +1159,11 @@ impl NonBlockStrategy {
 
 impl RunStrategy for NonBlockStrategy {
     fn policy(&self) -> &'static str {
-        "restart"
+        if self.worker.restarts_affected() {
+            "restart-affected"
+        } else {
+            "restart"
+        }
     }
 
     fn on_ready(&self) {
@@ -892,6 +1194,41 @@ impl RunStrategy for NonBlockStrategy {
         }
     }
 
+    fn run_git(
+        &self,
+        plan: RunPlan,
+        trigger: &str,
+        revision: Option<ConfigRevision>,
+    ) -> Option<u64> {
+        match self
+            .worker
+            .schedule_plan_with_trigger(plan, trigger, None, revision)
+        {
+            Ok(run_id) => Some(run_id),
+            Err(err) => {
+                stdout::error(&format!("failed to initiate next run: {:?}", err));
+                None
+            }
+        }
+    }
+
+    fn on_git_hold(&self, operation: GitOperation) {
+        self.control_state
+            .lock()
+            .unwrap()
+            .hold_for_git(operation.label());
+        if let Some(broker) = &self.broker {
+            broker.publish();
+        }
+    }
+
+    fn on_git_release(&self) {
+        self.control_state.lock().unwrap().release_git_hold();
+        if let Some(broker) = &self.broker {
+            broker.publish();
+        }
+    }
+
     fn run_change(
         &self,
         plan: RunPlan,
@@ -899,13 +1236,8 @@ impl RunStrategy for NonBlockStrategy {
         batch: &Batch,
         revision: Option<ConfigRevision>,
     ) -> Option<u64> {
-        if let Err(err) = self.worker.cancel_running_tasks() {
-            stdout::error(&format!(
-                "failed to cancel current running tasks: {:?}",
-                err
-            ));
-        }
-
+        // The worker replaces the active run when it takes this one, so the
+        // busy policy and kept services are applied in one step there.
         let commands = plan.commands().len();
         match self.worker.schedule_plan_correlated(
             plan,
@@ -921,7 +1253,7 @@ impl RunStrategy for NonBlockStrategy {
                     source: Some("filesystem"),
                     decision: Some("scheduled"),
                     generation: Some(run_id),
-                    policy: Some("restart"),
+                    policy: Some(self.policy()),
                     commands: Some(commands),
                     ..Default::default()
                 });
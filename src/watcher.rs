@@ -21,6 +21,20 @@ pub struct FileEvent {
     pub continuous: bool,
 }
 
+/// Write-settle policy (`on.settle` / `on.settle_max`): before a batch
+/// closes, every changed regular file must keep the same size and mtime
+/// across two checks `interval` apart, waiting at most `max` in total.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Settle {
+    pub interval: Duration,
+    pub max: Duration,
+}
+
+impl Settle {
+    /// Default upper bound on one batch's settle wait.
+    pub const DEFAULT_MAX: Duration = Duration::from_secs(30);
+}
+
 /// One live root-set swap requested by a config reload (TASK-0090): the
 /// complete new root list after commit. The backend diffs it against the
 /// currently registered roots and applies `unwatch`/`watch` live, then
@@ -82,13 +96,17 @@ impl RootSwapPublisher {
 
 /// Runs the configured backend with an optional live root-swap channel
 /// (TASK-0090). `swap_rx` is consumed by the backend loop: each swap diff is
-/// applied to the live watcher without stopping it.
+/// applied to the live watcher without stopping it. `on_idle` runs on every
+/// wake-up tick without events, so callers can release deferred work (e.g.
+/// batches held during a git operation) without waiting for the next change.
 #[allow(clippy::too_many_arguments)]
 pub fn events(
     watch_path_list: Vec<String>,
     on_ready: impl FnOnce(),
     handler: impl Fn(u64, &[FileEvent]),
+    on_idle: impl Fn(),
     debounce: Duration,
+    settle: Option<Settle>,
     backend: WatchBackend,
     verbose: bool,
     swap_rx: Option<RootSwapReceiver>,
@@ -99,7 +117,9 @@ pub fn events(
             watch_path_list,
             on_ready,
             handler,
+            on_idle,
             debounce,
+            settle,
             verbose,
             swap_rx,
             shutdown,
@@ -108,7 +128,10 @@ pub fn events(
             watch_path_list,
             on_ready,
             handler,
+            on_idle,
             interval,
+            settle,
+            verbose,
             swap_rx,
             shutdown,
         ),
@@ -121,7 +144,9 @@ pub fn events(
                     watch_path_list,
                     on_ready,
                     handler,
+                    on_idle,
                     debounce,
+                    settle,
                     verbose,
                     swap_rx,
                     shutdown,
@@ -135,7 +160,10 @@ pub fn events(
                         watch_path_list,
                         on_ready,
                         handler,
+                        on_idle,
                         Duration::from_millis(500),
+                        settle,
+                        verbose,
                         swap_rx,
                         shutdown,
                     )
@@ -163,11 +191,14 @@ fn native_available(watch_path_list: &[String]) -> Result<(), String> {
 /// Runs the native notify backend: one normalized batch per debounce window.
 /// With `swap_rx`, each live root swap is diffed and applied (unwatch/watch)
 /// without stopping the backend (TASK-0090).
+#[allow(clippy::too_many_arguments)]
 fn run_native(
     watch_path_list: Vec<String>,
     on_ready: impl FnOnce(),
     handler: impl Fn(u64, &[FileEvent]),
+    on_idle: impl Fn(),
     debounce: Duration,
+    settle: Option<Settle>,
     verbose: bool,
     swap_rx: Option<RootSwapReceiver>,
     shutdown: Option<std::sync::Arc<std::sync::atomic::AtomicBool>>,
@@ -227,6 +258,9 @@ fn run_native(
 
     let mut current_roots = watch_path_list.clone();
     let mut swap_rx = swap_rx;
+    // Signatures of the last settled batch: the debouncer reports writes
+    // made while settling in a later window, which must not run again.
+    let mut settled = SettledWrites::default();
 
     loop {
         if shutdown
@@ -250,6 +284,7 @@ fn run_native(
             Ok(debounced_evts) => {
                 let (events, malformed) = normalize_batch(debounced_evts);
                 let events = reconcile_new_directories(events, &current_roots);
+                let mut events = settled.drop_seen(events);
                 if events.is_empty() {
                     // Malformed or empty windows never schedule; still surface
                     // the observation when diagnostics are enabled.
@@ -264,6 +299,16 @@ fn run_native(
                     continue;
                 }
                 let batch_id = batch_sequence.next();
+                if let Some(settle) = settle {
+                    settle_batch(&events, settle, batch_id, verbose);
+                    // Windows that closed while settling belong to this batch.
+                    while let Ok(queued) = rx.try_recv() {
+                        let (queued, _) = normalize_batch(queued);
+                        let queued = reconcile_new_directories(queued, &current_roots);
+                        events = merge_events(events, settled.drop_seen(queued));
+                    }
+                    settled = SettledWrites::record(&events);
+                }
                 if verbose {
                     // One deterministic batch summary (TASK-0031): batch
                     // identity, debounce window, and normalized size, so the
@@ -300,6 +345,7 @@ fn run_native(
 
             Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
                 // Wake-up tick for pending root swaps; not an error.
+                on_idle();
                 continue;
             }
             Err(err) => {
@@ -321,11 +367,15 @@ fn run_native(
 /// roots on a fixed interval and feeds the same normalized batch + handler
 /// path as the native backend. Removals and renames appear as path changes
 /// that the shared matching handles identically.
+#[allow(clippy::too_many_arguments)]
 fn run_poll(
     watch_path_list: Vec<String>,
     on_ready: impl FnOnce(),
     handler: impl Fn(u64, &[FileEvent]),
+    on_idle: impl Fn(),
     interval: Duration,
+    settle: Option<Settle>,
+    verbose: bool,
     swap_rx: Option<RootSwapReceiver>,
     shutdown: Option<std::sync::Arc<std::sync::atomic::AtomicBool>>,
 ) -> Result<(), String> {
@@ -357,12 +407,18 @@ fn run_poll(
                 }
             }
         }
-        let events = scanner.scan();
+        let mut events = scanner.scan();
         if !events.is_empty() {
             let batch_id = batch_sequence.next();
-            if !events.is_empty() {
-                handler(batch_id, &events);
+            if let Some(settle) = settle {
+                settle_batch(&events, settle, batch_id, verbose);
+                // Writes observed while settling, to any file, belong to this
+                // batch; the rescan also moves the baseline past them.
+                events = merge_events(events, scanner.scan());
             }
+            handler(batch_id, &events);
+        } else {
+            on_idle();
         }
         std::thread::sleep(interval.max(Duration::from_millis(20)));
     }
@@ -426,6 +482,114 @@ fn normalize_batch(
     (events, false)
 }
 
+/// Size and mtime of a regular file; None for directories and removed paths.
+fn write_signature(path: &str) -> Option<(u64, Option<std::time::SystemTime>)> {
+    std::fs::metadata(path)
+        .ok()
+        .filter(|meta| meta.is_file())
+        .map(|meta| (meta.len(), meta.modified().ok()))
+}
+
+/// Size and mtime of each changed regular file; directories and removed
+/// paths have nothing to settle and are skipped.
+fn write_signatures(events: &[FileEvent]) -> Vec<(u64, Option<std::time::SystemTime>)> {
+    events
+        .iter()
+        .filter_map(|event| write_signature(&event.path))
+        .collect()
+}
+
+/// Unions two normalized batches, keeping the path order and dedup of
+/// `normalize_batch`.
+fn merge_events(mut events: Vec<FileEvent>, more: Vec<FileEvent>) -> Vec<FileEvent> {
+    events.extend(more);
+    events.sort_by(|a, b| a.path.cmp(&b.path));
+    events.dedup_by(|a, b| {
+        let duplicate = a.path == b.path;
+        if duplicate {
+            b.continuous |= a.continuous;
+        }
+        duplicate
+    });
+    events
+}
+
+/// File signatures a settled batch already covered, so a later debounce
+/// window reporting the same writes is not routed twice.
+#[derive(Default)]
+struct SettledWrites(std::collections::HashMap<String, (u64, Option<std::time::SystemTime>)>);
+
+impl SettledWrites {
+    fn record(events: &[FileEvent]) -> Self {
+        SettledWrites(
+            events
+                .iter()
+                .filter_map(|event| {
+                    write_signature(&event.path).map(|signature| (event.path.clone(), signature))
+                })
+                .collect(),
+        )
+    }
+
+    /// Drops events for files unchanged since they settled.
+    fn drop_seen(&mut self, events: Vec<FileEvent>) -> Vec<FileEvent> {
+        events
+            .into_iter()
+            .filter(|event| match self.0.get(&event.path) {
+                Some(signature) if write_signature(&event.path).as_ref() == Some(signature) => {
+                    false
+                }
+                Some(_) => {
+                    self.0.remove(&event.path);
+                    true
+                }
+                None => true,
+            })
+            .collect()
+    }
+}
+
+/// Blocks until the batch's files stop changing (`on.settle`): consecutive
+/// checks `interval` apart must see identical size and mtime for every file,
+/// bounded by `max`. Returns the wait and whether the files settled.
+fn wait_for_settle(events: &[FileEvent], settle: Settle) -> (Duration, bool) {
+    let started = std::time::Instant::now();
+    let mut previous = write_signatures(events);
+    loop {
+        if started.elapsed() >= settle.max {
+            return (started.elapsed(), false);
+        }
+        std::thread::sleep(
+            settle
+                .interval
+                .min(settle.max.saturating_sub(started.elapsed())),
+        );
+        let current = write_signatures(events);
+        if current == previous {
+            return (started.elapsed(), true);
+        }
+        previous = current;
+    }
+}
+
+/// Settles one closed batch and records the delay when diagnostics are on.
+fn settle_batch(events: &[FileEvent], settle: Settle, batch_id: u64, verbose: bool) {
+    let (waited, settled) = wait_for_settle(events, settle);
+    if verbose {
+        diagnostics::debug(&diagnostics::Record {
+            batch: Some(batch_id),
+            source: Some("filesystem"),
+            decision: Some(if settled { "settled" } else { "settle_timeout" }),
+            note: Some(format!(
+                "waited {}ms for {} path(s) to stop changing",
+                waited.as_millis(),
+                events.len()
+            )),
+            ..Default::default()
+        });
+    }
+}
+
 /// Closes the native-backend registration race (WATCH-DISCOVERY-CONTRACT
 /// §4): inotify adds the watch for a newly created directory only when its
 /// create event is processed, so files written inside in the same instant
@@ -502,6 +666,278 @@ fn reconcile_new_directories(mut events: Vec<FileEvent>, watch_roots: &[String])
     events
 }
 
+/// External change-event source (`on.event_source`) that replaces the
+/// filesystem backend: another watcher writes one path per line, or one
+/// NDJSON `{"path": ..., "kind": "any"|"continuous"}` record per line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EventSource {
+    Stdin,
+    Fifo(PathBuf),
+}
+
+impl EventSource {
+    /// Parses `stdin` or `fifo:PATH`.
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        match raw.trim() {
+            "stdin" => Ok(EventSource::Stdin),
+            other => match other.strip_prefix("fifo:") {
+                Some(path) if !path.trim().is_empty() => {
+                    Ok(EventSource::Fifo(PathBuf::from(path.trim())))
+                }
+                _ => Err(format!(
+                    "invalid 'on.event_source' '{}': expected stdin or fifo:PATH",
+                    other
+                )),
+            },
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            EventSource::Stdin => "stdin".to_owned(),
+            EventSource::Fifo(path) => format!("fifo:{}", path.display()),
+        }
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct ExternalRecord {
+    path: String,
+    #[serde(default)]
+    kind: Option<String>,
+}
+
+/// Parses one external event line into a root-anchored `FileEvent`. Blank
+/// lines are skipped; malformed NDJSON is reported and dropped.
+fn parse_external_line(line: &str, root: &Path) -> Result<Option<FileEvent>, String> {
+    let line = line.trim();
+    if line.is_empty() {
+        return Ok(None);
+    }
+    let (path, continuous) = if line.starts_with('{') {
+        let record: ExternalRecord = serde_json::from_str(line)
+            .map_err(|err| format!("invalid event record '{}': {}", line, err))?;
+        let continuous = match record.kind.as_deref() {
+            None | Some("any") => false,
+            Some("continuous") => true,
+            Some(other) => {
+                return Err(format!(
+                    "invalid event kind '{}': expected any or continuous",
+                    other
+                ))
+            }
+        };
+        (record.path, continuous)
+    } else {
+        (line.to_owned(), false)
+    };
+    let path = root.join(path);
+    Ok(Some(FileEvent {
+        path: path.to_string_lossy().into_owned(),
+        continuous,
+    }))
+}
+
+enum ExternalMessage {
+    Line(String),
+    Closed,
+}
+
+/// Reads the source on a dedicated thread. Stdin closes the stream at EOF;
+/// a FIFO is reopened after each writer disconnects, so the producer may
+/// restart without restarting the watcher.
+fn spawn_external_reader(source: EventSource) -> std::sync::mpsc::Receiver<ExternalMessage> {
+    use std::io::BufRead;
+    let (tx, rx) = channel();
+    std::thread::spawn(move || match source {
+        EventSource::Stdin => {
+            for line in std::io::stdin().lock().lines() {
+                let Ok(line) = line else { break };
+                if tx.send(ExternalMessage::Line(line)).is_err() {
+                    return;
+                }
+            }
+            let _ = tx.send(ExternalMessage::Closed);
+        }
+        EventSource::Fifo(path) => loop {
+            let Ok(file) = std::fs::File::open(&path) else {
+                let _ = tx.send(ExternalMessage::Closed);
+                return;
+            };
+            for line in std::io::BufReader::new(file).lines() {
+                let Ok(line) = line else { break };
+                if tx.send(ExternalMessage::Line(line)).is_err() {
+                    return;
+                }
+            }
+        },
+    });
+    rx
+}
+
+/// Runs an external event source in place of the filesystem backend. Lines
+/// are collected until the source stays quiet for `debounce`, then one
+/// normalized batch goes through the same settle, batch identity and handler
+/// path as native events. Returns when stdin closes.
+#[allow(clippy::too_many_arguments)]
+pub fn external_events(
+    source: EventSource,
+    root: &Path,
+    on_ready: impl FnOnce(),
+    handler: impl Fn(u64, &[FileEvent]),
+    on_idle: impl Fn(),
+    debounce: Duration,
+    settle: Option<Settle>,
+    verbose: bool,
+    swap_rx: Option<RootSwapReceiver>,
+    shutdown: Option<std::sync::Arc<std::sync::atomic::AtomicBool>>,
+) -> Result<(), String> {
+    if let EventSource::Fifo(path) = &source {
+        let is_fifo = std::fs::metadata(path)
+            .map(|meta| std::os::unix::fs::FileTypeExt::is_fifo(&meta.file_type()))
+            .unwrap_or(false);
+        if !is_fifo {
+            return Err(format!("event source {} is not a FIFO", path.display()));
+        }
+    }
+    if verbose {
+        diagnostics::debug(&diagnostics::Record {
+            source: Some("config"),
+            decision: Some("event_source"),
+            note: Some(source.label()),
+            ..Default::default()
+        });
+    }
+    let rx = spawn_external_reader(source);
+    let batch_sequence = AtomicSequence::new();
+    if shutdown
+        .as_ref()
+        .is_some_and(|flag| flag.load(std::sync::atomic::Ordering::SeqCst))
+    {
+        return Ok(());
+    }
+    on_ready();
+
+    let mut pending: Vec<FileEvent> = vec![];
+    let mut last_event = std::time::Instant::now();
+    let mut closed = false;
+    loop {
+        if shutdown
+            .as_ref()
+            .is_some_and(|flag| flag.load(std::sync::atomic::Ordering::SeqCst))
+        {
+            return Ok(());
+        }
+        // No roots are registered: reload root swaps are acknowledged
+        // as-is so the reload transaction never waits on this source.
+        if let Some(swaps) = swap_rx.as_ref() {
+            while let Ok(swap) = swaps.try_recv() {
+                if let Some(ack) = swap.ack {
+                    let _ = ack.send(Ok(()));
+                }
+            }
+        }
+        match rx.recv_timeout(debounce.min(Duration::from_millis(200))) {
+            Ok(ExternalMessage::Line(line)) => match parse_external_line(&line, root) {
+                Ok(Some(event)) => {
+                    pending.push(event);
+                    last_event = std::time::Instant::now();
+                    continue;
+                }
+                Ok(None) => continue,
+                Err(err) => {
+                    stdout::warn(&err);
+                    continue;
+                }
+            },
+            Ok(ExternalMessage::Closed) | Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => {
+                closed = true;
+            }
+            Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {}
+        }
+        if !pending.is_empty() && (closed || last_event.elapsed() >= debounce) {
+            let mut events = std::mem::take(&mut pending);
+            events.sort_by(|a, b| a.path.cmp(&b.path));
+            events.dedup_by(|a, b| a.path == b.path);
+            let batch_id = batch_sequence.next();
+            if let Some(settle) = settle {
+                settle_batch(&events, settle, batch_id, verbose);
+            }
+            if verbose {
+                diagnostics::debug(&diagnostics::Record {
+                    batch: Some(batch_id),
+                    source: Some("external"),
+                    decision: Some("batch"),
+                    note: Some(format!(
+                        "{} normalized path(s) in a {:?} debounce window",
+                        events.len(),
+                        debounce
+                    )),
+                    ..Default::default()
+                });
+            }
+            handler(batch_id, &events);
+        } else if pending.is_empty() {
+            on_idle();
+        }
+        if closed {
+            stdout::info("Event source closed.");
+            return Ok(());
+        }
+    }
+}
+
+/// Feeds a recorded event trace (`fzz replay`) through the handler in
+/// place of any backend, on the replay clock: each recorded batch is
+/// delivered as it was recorded, one window each, after advancing the clock
+/// to its offset. The backend already debounced them, so batches are never
+/// merged again; two that ran as two generations replay as two. Nothing
+/// sleeps and nothing is settled, so the same trace always produces the
+/// same routing. Returns after the last batch.
+pub fn replay_events(
+    replay: &crate::event_trace::Replay,
+    root: &Path,
+    on_ready: impl FnOnce(),
+    handler: impl Fn(u64, &[FileEvent]),
+    verbose: bool,
+    shutdown: Option<std::sync::Arc<std::sync::atomic::AtomicBool>>,
+) -> Result<(), String> {
+    let trace = &replay.trace;
+    on_ready();
+    let mut windows = 0;
+    for batch in &trace.batches {
+        if shutdown
+            .as_ref()
+            .is_some_and(|flag| flag.load(std::sync::atomic::Ordering::SeqCst))
+        {
+            return Ok(());
+        }
+        let mut events = batch.file_events(&trace.root, root);
+        events.sort_by(|a, b| a.path.cmp(&b.path));
+        events.dedup_by(|a, b| a.path == b.path);
+        replay.clock.advance_to(Duration::from_millis(batch.t_ms));
+        windows += 1;
+        stdout::info(&format!(
+            "+{}ms batch {}: {} event(s)",
+            batch.t_ms,
+            batch.batch,
+            events.len()
+        ));
+        if verbose {
+            diagnostics::debug(&diagnostics::Record {
+                batch: Some(batch.batch),
+                source: Some("replay"),
+                decision: Some("batch"),
+                note: Some(format!("recorded at +{}ms", batch.t_ms)),
+                ..Default::default()
+            });
+        }
+        handler(batch.batch, &events);
+    }
+    stdout::info(&format!("Replay finished: {} batch(es).", windows));
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -542,6 +978,133 @@ mod tests {
         );
     }
 
+    #[test]
+    fn settle_waits_for_a_growing_file_and_returns_once_stable() {
+        let dir = std::env::temp_dir().join(format!("fzz-settle-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("artifact.bin");
+        std::fs::write(&file, b"start").unwrap();
+        let events = vec![FileEvent {
+            path: file.to_str().unwrap().to_owned(),
+            continuous: false,
+        }];
+        let settle = Settle {
+            interval: Duration::from_millis(50),
+            max: Duration::from_secs(5),
+        };
+
+        let writer = {
+            let file = file.clone();
+            std::thread::spawn(move || {
+                use std::io::Write;
+                let mut handle = std::fs::OpenOptions::new().append(true).open(file).unwrap();
+                for _ in 0..6 {
+                    handle.write_all(&[0u8; 1024]).unwrap();
+                    std::thread::sleep(Duration::from_millis(30));
+                }
+            })
+        };
+        let (waited, settled) = wait_for_settle(&events, settle);
+        writer.join().unwrap();
+        assert!(settled);
+        assert!(waited >= Duration::from_millis(150), "waited {waited:?}");
+        assert_eq!(std::fs::metadata(&file).unwrap().len(), 5 + 6 * 1024);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn settle_gives_up_at_max_while_the_file_keeps_changing() {
+        let dir = std::env::temp_dir().join(format!("fzz-settle-max-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("stream.log");
+        std::fs::write(&file, b"").unwrap();
+        let events = vec![FileEvent {
+            path: file.to_str().unwrap().to_owned(),
+            continuous: true,
+        }];
+        let writer = {
+            let file = file.clone();
+            std::thread::spawn(move || {
+                use std::io::Write;
+                let mut handle = std::fs::OpenOptions::new().append(true).open(file).unwrap();
+                for _ in 0..40 {
+                    handle.write_all(b"line\n").unwrap();
+                    std::thread::sleep(Duration::from_millis(10));
+                }
+            })
+        };
+        let (waited, settled) = wait_for_settle(
+            &events,
+            Settle {
+                interval: Duration::from_millis(40),
+                max: Duration::from_millis(150),
+            },
+        );
+        writer.join().unwrap();
+        assert!(!settled);
+        assert!(waited < Duration::from_millis(400), "waited {waited:?}");
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn settled_writes_are_not_reported_again_by_a_later_window() {
+        let dir = std::env::temp_dir().join(format!("fzz-settled-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let event = |name: &str| FileEvent {
+            path: dir.join(name).to_str().unwrap().to_owned(),
+            continuous: false,
+        };
+        std::fs::write(dir.join("a.bin"), b"done").unwrap();
+        std::fs::write(dir.join("b.bin"), b"late").unwrap();
+        let events = merge_events(vec![event("a.bin")], vec![event("b.bin"), event("a.bin")]);
+        assert_eq!(events, vec![event("a.bin"), event("b.bin")]);
+
+        let mut settled = SettledWrites::record(&events);
+        assert!(settled
+            .drop_seen(vec![event("a.bin"), event("b.bin")])
+            .is_empty());
+        std::fs::write(dir.join("b.bin"), b"changed again").unwrap();
+        assert_eq!(
+            settled.drop_seen(vec![event("a.bin"), event("b.bin"), event("c.bin")]),
+            vec![event("b.bin"), event("c.bin")]
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn external_lines_accept_plain_paths_and_ndjson_records() {
+        let root = Path::new("/workspace");
+        assert_eq!(
+            parse_external_line("src/main.rs", root).unwrap(),
+            Some(FileEvent {
+                path: "/workspace/src/main.rs".to_owned(),
+                continuous: false,
+            })
+        );
+        assert_eq!(
+            parse_external_line(r#"{"path": "/abs/out.log", "kind": "continuous"}"#, root).unwrap(),
+            Some(FileEvent {
+                path: "/abs/out.log".to_owned(),
+                continuous: true,
+            })
+        );
+        assert_eq!(parse_external_line("   ", root).unwrap(), None);
+        assert!(parse_external_line(r#"{"path": "a", "kind": "moved"}"#, root).is_err());
+        assert!(parse_external_line(r#"{"kind": "any"}"#, root).is_err());
+    }
+
+    #[test]
+    fn event_source_parses_stdin_and_fifo() {
+        assert_eq!(EventSource::parse("stdin"), Ok(EventSource::Stdin));
+        assert_eq!(
+            EventSource::parse("fifo:.tmp/events"),
+            Ok(EventSource::Fifo(PathBuf::from(".tmp/events")))
+        );
+        assert!(EventSource::parse("fifo:").is_err());
+        assert!(EventSource::parse("socket:x").is_err());
+    }
+
     #[test]
     fn malformed_window_yields_empty_batch() {
         let (normalized, malformed) = normalize_batch(Err(
@@ -15,6 +15,10 @@ pub enum WatcherExecutionState {
     Passed,
     Failed,
     Cancelled,
+    /// Filesystem batches are held while a git operation is in progress;
+    /// one coalesced generation is scheduled once it finished.
+    #[serde(rename = "held_for_git")]
+    HeldForGit,
 }
 
 /// One Funzzy process identity: token changes on restart so clients can detect
@@ -74,6 +78,14 @@ pub struct WatcherState {
     /// Immutable configuration revision this generation was frozen under.
     revision: Option<u64>,
     revision_hash: Option<String>,
+    /// In-progress git operation batches are held for (`rebase`, `merge`,
+    /// `index_lock`); omitted when nothing is held.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    git_operation: Option<&'static str>,
+    /// Execution state underneath an active git hold: generation events
+    /// keep updating it so releasing the hold reports the truthful state.
+    #[serde(skip)]
+    held_state: Option<WatcherExecutionState>,
 }
 
 impl Default for WatcherState {
@@ -94,6 +106,8 @@ impl Default for WatcherState {
             concurrency_source: None,
             revision: None,
             revision_hash: None,
+            git_operation: None,
+            held_state: None,
         }
     }
 }
@@ -159,6 +173,35 @@ impl WatcherState {
         self.concurrency_source
     }
 
+    pub fn git_operation(&self) -> Option<&'static str> {
+        self.git_operation
+    }
+
+    /// Reports `held_for_git` until [`Self::release_git_hold`]; the running
+    /// generation's own transitions are tracked underneath.
+    pub fn hold_for_git(&mut self, operation: &'static str) {
+        if self.held_state.is_none() {
+            let previous = std::mem::replace(&mut self.state, WatcherExecutionState::HeldForGit);
+            self.held_state = Some(previous);
+        }
+        self.git_operation = Some(operation);
+    }
+
+    /// Restores the execution state tracked while the hold was active.
+    pub fn release_git_hold(&mut self) {
+        if let Some(previous) = self.held_state.take() {
+            self.state = previous;
+        }
+        self.git_operation = None;
+    }
+
+    fn set_state(&mut self, state: WatcherExecutionState) {
+        match self.held_state.as_mut() {
+            Some(held) => *held = state,
+            None => self.state = state,
+        }
+    }
+
     pub fn apply(&mut self, event: Event) {
         match event {
             Event::Started {
@@ -175,7 +218,7 @@ impl WatcherState {
                 ..
             } => {
                 self.generation = run_id;
-                self.state = WatcherExecutionState::Running;
+                self.set_state(WatcherExecutionState::Running);
                 self.trigger = Some(trigger);
                 self.batch = batch;
                 self.changed = changed;
@@ -196,28 +239,53 @@ impl WatcherState {
                 failures,
                 ..
             } => {
-                self.state = if failures.is_empty() {
+                self.set_state(if failures.is_empty() {
                     WatcherExecutionState::Passed
                 } else {
                     WatcherExecutionState::Failed
-                };
+                });
                 self.duration_ms = Some(elapsed.as_millis() as u64);
                 self.failures = failures;
                 self.superseded_by = superseded_by;
             }
             Event::Cancelled { superseded_by, .. } => {
-                self.state = WatcherExecutionState::Cancelled;
+                self.set_state(WatcherExecutionState::Cancelled);
                 self.duration_ms = None;
                 self.superseded_by = superseded_by;
             }
             Event::Tick { .. } => {}
             Event::TaskTerminal { run_id, task } => {
                 if run_id == self.generation {
+                    self.tasks.retain(|known| known.id != task.id);
+                    self.tasks.push(task);
+                    self.tasks.sort_by_key(|task| task.position);
+                }
+            }
+            // Lock waits show as `waiting` rows until the task acquires its
+            // locks or reaches a terminal state.
+            Event::TaskWaiting { run_id, task } => {
+                if run_id == self.generation {
+                    self.tasks.retain(|known| known.id != task.id);
+                    if task.waiting.is_some() {
+                        self.tasks.push(task);
+                        self.tasks.sort_by_key(|task| task.position);
+                    }
+                }
+            }
+            // Services show their readiness until they reach a terminal
+            // state.
+            Event::ServiceState { run_id, task } => {
+                if run_id == self.generation {
+                    self.tasks.retain(|known| known.id != task.id);
                     self.tasks.push(task);
                     self.tasks.sort_by_key(|task| task.position);
                 }
             }
-            Event::RecoveryPhase { .. } => {}
+            // A kept service's row is re-reported by the generation that
+            // takes it over.
+            Event::ServiceAction { .. }
+            | Event::Diagnostics { .. }
+            | Event::RecoveryPhase { .. } => {}
         }
     }
 }
@@ -327,6 +395,13 @@ mod tests {
                     name: name.to_owned(),
                     state: crate::executor::TaskState::Passed,
                     duration_ms: Some(42),
+                    waiting: None,
+                    failure_reason: None,
+                    restarts: None,
+                    health: None,
+                    tests: None,
+                    steps: vec![],
+                    superseded_by: None,
                 },
             });
         }
@@ -354,6 +429,13 @@ mod tests {
                     name: name.to_owned(),
                     state: crate::executor::TaskState::Passed,
                     duration_ms,
+                    waiting: None,
+                    failure_reason: None,
+                    restarts: None,
+                    health: None,
+                    tests: None,
+                    steps: vec![],
+                    superseded_by: None,
                 },
             });
         }
@@ -383,4 +465,31 @@ mod tests {
         assert_eq!(object["predecessor"], serde_json::json!(null));
         assert_eq!(object["supersededBy"], serde_json::json!(null));
     }
+
+    #[test]
+    fn git_hold_reports_held_state_and_restores_tracked_state() {
+        let mut state = WatcherState::default();
+        state.apply(started(1, None, None));
+        state.hold_for_git("rebase");
+
+        assert_eq!(state.state(), &WatcherExecutionState::HeldForGit);
+        let json = serde_json::to_value(&state).unwrap();
+        assert_eq!(json["state"], "held_for_git");
+        assert_eq!(json["gitOperation"], "rebase");
+
+        // The running generation finishes underneath the hold.
+        state.apply(Event::Finished {
+            run_id: 1,
+            superseded_by: None,
+            elapsed: Duration::from_millis(3),
+            failures: vec![],
+        });
+        assert_eq!(state.state(), &WatcherExecutionState::HeldForGit);
+
+        state.release_git_hold();
+        assert_eq!(state.state(), &WatcherExecutionState::Passed);
+        assert_eq!(state.git_operation(), None);
+        let json = serde_json::to_value(&state).unwrap();
+        assert!(json.get("gitOperation").is_none());
+    }
 }
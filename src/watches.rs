@@ -3,7 +3,7 @@ use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use crate::plan::RunPlan;
-use crate::rules::Rules;
+use crate::rules::{GitTrigger, Rules};
 
 /// Why a configured rule was selected or skipped for an explained path.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -19,6 +19,8 @@ pub struct ExplainRule {
     pub environment_keys: Vec<String>,
     /// Whether this job declares an available recovery; approval is separate.
     pub recovery_available: bool,
+    /// The job's own stop signal and grace, if it overrides the defaults.
+    pub stop: crate::rules::StopPolicy,
     /// Where the effective rule came from (TASK-0023): `task` or `group`
     /// when the responsible pattern was inherited from a group `on` section.
     pub origin: String,
@@ -31,6 +33,9 @@ pub struct ExplainResult {
     pub matched: Vec<ExplainRule>,
     /// Rules whose change pattern matched but an ignore pattern won.
     pub ignored: Vec<ExplainRule>,
+    /// Set when the path is an editor artifact (`on.editor_noise`): dropped
+    /// paths match nothing; atomic-save paths are explained as their target.
+    pub editor_noise: Option<crate::editor_noise::Noise>,
     /// The filtered execution topology (stages + named group occurrences)
     /// after path filtering — the actual run plan preview, same planner as
     /// execution (TASK-0034). Empty when nothing matches.
@@ -46,6 +51,16 @@ pub enum PlanStagePreview {
     Parallel { group: String, tasks: Vec<String> },
 }
 
+/// Why a job joined an affected-only run (`fzz run --changed-since`): the
+/// first changed path, in sorted order, that selected it and the change
+/// patterns that matched that path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AffectedJob {
+    pub name: String,
+    pub path: String,
+    pub change_patterns: Vec<String>,
+}
+
 /// Execution facts relevant to an explained plan (TASK-0034): the effective
 /// scheduler concurrency and the filesystem debounce window.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -94,8 +109,14 @@ pub struct Watches {
     debounce: Duration,
     /// Filesystem backend policy (TASK-0037): native, poll, or auto.
     backend: crate::watcher::WatchBackend,
+    /// Write-settle policy (`on.settle`); None closes batches immediately.
+    settle: Option<crate::watcher::Settle>,
+    /// External event source (`on.event_source`) replacing the backend.
+    event_source: Option<crate::watcher::EventSource>,
     /// Whether workspace `.gitignore` rules are respected (TASK-0036).
     respect_gitignore: bool,
+    /// Whether editor swap/backup/temp files are filtered from batches.
+    editor_noise: bool,
     /// Effective user-approved recovery policy for failed jobs.
     recovery_policy: crate::config::RecoveryPolicy,
     /// Generation terminal hooks (TASK-0040): success/failure commands.
@@ -141,7 +162,10 @@ impl Watches {
             concurrency,
             debounce: Duration::from_millis(1000),
             backend: crate::watcher::WatchBackend::Auto,
+            settle: None,
+            event_source: None,
             respect_gitignore: false,
+            editor_noise: true,
             gitignore: None,
             recovery_policy: crate::config::RecoveryPolicy::Prompt,
             hooks: crate::config::GenerationHooks::default(),
@@ -173,6 +197,28 @@ impl Watches {
         self.backend
     }
 
+    /// Sets the write-settle policy (`on.settle`).
+    pub fn with_settle(mut self, settle: Option<crate::watcher::Settle>) -> Self {
+        self.settle = settle;
+        self
+    }
+
+    /// The configured write-settle policy, if any.
+    pub fn settle(&self) -> Option<crate::watcher::Settle> {
+        self.settle
+    }
+
+    /// Replaces the filesystem backend with an external event source.
+    pub fn with_event_source(mut self, source: Option<crate::watcher::EventSource>) -> Self {
+        self.event_source = source;
+        self
+    }
+
+    /// The configured external event source, if any.
+    pub fn event_source(&self) -> Option<&crate::watcher::EventSource> {
+        self.event_source.as_ref()
+    }
+
     /// Enables gitignore respect and builds the root-anchored matcher
     /// (TASK-0036). Explicit config `ignore` rules stay strongest.
     pub fn with_gitignore(mut self, respect: bool) -> Self {
@@ -206,6 +252,20 @@ impl Watches {
         self.respect_gitignore
     }
 
+    /// Enables or disables the editor artifact filter (`on.editor_noise`).
+    pub fn with_editor_noise(mut self, enabled: bool) -> Self {
+        self.editor_noise = enabled;
+        self
+    }
+
+    /// Applies the editor artifact filter to raw batch paths when enabled.
+    pub fn filter_editor_noise(&self, paths: Vec<String>) -> Vec<String> {
+        if !self.editor_noise {
+            return paths;
+        }
+        crate::editor_noise::filter(paths)
+    }
+
     /// Sets the effective user-approved recovery policy.
     pub fn with_recovery_policy(mut self, policy: crate::config::RecoveryPolicy) -> Self {
         self.recovery_policy = policy;
@@ -274,7 +334,10 @@ impl Watches {
             concurrency: self.concurrency,
             debounce: self.debounce,
             backend: self.backend,
+            settle: self.settle,
+            event_source: self.event_source.clone(),
             respect_gitignore: self.respect_gitignore,
+            editor_noise: self.editor_noise,
             gitignore: self.gitignore.clone(),
             recovery_policy: self.recovery_policy,
             hooks: self.hooks.clone(),
@@ -335,6 +398,14 @@ impl Watches {
             .unwrap_or_else(|| path.to_owned())
     }
 
+    /// Whether any job subscribes to repository events (`on.git` or
+    /// `jobs[].git`); only then is the repository polled.
+    pub fn uses_git(&self) -> bool {
+        self.rules
+            .iter()
+            .any(|rule| !rule.git_triggers().is_empty())
+    }
+
     /// Returns all configured targets.
     pub fn targets(&self) -> Vec<Rules> {
         self.rules.clone()
@@ -412,10 +483,21 @@ impl Watches {
 
     /// Selects matching tasks while retaining original stage occurrences.
     pub fn watch_plan(&self, path: &str) -> Option<RunPlan> {
+        let routes = self.path_router(path);
+        let plan = self.topology.clone().filter(|rule| routes(rule));
+        if plan.is_empty() {
+            return None;
+        }
+        Some(plan)
+    }
+
+    /// The per-rule routing decision for one path: not ignored (config
+    /// ignore, then gitignore) and matched by a change pattern.
+    fn path_router<'a>(&'a self, path: &str) -> impl Fn(&Rules) -> bool + 'a {
         let (absolute_path, relative_path) = self.normalize_paths(path);
-        let absolute_path_str = absolute_path.to_str().unwrap_or_default();
-        let plan = self.topology.clone().filter(|rule| {
-            let ignored_by_absolute = rule.ignore_absolute(absolute_path_str);
+        let absolute_path = absolute_path.to_str().unwrap_or_default().to_owned();
+        move |rule: &Rules| {
+            let ignored_by_absolute = rule.ignore_absolute(&absolute_path);
             let ignored_by_relative = relative_path
                 .as_ref()
                 .map(|relative| rule.ignore_relative(relative))
@@ -438,17 +520,52 @@ impl Watches {
                 }
             }
 
-            let watched_by_absolute = rule.watch_absolute(absolute_path_str);
+            let watched_by_absolute = rule.watch_absolute(&absolute_path);
             let watched_by_relative = relative_path
                 .as_ref()
                 .map(|relative| rule.watch_relative(relative))
                 .unwrap_or(false);
             watched_by_absolute || watched_by_relative
-        });
+        }
+    }
+
+    /// Routes one batch plus the repository events observed with it into
+    /// zero or one generation: the batch's first matching path selects jobs
+    /// as in [`Self::watch_plan_batch`], and jobs subscribed to any fired git
+    /// trigger join the same plan. Without a matching path the trigger is
+    /// `git:<event>` for the first fired event.
+    pub fn route_plan(&self, paths: &[String], fired: &[GitTrigger]) -> Option<(RunPlan, String)> {
+        let subscribed = |rule: &Rules| {
+            rule.git_triggers()
+                .iter()
+                .any(|trigger| fired.contains(trigger))
+        };
+        let trigger_path = {
+            let mut sorted = paths.to_vec();
+            sorted.sort();
+            sorted
+                .into_iter()
+                .find(|path| self.watch_plan(path).is_some())
+        };
+        let (plan, trigger) = match trigger_path {
+            Some(path) => {
+                let routes = self.path_router(&path);
+                let plan = self
+                    .topology
+                    .clone()
+                    .filter(|rule| routes(rule) || subscribed(rule));
+                (plan, path)
+            }
+            None => {
+                let first = fired.first()?;
+                let plan = self.topology.clone().filter(subscribed);
+                (plan, format!("git:{}", first.label()))
+            }
+        };
         if plan.is_empty() {
             return None;
         }
-        Some(plan)
+        Some((plan, trigger))
     }
 
     /// Routes one normalized event batch to zero or one generation (contract
@@ -467,6 +584,43 @@ impl Watches {
         None
     }
 
+    /// Selects every job affected by a synthetic batch (`fzz run
+    /// --changed-since` / `--paths-from`): unlike watch routing, ALL changed
+    /// paths contribute, each through the same change/ignore/gitignore
+    /// matching as `explain`. With a target, only its jobs are eligible.
+    pub fn affected_plan(
+        &self,
+        paths: &[String],
+        target: Option<&str>,
+    ) -> Result<(RunPlan, Vec<AffectedJob>), RunTargetError> {
+        let base = match target {
+            Some(target) => self.run_target_plan(target)?,
+            None => self.topology.clone(),
+        };
+        let eligible = base.task_names();
+        let mut sorted = paths.to_vec();
+        sorted.sort();
+        sorted.dedup();
+
+        let mut affected: Vec<AffectedJob> = vec![];
+        for path in sorted {
+            for rule in self.explain(&path).matched {
+                if !eligible.contains(&rule.name)
+                    || affected.iter().any(|job| job.name == rule.name)
+                {
+                    continue;
+                }
+                affected.push(AffectedJob {
+                    name: rule.name,
+                    path: path.clone(),
+                    change_patterns: rule.change_patterns,
+                });
+            }
+        }
+        let plan = base.filter(|rule| affected.iter().any(|job| job.name == rule.name));
+        Ok((plan, affected))
+    }
+
     /// Returns the commands for first rule found for the given path
     ///
     pub fn watch(&self, path: &str) -> Option<Vec<Rules>> {
@@ -538,6 +692,21 @@ impl Watches {
     /// change and ignore patterns matched per rule. It never starts a watcher
     /// or executes a task.
     pub fn explain(&self, path: &str) -> ExplainResult {
+        let noise = self
+            .editor_noise
+            .then(|| crate::editor_noise::classify(path))
+            .flatten();
+        let target = match &noise {
+            None => path.to_owned(),
+            Some(crate::editor_noise::Noise::Target { target, .. }) => target.clone(),
+            Some(crate::editor_noise::Noise::Drop { .. }) => {
+                return ExplainResult {
+                    editor_noise: noise,
+                    ..Default::default()
+                };
+            }
+        };
+        let path = target.as_str();
         let (absolute_path, relative_path) = self.normalize_paths(path);
         let absolute_path_str = absolute_path.to_str().unwrap_or_default();
 
@@ -585,6 +754,7 @@ impl Watches {
                     cwd,
                     environment_keys,
                     recovery_available: rule.recovery_commands().is_some(),
+                    stop: rule.stop(),
                     origin,
                 });
             } else {
@@ -600,6 +770,7 @@ impl Watches {
                     cwd,
                     environment_keys,
                     recovery_available: rule.recovery_commands().is_some(),
+                    stop: rule.stop(),
                     origin,
                 });
             }
@@ -608,6 +779,7 @@ impl Watches {
         ExplainResult {
             matched,
             ignored,
+            editor_noise: noise,
             // TASK-0034: the filtered execution topology uses the exact same
             // planner as execution (watch_plan), so the preview can never
             // drift from what would actually run.
@@ -1712,6 +1884,112 @@ mod tests {
         assert_eq!(plan.task_names(), vec!["build".to_owned()]);
     }
 
+    #[test]
+    fn git_triggers_join_file_routes_and_route_alone() {
+        let rules = vec![
+            Rules::new(
+                "build".to_owned(),
+                vec!["echo build".to_owned()],
+                vec!["src/**".to_owned()],
+                vec![],
+                false,
+            ),
+            Rules::new(
+                "deps".to_owned(),
+                vec!["npm ci".to_owned()],
+                vec![],
+                vec![],
+                false,
+            )
+            .with_git_triggers(vec![GitTrigger::HeadChange]),
+        ];
+        let watches = Watches::with_root_and_concurrency(rules, env::current_dir().unwrap(), 1);
+
+        let (plan, trigger) = watches
+            .route_plan(&["src/main.rs".to_owned()], &[GitTrigger::HeadChange])
+            .expect("file and git routes");
+        assert_eq!(trigger, "src/main.rs");
+        assert_eq!(
+            plan.task_names(),
+            vec!["build".to_owned(), "deps".to_owned()]
+        );
+
+        let (plan, trigger) = watches
+            .route_plan(&[], &[GitTrigger::HeadChange])
+            .expect("git-only route");
+        assert_eq!(trigger, "git:head_change");
+        assert_eq!(plan.task_names(), vec!["deps".to_owned()]);
+
+        assert!(watches.route_plan(&[], &[GitTrigger::Commit]).is_none());
+        assert!(watches.route_plan(&[], &[]).is_none());
+    }
+
+    #[test]
+    fn affected_plan_unions_every_changed_path_and_reports_why() {
+        let rules = vec![
+            Rules::new(
+                "build".to_owned(),
+                vec!["echo build".to_owned()],
+                vec!["src/**".to_owned()],
+                vec!["src/generated/**".to_owned()],
+                false,
+            ),
+            Rules::new(
+                "docs".to_owned(),
+                vec!["echo docs".to_owned()],
+                vec!["docs/**".to_owned()],
+                vec![],
+                false,
+            ),
+            Rules::new(
+                "lint".to_owned(),
+                vec!["echo lint".to_owned()],
+                vec!["**/*.toml".to_owned()],
+                vec![],
+                false,
+            ),
+        ];
+        let watches = Watches::with_root_and_concurrency(rules, env::current_dir().unwrap(), 1);
+        let paths = vec![
+            "src/main.rs".to_owned(),
+            "docs/a.md".to_owned(),
+            "src/generated/out.rs".to_owned(),
+        ];
+
+        let (plan, affected) = watches.affected_plan(&paths, None).expect("no target");
+        assert_eq!(
+            plan.task_names(),
+            vec!["build".to_owned(), "docs".to_owned()]
+        );
+        assert_eq!(
+            affected,
+            vec![
+                AffectedJob {
+                    name: "docs".to_owned(),
+                    path: "docs/a.md".to_owned(),
+                    change_patterns: vec!["docs/**".to_owned()],
+                },
+                AffectedJob {
+                    name: "build".to_owned(),
+                    path: "src/main.rs".to_owned(),
+                    change_patterns: vec!["src/**".to_owned()],
+                },
+            ]
+        );
+
+        let (plan, _) = watches
+            .affected_plan(&paths, Some("build"))
+            .expect("target exists");
+        assert_eq!(plan.task_names(), vec!["build".to_owned()]);
+
+        let (plan, affected) = watches
+            .affected_plan(&["src/generated/out.rs".to_owned()], None)
+            .expect("no target");
+        assert!(plan.is_empty());
+        assert!(affected.is_empty());
+        assert!(watches.affected_plan(&paths, Some("missing")).is_err());
+    }
+
     #[test]
     fn empty_batch_never_schedules() {
         let rules = vec![Rules::new(
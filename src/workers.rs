@@ -44,6 +44,9 @@ struct RunRequest {
     revision: Option<u64>,
     /// Non-secret semantic hash of the frozen config revision.
     revision_hash: Option<String>,
+    /// `--on-busy restart-affected`: replace only the active run's jobs
+    /// this request schedules again; its other running jobs carry over.
+    restart_affected: bool,
 }
 
 /// Result of an exact-generation cancel (TASK-0046): the generation matched
@@ -144,6 +147,9 @@ impl Scheduler {
 
     fn send(&self, command: WorkerCommand) {
         match &command {
+            // A partial replacement happens at the consumer, which must
+            // find the active run still running.
+            WorkerCommand::Run(req) if req.restart_affected => {}
             WorkerCommand::Run(_) => self.cancel_active(None),
             WorkerCommand::Cancel { generation, .. } => self.cancel_active(*generation),
             _ => {}
@@ -288,6 +294,9 @@ pub struct Worker {
     /// (TASK-0090) swaps it at the commit boundary. Interior mutability so
     /// the reload transaction can swap without rebuilding the worker.
     revision: std::sync::Mutex<Option<crate::config_revision::ConfigRevision>>,
+    /// `--on-busy restart-affected`: a new run cancels only the active
+    /// jobs it schedules again.
+    restart_affected: bool,
 
     consumer: Option<JoinHandle<()>>,
 }
@@ -403,40 +412,42 @@ impl Worker {
         let consumer = std::thread::spawn(move || {
             let mut active: Option<Run> = None;
             let mut pending: Option<RunRequest> = None;
+            // Services the pending run takes over from the one it replaced.
+            let mut kept = crate::executor::KeptServices::default();
+            // Running jobs it takes over under `restart-affected`.
+            let mut carried = crate::executor::CarriedTasks::default();
 
             loop {
                 if active.is_none() {
                     // Promote the newest superseding run, or block on the next
                     // command when idle.
                     if let Some(req) = pending.take() {
-                        active = Some(
-                            executor.start(
-                                RunMetadata::correlated(
-                                    req.run_id,
-                                    req.trigger.clone(),
-                                    req.batch,
-                                    req.predecessor,
-                                    req.changed.clone(),
-                                )
-                                .with_duration_profile(
-                                    req.target.clone(),
-                                    req.execution_signature.clone(),
-                                )
-                                .with_effective_concurrency(req.effective_concurrency)
-                                .with_concurrency_source(req.concurrency_source)
-                                .with_hooks(req.hooks.clone())
-                                .with_recovery_policy(req.recovery_policy)
-                                .with_revision(
-                                    req.revision.unwrap_or(0),
-                                    req.revision_hash.clone().unwrap_or_default(),
-                                ),
-                                req.plan,
+                        let mut run = executor.start_with_services(
+                            RunMetadata::correlated(
+                                req.run_id,
+                                req.trigger.clone(),
+                                req.batch,
+                                req.predecessor,
+                                req.changed.clone(),
+                            )
+                            .with_duration_profile(
+                                req.target.clone(),
+                                req.execution_signature.clone(),
+                            )
+                            .with_effective_concurrency(req.effective_concurrency)
+                            .with_concurrency_source(req.concurrency_source)
+                            .with_hooks(req.hooks.clone())
+                            .with_recovery_policy(req.recovery_policy)
+                            .with_revision(
+                                req.revision.unwrap_or(0),
+                                req.revision_hash.clone().unwrap_or_default(),
                             ),
+                            req.plan,
+                            std::mem::take(&mut kept),
                         );
-                        if let Some(run) = active.as_ref() {
-                            consumer_scheduler
-                                .register_active(run.run_id(), run.cancellation_token());
-                        }
+                        executor.adopt(&mut run, std::mem::take(&mut carried));
+                        consumer_scheduler.register_active(run.run_id(), run.cancellation_token());
+                        active = Some(run);
                         continue;
                     }
 
@@ -532,7 +543,19 @@ impl Worker {
                             let mut replaced = active.take().expect("active run");
                             let replaced_id = replaced.run_id();
                             consumer_scheduler.unregister_active(replaced_id);
-                            executor.cancel(&mut replaced, Some(req.run_id));
+                            // Services whose `on_change` keeps them up are
+                            // handed to the superseding run, not stopped.
+                            kept = executor.keep_services(&mut replaced, &req.plan, req.run_id);
+                            if req.restart_affected {
+                                // Only the jobs the new run schedules again
+                                // are cancelled, each naming their successor;
+                                // the generation itself is not superseded.
+                                carried =
+                                    executor.carry_unaffected(&mut replaced, &req.plan, req.run_id);
+                                executor.replace(&mut replaced, None);
+                            } else {
+                                executor.replace(&mut replaced, Some(req.run_id));
+                            }
                             let mut superseding = req;
                             superseding.predecessor = Some(replaced_id);
                             pending = Some(superseding);
@@ -582,6 +605,10 @@ impl Worker {
                                     _ => break,
                                 }
                             }
+                            if pending.is_none() {
+                                executor.stop_services(std::mem::take(&mut kept));
+                                executor.stop_carried(std::mem::take(&mut carried));
+                            }
                         }
                         Some(WorkerCommand::ReconcileServices { stop_names, reply }) => {
                             // TASK-0090 AC6: stop the named changed/removed
@@ -688,6 +715,7 @@ impl Worker {
             recovery_policy: std::sync::Mutex::new(crate::config::RecoveryPolicy::Prompt),
             hooks: std::sync::Mutex::new(crate::config::GenerationHooks::default()),
             revision: std::sync::Mutex::new(None),
+            restart_affected: false,
             consumer: Some(consumer),
         }
     }
@@ -747,6 +775,17 @@ impl Worker {
         *self.hooks.lock().unwrap() = hooks;
     }
 
+    /// Selects `--on-busy restart-affected` for runs scheduled from now on.
+    pub fn with_restart_affected(mut self, restart_affected: bool) -> Self {
+        self.restart_affected = restart_affected;
+        self
+    }
+
+    /// Whether a new run replaces only the active jobs it schedules again.
+    pub fn restarts_affected(&self) -> bool {
+        self.restart_affected
+    }
+
     /// Binds the immutable config revision all plans prepared through this
     /// worker are frozen under (TASK-0089, CONFIG-RELOAD-CONTRACT §4).
     pub fn with_revision(self, revision: crate::config_revision::ConfigRevision) -> Self {
@@ -764,8 +803,9 @@ impl Worker {
     /// Cancels an exact generation through the worker command stream
     /// (TASK-0046): a compare-and-act on generation identity. Returns whether
     /// the generation matched (active or queued) and how it terminated, or a
-    /// no-op when it was already terminal or unknown. Bounded by the shutdown
-    /// grace plus a margin; the consumer always replies.
+    /// no-op when it was already terminal or unknown. Bounded by the longest
+    /// stop grace and the `when: always` jobs' bound plus a margin; the
+    /// consumer always replies.
     pub fn cancel_generation(&self, generation: u64) -> Result<CancelResult, String> {
         let Some(scheduler) = self.scheduler.as_ref() else {
             return Err("worker scheduler is unavailable".to_string());
@@ -775,8 +815,9 @@ impl Worker {
             generation: Some(generation),
             reply: Some(reply),
         });
-        let (_, grace) = crate::process_owner::shutdown_policy();
-        let bound = grace + Duration::from_secs(5);
+        let bound = crate::process_owner::longest_grace()
+            + crate::executor::ALWAYS_TIMEOUT
+            + Duration::from_secs(5);
         receipt
             .recv_timeout(bound)
             .map_err(|_| "cancellation acknowledgement timed out".to_string())
@@ -922,6 +963,7 @@ impl Worker {
             recovery_policy: *self.recovery_policy.lock().unwrap(),
             revision: revision.as_ref().map(|r| r.number),
             revision_hash: revision.as_ref().map(|r| r.hash.clone()),
+            restart_affected: self.restart_affected,
         })
     }
 
@@ -930,15 +972,14 @@ impl Worker {
     /// (changed/removed by the new revision) and returns the names still
     /// running. The caller starts the new/changed services under the new
     /// revision; unchanged services remain owned. Synchronous, bounded by the
-    /// shutdown grace plus a margin.
+    /// longest stop grace plus a margin.
     pub fn reconcile_services(&self, stop_names: Vec<String>) -> Result<Vec<String>, String> {
         let Some(scheduler) = self.scheduler.as_ref() else {
             return Err("worker scheduler is unavailable".to_string());
         };
         let (reply, receipt) = std::sync::mpsc::channel();
         scheduler.send(WorkerCommand::ReconcileServices { stop_names, reply });
-        let (_, grace) = crate::process_owner::shutdown_policy();
-        let bound = grace + Duration::from_secs(5);
+        let bound = crate::process_owner::longest_grace() + Duration::from_secs(5);
         receipt
             .recv_timeout(bound)
             .map_err(|_| "service reconciliation timed out".to_string())
@@ -1800,6 +1841,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn superseding_run_keeps_services_that_ignore_changes() {
+        let started = output_file("kept-service");
+        let _ = std::fs::remove_file(&started);
+        let service = || {
+            Rules::new(
+                "svc".to_string(),
+                vec![format!("echo up >> {}; sleep 5", started.display())],
+                vec![],
+                vec![],
+                false,
+            )
+            .with_service(true)
+            .with_on_change(crate::rules::OnChange::Ignore)
+        };
+        let (worker, rx) = worker_with_events(false, false);
+        let first = worker.schedule(vec![service()], "a.txt").unwrap();
+        expect_event(
+            &rx,
+            "first run to start",
+            |e| matches!(e, WorkerEvent::Started { run_id, .. } if *run_id == first),
+        );
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while !started.exists() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(20));
+        }
+
+        let second = worker
+            .schedule(vec![service(), rule(vec!["echo ok"])], "b.txt")
+            .unwrap();
+        expect_event(
+            &rx,
+            "superseding run to start",
+            |e| matches!(e, WorkerEvent::Started { run_id, .. } if *run_id == second),
+        );
+        std::thread::sleep(Duration::from_millis(300));
+        drop(worker);
+
+        let spawns = std::fs::read_to_string(&started).unwrap();
+        let _ = std::fs::remove_file(&started);
+        assert_eq!(spawns.lines().count(), 1, "the service is handed over");
+    }
+
     #[test]
     fn discarded_queued_generation_reports_superseded_terminal() {
         let (worker, rx) = worker_with_events(false, false);
@@ -101,6 +101,12 @@ impl WorkflowRunner {
             (None, Some(events)) => events,
             (None, None) => Arc::new(|_| {}),
         };
+        // Generation lifetimes feed `--record-events` traces.
+        let events: Arc<dyn crate::executor::EventSink> =
+            Arc::new(move |event: crate::executor::Event| {
+                crate::event_trace::observe(&event);
+                events.emit(event);
+            });
         let executor = Executor::new(
             Arc::new(SystemProcessRunner),
             Arc::new(SystemClock),
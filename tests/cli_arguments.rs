@@ -424,6 +424,61 @@ fn explain_ignored_path_names_winning_ignore_rule() {
         .stdout(predicate::str::contains("matched:").not());
 }
 
+#[test]
+fn explain_reports_editor_noise_as_suppressed_or_mapped() {
+    fzz()
+        .args([
+            "-c",
+            FILTER_EXAMPLE,
+            "explain",
+            "examples/workdir/.foo.txt.swp",
+        ])
+        .assert()
+        .code(0)
+        .stdout(predicate::str::contains(
+            "editor noise: vim artifact, suppressed (on.editor_noise)",
+        ))
+        .stdout(predicate::str::contains("matched:").not());
+
+    fzz()
+        .args([
+            "-c",
+            FILTER_EXAMPLE,
+            "explain",
+            "examples/workdir/foo.txt___jb_tmp___",
+        ])
+        .assert()
+        .code(0)
+        .stdout(predicate::str::contains(
+            "editor noise: jetbrains atomic save of examples/workdir/foo.txt",
+        ))
+        .stdout(predicate::str::contains("run my test @quick"));
+}
+
+#[test]
+fn explain_shows_each_jobs_stop_signal_and_grace() {
+    with_tmp_dir("explain-stop", |dir| {
+        let config = dir.join(".watch.yaml");
+        std::fs::write(
+            &config,
+            "jobs:\n  - name: server\n    run: ./serve\n    change: 'src/**'\n    stop_signal: SIGINT\n    stop_grace: 20s\n  - name: test\n    run: make test\n    change: 'src/**'\n",
+        )
+        .expect("write config");
+        fzz()
+            .env_remove("FUNZZY_CANCEL_SIGNAL")
+            .env_remove("FUNZZY_CANCEL_GRACE_MS")
+            .args(["-c", config.to_str().unwrap(), "explain", "src/main.rs"])
+            .assert()
+            .code(0)
+            .stdout(predicate::str::contains(
+                "stop: SIGINT then SIGKILL after 20s",
+            ))
+            .stdout(predicate::str::contains(
+                "stop: SIGTERM then SIGKILL after 5s",
+            ));
+    });
+}
+
 #[test]
 fn explain_unmatched_path_is_informative() {
     // No rule watches the path: explicit unmatched message, exit 0.
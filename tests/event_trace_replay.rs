@@ -0,0 +1,182 @@
+//! Black-box contract for event traces: `--record-events` writes every
+//! watcher batch, and `fzz replay` routes the trace through the watch loop
+//! as a dry run, in any checkout of the project.
+
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+const CONFIG: &str = "on:\n  watch_backend: stdin\n  debounce: 100ms\njobs:\n  - name: build\n    change: 'src/**'\n    run: 'touch built.txt'\n  - name: docs\n    change: 'docs/**'\n    run: 'touch docs.txt'\n";
+
+fn fixture(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!(
+        "funzzy-event-trace-{}-{}",
+        std::process::id(),
+        name
+    ));
+    let _ = std::fs::remove_dir_all(&directory);
+    std::fs::create_dir_all(directory.join("src")).expect("create fixture");
+    std::fs::write(directory.join(".watch.yaml"), CONFIG).unwrap();
+    std::fs::canonicalize(&directory).expect("canonical fixture")
+}
+
+fn wait_for(path: &Path) -> bool {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while Instant::now() < deadline {
+        if path.exists() {
+            return true;
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    false
+}
+
+fn record(directory: &Path, trace: &Path) {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_fzz"))
+        .arg("--record-events")
+        .arg(trace)
+        .current_dir(directory)
+        .env("FUNZZY_COLORED", "false")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("spawn fzz");
+    let stdout = child.stdout.take().unwrap();
+    let (ready_tx, ready_rx) = std::sync::mpsc::channel();
+    let reader = std::thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            if line.unwrap_or_default().contains("Watching...") {
+                let _ = ready_tx.send(());
+            }
+        }
+    });
+    ready_rx
+        .recv_timeout(Duration::from_secs(10))
+        .expect("watcher ready");
+
+    std::fs::write(directory.join("src/lib.rs"), "// changed\n").unwrap();
+    let mut stdin = child.stdin.take().unwrap();
+    writeln!(stdin, "src/lib.rs").unwrap();
+    writeln!(stdin, "src/.lib.rs.swp").unwrap();
+    assert!(
+        wait_for(&directory.join("built.txt")),
+        "event did not route"
+    );
+    drop(stdin);
+    assert!(child.wait().unwrap().success());
+    reader.join().unwrap();
+}
+
+#[test]
+fn recorded_trace_replays_as_a_dry_run_in_another_checkout() {
+    let recorded = fixture("recorded");
+    let trace = recorded.join("trace.ndjson");
+    record(&recorded, &trace);
+
+    let lines: Vec<String> = std::fs::read_to_string(&trace)
+        .unwrap()
+        .lines()
+        .map(str::to_owned)
+        .collect();
+    assert_eq!(
+        lines.len(),
+        3,
+        "header, one batch and its generation: {lines:?}"
+    );
+    assert!(lines[0].contains("\"version\":1"), "{}", lines[0]);
+    assert!(lines[1].contains("\"t_ms\":"), "{}", lines[1]);
+    assert!(
+        lines[1].contains(".lib.rs.swp"),
+        "raw events kept: {}",
+        lines[1]
+    );
+    assert!(
+        lines[2].contains("\"batch\":1") && lines[2].contains("\"duration_ms\":"),
+        "generation recorded: {}",
+        lines[2]
+    );
+
+    // Replay in a fresh checkout: paths re-anchor onto its root and the
+    // swap file is still suppressed by the current config.
+    let replayed = fixture("replayed");
+    let output = Command::new(env!("CARGO_BIN_EXE_fzz"))
+        .arg("replay")
+        .arg(&trace)
+        .current_dir(&replayed)
+        .env("FUNZZY_COLORED", "false")
+        .output()
+        .expect("run fzz replay");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    assert!(stdout.contains("batch 1: 2 event(s)"), "{stdout}");
+    assert!(
+        stdout.contains(&format!(
+            "generation 1 ({}): build",
+            replayed.join("src/lib.rs").display()
+        )),
+        "{stdout}"
+    );
+    assert!(stdout.contains("Replay finished: 1 batch(es)."), "{stdout}");
+    assert!(!stdout.contains("generation 2"), "{stdout}");
+    assert!(
+        !replayed.join("built.txt").exists(),
+        "replay must not run jobs"
+    );
+
+    std::fs::remove_dir_all(recorded).unwrap();
+    std::fs::remove_dir_all(replayed).unwrap();
+}
+
+fn replay(directory: &Path, trace: &str, policy: &str) -> String {
+    let path = directory.join("trace.ndjson");
+    std::fs::write(&path, trace).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_fzz"))
+        .args(["--on-busy", policy, "replay"])
+        .arg(&path)
+        .current_dir(directory)
+        .env("FUNZZY_COLORED", "false")
+        .output()
+        .expect("run fzz replay");
+    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    assert!(output.status.success(), "{stdout}");
+    stdout
+}
+
+#[test]
+fn replay_keeps_recorded_batches_and_busy_policy_on_the_recorded_clock() {
+    let directory = fixture("clock");
+    let trace = concat!(
+        "{\"version\":1,\"root\":\"/recorded\"}\n",
+        "{\"t_ms\":0,\"batch\":1,\"events\":[{\"path\":\"/recorded/src/a.rs\",\"kind\":\"any\"}],\"normalized\":[]}\n",
+        "{\"t_ms\":0,\"generation\":1,\"batch\":1,\"duration_ms\":1000}\n",
+        "{\"t_ms\":300,\"batch\":2,\"events\":[{\"path\":\"/recorded/docs/a.md\",\"kind\":\"any\"}],\"normalized\":[]}\n",
+        "{\"t_ms\":350,\"batch\":3,\"events\":[{\"path\":\"/recorded/src/b.rs\",\"kind\":\"any\"}],\"normalized\":[]}\n",
+    );
+
+    // Batches 2 and 3 are 50ms apart, inside the 100ms debounce, yet the
+    // watcher delivered them as two: they stay two generations.
+    let restarted = replay(&directory, trace, "restart");
+    for line in [
+        "+300ms batch 2: 1 event(s)",
+        "docs (supersedes generation 1)",
+        "+350ms batch 3: 1 event(s)",
+        "generation 3",
+        "Replay finished: 3 batch(es).",
+    ] {
+        assert!(restarted.contains(line), "{line}: {restarted}");
+    }
+
+    let waited = replay(&directory, trace, "wait");
+    assert!(
+        waited.contains("docs (waits 700ms for generation 1)"),
+        "{waited}"
+    );
+    assert!(
+        waited.contains("build (waits 650ms for generation 2)"),
+        "{waited}"
+    );
+
+    std::fs::remove_dir_all(directory).unwrap();
+}
@@ -0,0 +1,93 @@
+//! Black-box contract for the external event source (`watch_backend: stdin`):
+//! newline-delimited paths on stdin drive the same routing as native events,
+//! and closing stdin ends the watch session.
+
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+fn fixture(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!(
+        "funzzy-external-source-{}-{}",
+        std::process::id(),
+        name
+    ));
+    let _ = std::fs::remove_dir_all(&directory);
+    std::fs::create_dir_all(directory.join("src")).expect("create fixture");
+    std::fs::canonicalize(&directory).expect("canonical fixture")
+}
+
+fn wait_for(path: &Path) -> bool {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while Instant::now() < deadline {
+        if path.exists() {
+            return true;
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    false
+}
+
+#[test]
+fn stdin_paths_route_like_filesystem_events_and_eof_stops_the_watcher() {
+    let directory = fixture("stdin");
+    std::fs::write(
+        directory.join(".watch.yaml"),
+        "on:\n  watch_backend: stdin\n  debounce: 100ms\njobs:\n  - name: build\n    change: 'src/**'\n    run: 'printf {{relative_filepath}} > built.txt'\n  - name: docs\n    change: 'docs/**'\n    run: 'touch docs.txt'\n",
+    )
+    .unwrap();
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_fzz"))
+        .current_dir(&directory)
+        .env("FUNZZY_COLORED", "false")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("spawn fzz");
+    let stdout = child.stdout.take().unwrap();
+    let (ready_tx, ready_rx) = std::sync::mpsc::channel();
+    let reader = std::thread::spawn(move || {
+        let mut output = String::new();
+        for line in BufReader::new(stdout).lines() {
+            let line = line.unwrap_or_default();
+            if line.contains("Watching...") {
+                let _ = ready_tx.send(());
+            }
+            output.push_str(&line);
+            output.push('\n');
+        }
+        output
+    });
+    ready_rx
+        .recv_timeout(Duration::from_secs(10))
+        .expect("watcher ready");
+
+    std::fs::write(directory.join("src/lib.rs"), "// changed\n").unwrap();
+    let mut stdin = child.stdin.take().unwrap();
+    writeln!(stdin, "src/lib.rs").unwrap();
+    assert!(
+        wait_for(&directory.join("built.txt")),
+        "stdin event did not route"
+    );
+    drop(stdin);
+
+    let deadline = Instant::now() + Duration::from_secs(10);
+    let status = loop {
+        if let Some(status) = child.try_wait().unwrap() {
+            break status;
+        }
+        assert!(Instant::now() < deadline, "watcher kept running after EOF");
+        std::thread::sleep(Duration::from_millis(50));
+    };
+    let output = reader.join().unwrap();
+    assert!(status.success(), "{output}");
+    assert!(output.contains("Event source closed."), "{output}");
+    assert_eq!(
+        std::fs::read_to_string(directory.join("built.txt")).unwrap(),
+        "src/lib.rs"
+    );
+    assert!(!directory.join("docs.txt").exists());
+    std::fs::remove_dir_all(directory).unwrap();
+}
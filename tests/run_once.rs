@@ -473,6 +473,202 @@ fn run_events_flag_writes_ndjson_stream() {
     std::fs::remove_dir_all(directory).unwrap();
 }
 
+#[test]
+fn problem_matchers_emit_structured_diagnostics_events() {
+    let directory = fixture("matchers");
+    write_config(
+        &directory,
+        "on:\n  change: '**/*'\njobs:\n  - name: vet\n    run: \"printf './main.go:10:2: unreachable code\\n' >&2; exit 1\"\n    matchers: go-vet\n",
+    );
+    let events_path = directory.join("run-events.ndjson");
+
+    fzz(&directory)
+        .args(["run", "vet", "--events", events_path.to_str().unwrap()])
+        .assert()
+        .code(1);
+
+    let content = std::fs::read_to_string(&events_path).expect("events file");
+    let record: serde_json::Value = content
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).expect("valid ndjson"))
+        .find(|record| record["event"] == "diagnostics")
+        .unwrap_or_else(|| panic!("diagnostics record: {content}"));
+    assert_eq!(record["task"], "vet");
+    assert_eq!(
+        record["diagnostics"],
+        serde_json::json!([{
+            "task": "vet",
+            "matcher": "go-vet",
+            "file": "./main.go",
+            "line": 10,
+            "column": 2,
+            "severity": "error",
+            "message": "unreachable code",
+            "code": null,
+        }])
+    );
+    std::fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn test_reports_summarise_tap_and_junit_results() {
+    let directory = fixture("test-reports");
+    std::fs::write(
+        directory.join("tap.sh"),
+        "printf '1..3\\nok 1 - parses\\nnot ok 2 - rejects empty\\nok 3 - slow # SKIP offline\\n'\n",
+    )
+    .unwrap();
+    std::fs::write(
+        directory.join("junit.sh"),
+        "mkdir -p out && cat > out/junit.xml <<'EOF'\n<testsuite><testcase classname=\"api\" name=\"lists\"/><testcase classname=\"api\" name=\"creates\"><failure message=\"expected 201\"/></testcase></testsuite>\nEOF\n",
+    )
+    .unwrap();
+    write_config(
+        &directory,
+        "on:\n  change: '**/*'\njobs:\n  - name: unit @tests\n    run: sh tap.sh\n    reports:\n      tap: stdout\n  - name: api @tests\n    run: sh junit.sh\n    reports:\n      junit: 'out/**/*.xml'\n",
+    );
+    let events_path = directory.join("run-events.ndjson");
+
+    let output = fzz(&directory)
+        .args(["run", "@tests", "--events", events_path.to_str().unwrap()])
+        .output()
+        .expect("run fzz");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Tests unit @tests: 1 passed, 1 failed, 1 skipped"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("  FAILED rejects empty"), "{stdout}");
+    assert!(
+        stdout.contains("Tests api @tests: 1 passed, 1 failed, 0 skipped"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("  FAILED api::creates: expected 201"),
+        "{stdout}"
+    );
+
+    let content = std::fs::read_to_string(&events_path).expect("events file");
+    let record: serde_json::Value = content
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).expect("valid ndjson"))
+        .find(|record| record["event"] == "task_terminal" && record["task"] == "api @tests")
+        .unwrap_or_else(|| panic!("terminal record: {content}"));
+    assert_eq!(
+        record["tests"],
+        serde_json::json!({
+            "passed": 1,
+            "failed": 1,
+            "skipped": 0,
+            "failures": [{ "name": "api::creates", "message": "expected 201" }],
+        })
+    );
+    std::fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn allowed_failures_warn_without_failing_the_run() {
+    let directory = fixture("allow-failure");
+    write_config(
+        &directory,
+        "hooks:\n  success: 'touch success.txt'\n  failure: 'touch failure.txt'\non:\n  change: '**/*'\njobs:\n  - name: audit @checks\n    run: 'exit 3'\n    allow_failure: true\n  - name: lint @checks\n    run: 'exit 1'\n    success_codes: [0, 1]\n  - name: test @checks\n    run: 'true'\n",
+    );
+
+    let output = fzz(&directory)
+        .args(["--fail-fast", "run", "@checks"])
+        .output()
+        .expect("run fzz");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(0), "{stdout}");
+    let row = |job: &str| {
+        stdout
+            .lines()
+            .find(|line| line.starts_with(job))
+            .unwrap_or_else(|| panic!("{job} row: {stdout}"))
+            .to_owned()
+    };
+    assert!(row("audit @checks").contains("warning"), "{stdout}");
+    assert!(row("lint @checks").contains("passed"), "{stdout}");
+    assert!(row("test @checks").contains("passed"), "{stdout}");
+    assert!(directory.join("success.txt").exists());
+    assert!(!directory.join("failure.txt").exists());
+    std::fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn when_jobs_run_after_fail_fast_failures() {
+    let directory = fixture("when-jobs");
+    write_config(
+        &directory,
+        "on:\n  change: '**/*'\njobs:\n  - name: test @ci\n    run: 'false'\n  - name: deploy @ci\n    run: 'touch deployed.txt'\n  - name: logs @ci\n    run: 'echo collected evidence'\n    when: on_failure\n  - name: down @ci\n    run: 'touch down.txt'\n    when: always\n",
+    );
+
+    let output = fzz(&directory)
+        .args(["--fail-fast", "run", "@ci"])
+        .output()
+        .expect("run fzz");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(1), "{stdout}");
+    assert!(stdout.contains("collected evidence"), "{stdout}");
+    assert!(!directory.join("deployed.txt").exists());
+    assert!(directory.join("down.txt").exists());
+    std::fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn multi_command_jobs_report_steps_under_the_job() {
+    let directory = fixture("job-steps");
+    write_config(
+        &directory,
+        "on:\n  change: '**/*'\njobs:\n  - name: ci @ci\n    run:\n      - 'echo built'\n      - run: 'echo lint warning; exit 3'\n        continue_on_error: true\n      - 'echo tested'\n",
+    );
+    let events_path = directory.join("steps.ndjson");
+
+    let output = fzz(&directory)
+        .args(["run", "@ci", "--events", events_path.to_str().unwrap()])
+        .output()
+        .expect("run fzz");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(0), "{stdout}");
+    let row = |prefix: &str| {
+        stdout
+            .lines()
+            .find(|line| line.starts_with(prefix))
+            .unwrap_or_else(|| panic!("{prefix}: {stdout}"))
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    assert!(row("ci @ci").starts_with("ci @ci passed"), "{stdout}");
+    assert!(row("  1.").starts_with("1. echo built passed"), "{stdout}");
+    assert!(
+        row("  2.").starts_with("2. echo lint warning; exit 3 warning (3)"),
+        "{stdout}"
+    );
+    assert!(row("  3.").starts_with("3. echo tested passed"), "{stdout}");
+
+    let content = std::fs::read_to_string(&events_path).expect("events file");
+    let record: serde_json::Value = content
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).expect("valid ndjson"))
+        .find(|record| record["event"] == "task_terminal" && record["task"] == "ci @ci")
+        .unwrap_or_else(|| panic!("terminal record: {content}"));
+    let steps = record["steps"].as_array().expect("steps");
+    assert_eq!(
+        steps
+            .iter()
+            .map(|step| (step["state"].clone(), step["exitCode"].clone()))
+            .collect::<Vec<_>>(),
+        [
+            (serde_json::json!("passed"), serde_json::json!(0)),
+            (serde_json::json!("warning"), serde_json::json!(3)),
+            (serde_json::json!("passed"), serde_json::json!(0)),
+        ]
+    );
+    assert_eq!(steps[1]["command"], "echo lint warning; exit 3");
+    std::fs::remove_dir_all(directory).unwrap();
+}
+
 #[test]
 fn current_run_rows_match_terminal_events_and_both_binary_aliases() {
     // Current-run rows are human projections of executor terminal snapshots.
@@ -662,6 +858,42 @@ fn show_on_failure_reveals_buffered_output_exactly_once() {
     std::fs::remove_dir_all(&directory).unwrap();
 }
 
+#[test]
+fn grouped_output_prints_each_parallel_job_as_one_block() {
+    // Parallel jobs under `output: grouped` hold their lines and print them
+    // contiguously under a header when each finishes; serial jobs stream.
+    let directory = fixture("grouped-output");
+    write_config(
+        &directory,
+        "on:\n  change: '**/*'\nexecution:\n  concurrency: 2\n  output: grouped\njobs:\n  - name: lint @checks\n    parallel: checks\n    run: 'echo lint-1; sleep 0.3; echo lint-2'\n  - name: test @checks\n    parallel: checks\n    run: 'sleep 0.15; echo test-1; sleep 0.3; echo test-2'\n  - name: docs @checks\n    run: 'echo docs-1'\n",
+    );
+
+    let output = fzz(&directory)
+        .args(["run", "@checks"])
+        .output()
+        .expect("run");
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    for job in ["lint", "test"] {
+        let block = format!("--- {job} @checks (passed, ");
+        let start = stdout
+            .find(&block)
+            .unwrap_or_else(|| panic!("{job} block header: {stdout}"));
+        let body: Vec<&str> = stdout[start..].lines().skip(1).take(2).collect();
+        assert_eq!(
+            body,
+            [format!("{job}-1"), format!("{job}-2")],
+            "{job} lines are contiguous: {stdout}"
+        );
+    }
+    assert!(
+        !stdout.contains("--- docs @checks"),
+        "serial jobs stream without a block: {stdout}"
+    );
+    assert!(stdout.contains("docs-1"), "{stdout}");
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
 #[test]
 fn capture_policy_holds_output_for_retrieval() {
     // TASK-0041: `output: capture` does not stream live; output is held
@@ -781,3 +1013,104 @@ fn service_task_runs_across_generations_and_shuts_down() {
     let _ = child.wait();
     std::fs::remove_dir_all(&directory).unwrap();
 }
+
+#[test]
+fn paths_from_runs_only_affected_jobs_and_names_the_reason() {
+    let directory = fixture("paths-from");
+    write_config(
+        &directory,
+        "jobs:\n  - name: build\n    change: 'src/**'\n    run: 'printf build > build.txt'\n  - name: docs\n    change: 'docs/**'\n    run: 'printf docs > docs.txt'\n",
+    );
+
+    fzz(&directory)
+        .args(["run", "--paths-from", "-"])
+        .write_stdin("src/main.rs\nREADME.md\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Selected 1 job(s) from 2 changed path(s):",
+        ))
+        .stdout(predicate::str::contains(
+            "build: src/main.rs (change: src/**)",
+        ));
+
+    assert!(directory.join("build.txt").exists());
+    assert!(!directory.join("docs.txt").exists());
+
+    fzz(&directory)
+        .args(["run", "--paths-from", "-"])
+        .write_stdin("README.md\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "No jobs affected by 1 changed path(s).",
+        ));
+    std::fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn changed_since_reads_the_git_diff() {
+    let directory = fixture("changed-since");
+    let git = |args: &[&str]| {
+        let status = Command::new("git")
+            .args(args)
+            .current_dir(&directory)
+            .env("GIT_AUTHOR_NAME", "fzz")
+            .env("GIT_AUTHOR_EMAIL", "fzz@example.com")
+            .env("GIT_COMMITTER_NAME", "fzz")
+            .env("GIT_COMMITTER_EMAIL", "fzz@example.com")
+            .status()
+            .expect("run git");
+        assert!(status.success(), "git {:?}", args);
+    };
+    write_config(
+        &directory,
+        "jobs:\n  - name: build\n    change: 'src/**'\n    run: 'printf build > build.txt'\n  - name: docs\n    change: 'docs/**'\n    run: 'printf docs > docs.txt'\n  - name: notes\n    change: 'notes/**'\n    run: 'true'\n  - name: tools\n    change: 'tools/**'\n    run: 'true'\n",
+    );
+    std::fs::create_dir_all(directory.join("src")).unwrap();
+    std::fs::create_dir_all(directory.join("docs")).unwrap();
+    std::fs::create_dir_all(directory.join("notes")).unwrap();
+    std::fs::create_dir_all(directory.join("tools")).unwrap();
+    std::fs::write(directory.join("src/main.rs"), "fn main() {}\n").unwrap();
+    std::fs::write(directory.join("docs/a.md"), "a\n").unwrap();
+    std::fs::write(directory.join("notes/café.md"), "a\n").unwrap();
+    git(&["init", "-q"]);
+    git(&["add", "."]);
+    git(&["commit", "-qm", "base"]);
+    git(&["branch", "-q", "base"]);
+    // A change that landed only on the base branch is not affected.
+    git(&["checkout", "-q", "-b", "feature"]);
+    git(&["checkout", "-q", "base"]);
+    std::fs::write(directory.join("src/main.rs"), "fn main() { }\n").unwrap();
+    git(&["commit", "-qam", "base only"]);
+    git(&["checkout", "-q", "feature"]);
+    std::fs::write(directory.join("docs/a.md"), "b\n").unwrap();
+    // Non-ASCII names arrive unquoted, and untracked files count.
+    std::fs::write(directory.join("notes/café.md"), "b\n").unwrap();
+    std::fs::write(directory.join("tools/new.sh"), "true\n").unwrap();
+
+    fzz(&directory)
+        .args(["run", "--changed-since", "base"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "docs: docs/a.md (change: docs/**)",
+        ))
+        .stdout(predicate::str::contains(
+            "notes: notes/café.md (change: notes/**)",
+        ))
+        .stdout(predicate::str::contains(
+            "tools: tools/new.sh (change: tools/**)",
+        ))
+        .stdout(predicate::str::contains("build:").not());
+
+    assert!(directory.join("docs.txt").exists());
+    assert!(!directory.join("build.txt").exists());
+
+    fzz(&directory)
+        .args(["run", "--changed-since", "no-such-ref"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("Cannot list changed paths"));
+    std::fs::remove_dir_all(directory).unwrap();
+}